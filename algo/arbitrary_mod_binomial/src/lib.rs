@@ -0,0 +1,179 @@
+use ext_gcd::ext_gcd;
+use prime_factorization::PrimeFactorization;
+
+/// 素数とは限らない `modulo` を法として二項係数を計算します。
+///
+/// `modulo` を素数べき `p^e` の積に分解し、それぞれを法とした値を [Granville の方法](https://web.archive.org/web/20170202003812/http://www.dms.umontreal.ca/~andrew/PDF/BinCoeff.pdf) で求めてから中国剰余定理で復元します。
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary_mod_binomial::ArbitraryModBinomial;
+///
+/// // 12 = 2^2 * 3
+/// let binom = ArbitraryModBinomial::new(12);
+/// assert_eq!(binom.binomial(4, 2), 6);
+/// assert_eq!(binom.binomial(6, 3), 20 % 12);
+/// assert_eq!(binom.binomial(3, 4), 0);
+///
+/// let binom = ArbitraryModBinomial::new(1_000_000);
+/// // C(10^18, 10^9) mod 10^6
+/// assert_eq!(binom.binomial(1_000_000_000_000_000_000, 1_000_000_000), 0);
+/// ```
+pub struct ArbitraryModBinomial {
+    modulo: u64,
+    prime_powers: Vec<PrimePower>,
+}
+
+struct PrimePower {
+    p: u64,
+    e: u32,
+    q: u64,
+    // table[i]: p で割り切れない 1 以上 i 以下の整数の積 (mod q)
+    table: Vec<u64>,
+}
+
+impl ArbitraryModBinomial {
+    /// 前計算に O(`modulo`) 時間・空間かかります。
+    ///
+    /// # Panics
+    ///
+    /// `modulo` が `0` または `2^32` 以上の場合パニックです。
+    pub fn new(modulo: u64) -> Self {
+        assert!(modulo >= 1);
+        assert!(modulo <= u64::from(u32::MAX));
+        let prime_powers = modulo
+            .prime_factorization()
+            .into_iter()
+            .map(|(p, e)| {
+                let q = p.pow(e as u32);
+                let mut table = vec![1; q as usize];
+                for i in 1..q {
+                    table[i as usize] = if i % p == 0 {
+                        table[i as usize - 1]
+                    } else {
+                        table[i as usize - 1] * i % q
+                    };
+                }
+                PrimePower {
+                    p,
+                    e: e as u32,
+                    q,
+                    table,
+                }
+            })
+            .collect();
+        Self {
+            modulo,
+            prime_powers,
+        }
+    }
+
+    /// 二項係数 (mod `modulo`) を返します。`n` が `k` より小さいときは `0` です。
+    ///
+    /// `modulo` の素因数分解を `p_1^e_1 * p_2^e_2 * ...` として O(Σ log_{p_i}(`n`)) 時間です。
+    pub fn binomial(&self, n: u64, k: u64) -> u64 {
+        if n < k {
+            return 0;
+        }
+        let (mut r, mut m) = (0, 1);
+        for pp in &self.prime_powers {
+            let x = pp.binomial(n, k);
+            (r, m) = crt(r, m, x, pp.q);
+        }
+        debug_assert_eq!(m, self.modulo);
+        r % self.modulo
+    }
+}
+
+impl PrimePower {
+    fn binomial(&self, n: u64, k: u64) -> u64 {
+        let ex = legendre(n, self.p) - legendre(k, self.p) - legendre(n - k, self.p);
+        if ex >= u64::from(self.e) {
+            return 0;
+        }
+        let numer = self.factorial_without_p(n);
+        let denom = self.factorial_without_p(k) * self.factorial_without_p(n - k) % self.q;
+        numer * inv(denom, self.q) % self.q * self.p.pow(ex as u32) % self.q
+    }
+
+    // n! から p の因数をすべて取り除いた値 (mod q)
+    fn factorial_without_p(&self, n: u64) -> u64 {
+        let full = self.table[(self.q - 1) as usize]; // 1 or q - 1
+        let mut result = 1;
+        let mut n = n;
+        while n > 0 {
+            result = result * self.table[(n % self.q) as usize] % self.q;
+            if (n / self.q) % 2 == 1 {
+                result = result * full % self.q;
+            }
+            n /= self.p;
+        }
+        result
+    }
+}
+
+// n! を割り切る p の最大べき
+fn legendre(n: u64, p: u64) -> u64 {
+    let mut result = 0;
+    let mut n = n;
+    while n > 0 {
+        n /= p;
+        result += n;
+    }
+    result
+}
+
+fn inv(a: u64, m: u64) -> u64 {
+    let (x, _, g) = ext_gcd(a as i64, m as i64);
+    debug_assert_eq!(g, 1);
+    x.rem_euclid(m as i64) as u64
+}
+
+// x = r1 (mod m1), x = r2 (mod m2) を満たす x (mod m1 * m2)
+fn crt(r1: u64, m1: u64, r2: u64, m2: u64) -> (u64, u64) {
+    // x = r1 + m1 * t
+    // m1 * t = r2 - r1 (mod m2)
+    let diff = (r2 + m2 - r1 % m2) % m2;
+    let t = diff * inv(m1 % m2, m2) % m2;
+    (r1 + m1 * t, m1 * m2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArbitraryModBinomial;
+    use pascal_triangle::pascal_triangle;
+
+    #[test]
+    fn test_small_modulo() {
+        for m in 1..=100 {
+            let binom = ArbitraryModBinomial::new(m);
+            let expected = pascal_triangle(50, m);
+            for (n, row) in expected.iter().enumerate() {
+                for (k, &e) in row.iter().enumerate() {
+                    assert_eq!(
+                        binom.binomial(n as u64, k as u64),
+                        e % m,
+                        "m = {}, n = {}, k = {}",
+                        m,
+                        n,
+                        k
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_prime_power_modulo() {
+        // 2^6 * 5^6
+        let m = 1_000_000;
+        let binom = ArbitraryModBinomial::new(m);
+        let expected = pascal_triangle(100, m);
+        for (n, row) in expected.iter().enumerate() {
+            for (k, &e) in row.iter().enumerate() {
+                assert_eq!(binom.binomial(n as u64, k as u64), e);
+            }
+        }
+    }
+}
@@ -25,13 +25,167 @@ pub fn arithmetic_series<T: Int>(a: T, n: T, d: T) -> Option<T> {
     a.checked_add(last)?.checked_mul(n)?.checked_div(T::two())
 }
 
+/// 初項 `a`, 項数 `n`, 公比 `r` の等比数列の和 `a + ar + ar^2 + ... + ar^(n-1)` を求めます。
+///
+/// 計算途中でオーバーフローした場合は `None` を返します。
+///
+/// # Panics
+/// if `n` is negative.
+///
+/// # Examples
+/// ```
+/// use arithmetic_series::geometric_series;
+///
+/// // 1 + 2 + 4 + 8 + 16
+/// assert_eq!(geometric_series(1, 2, 5), Some(31));
+/// // 3 + (-3) + 3 + (-3) + 3
+/// assert_eq!(geometric_series(3, -1, 5), Some(3));
+/// // 7 + 7 + ... + 7
+/// assert_eq!(geometric_series(7_i64, 1, 1_000_000_000_000), Some(7_000_000_000_000));
+/// assert_eq!(geometric_series(1, 2, 64), None::<i64>);
+/// ```
+pub fn geometric_series<T: Int>(a: T, r: T, n: T) -> Option<T> {
+    if n == T::zero() {
+        return Some(T::zero());
+    }
+
+    assert!(n.is_positive());
+
+    if a == T::zero() {
+        return Some(T::zero());
+    }
+    if r == T::zero() {
+        return Some(a);
+    }
+    if r == T::one() {
+        return a.checked_mul(n);
+    }
+    if Some(r) == T::zero().checked_sub(T::one()) {
+        // a - a + a - a + ...
+        return if n.checked_rem(T::two())? == T::zero() {
+            Some(T::zero())
+        } else {
+            Some(a)
+        };
+    }
+
+    // |r| >= 2 なので項数 n が大きいとすぐにオーバーフローする
+    let mut sum = T::zero();
+    let mut term = a;
+    let mut rest = n;
+    loop {
+        sum = sum.checked_add(term)?;
+        rest = rest.decrement();
+        if rest == T::zero() {
+            break;
+        }
+        term = term.checked_mul(r)?;
+    }
+    Some(sum)
+}
+
+/// 初項 `a`, 項数 `n`, 公比 `r` の等比数列の和 (mod `m`) を O(log `n`) 時間で求めます。
+///
+/// `m` は素数でなくてもかまいません。
+///
+/// # Examples
+/// ```
+/// use arithmetic_series::geometric_series_mod;
+///
+/// // 1 + 2 + 4 + 8 + 16
+/// assert_eq!(geometric_series_mod(1, 2, 5, 1_000_000_007), 31);
+/// assert_eq!(geometric_series_mod(1, 2, 5, 10), 1);
+/// // 2^0 + 2^1 + ... + 2^99 = 2^100 - 1
+/// assert_eq!(geometric_series_mod(1, 2, 100, 1 << 40), (1 << 40) - 1);
+/// ```
+pub fn geometric_series_mod(a: u64, r: u64, n: u64, m: u64) -> u64 {
+    assert!(m >= 1);
+    let mul = |x: u64, y: u64| (u128::from(x) * u128::from(y) % u128::from(m)) as u64;
+    // (1 + r + ... + r^(k-1), r^k) (mod m)
+    let mut sum = 0;
+    let mut pow = 1 % m;
+    let r = r % m;
+    for i in (0..u64::BITS - n.leading_zeros()).rev() {
+        // k -> 2k
+        sum = mul(sum, 1 + pow);
+        pow = mul(pow, pow);
+        if n >> i & 1 == 1 {
+            // k -> k + 1
+            sum = (mul(sum, r) + 1) % m;
+            pow = mul(pow, r);
+        }
+    }
+    mul(a % m, sum)
+}
+
+/// `1^2 + 2^2 + ... + n^2` を求めます。オーバーフローした場合は `None` を返します。
+///
+/// # Panics
+/// if `n` is negative.
+///
+/// # Examples
+/// ```
+/// use arithmetic_series::sum_of_squares;
+///
+/// assert_eq!(sum_of_squares(3), Some(1 + 4 + 9));
+/// assert_eq!(sum_of_squares(0), Some(0));
+/// assert_eq!(sum_of_squares(1_000_000_u64), Some(333_333_833_333_500_000));
+/// ```
+pub fn sum_of_squares<T: Int>(n: T) -> Option<T> {
+    if n == T::zero() {
+        return Some(T::zero());
+    }
+
+    assert!(n.is_positive());
+
+    // n(n + 1)(2n + 1) / 6
+    let three = T::two().checked_add(T::one())?;
+    let mut x = n;
+    let mut y = n.checked_add(T::one())?;
+    let mut z = n.checked_mul(T::two())?.checked_add(T::one())?;
+    if x.checked_rem(T::two())? == T::zero() {
+        x = x.checked_div(T::two())?;
+    } else {
+        y = y.checked_div(T::two())?;
+    }
+    if x.checked_rem(three)? == T::zero() {
+        x = x.checked_div(three)?;
+    } else if y.checked_rem(three)? == T::zero() {
+        y = y.checked_div(three)?;
+    } else {
+        z = z.checked_div(three)?;
+    }
+    x.checked_mul(y)?.checked_mul(z)
+}
+
+/// `1^3 + 2^3 + ... + n^3` を求めます。オーバーフローした場合は `None` を返します。
+///
+/// # Panics
+/// if `n` is negative.
+///
+/// # Examples
+/// ```
+/// use arithmetic_series::sum_of_cubes;
+///
+/// assert_eq!(sum_of_cubes(3), Some(1 + 8 + 27));
+/// assert_eq!(sum_of_cubes(0), Some(0));
+/// ```
+pub fn sum_of_cubes<T: Int>(n: T) -> Option<T> {
+    // (1 + 2 + ... + n)^2
+    let s = arithmetic_series(T::one(), n, T::one())?;
+    s.checked_mul(s)
+}
+
 pub trait Int: Copy + Ord {
     fn is_positive(self) -> bool;
     fn decrement(self) -> Self;
     fn checked_add(self, rhs: Self) -> Option<Self>;
     fn checked_mul(self, rhs: Self) -> Option<Self>;
     fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
     fn zero() -> Self;
+    fn one() -> Self;
     fn two() -> Self;
 }
 
@@ -54,9 +208,18 @@ macro_rules! impl_int {
                 fn checked_div(self, rhs: Self) -> Option<Self> {
                     self.checked_div(rhs)
                 }
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    self.checked_sub(rhs)
+                }
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    self.checked_rem(rhs)
+                }
                 fn zero() -> Self {
                     0
                 }
+                fn one() -> Self {
+                    1
+                }
                 fn two() -> Self {
                     2
                 }
@@ -69,7 +232,9 @@ impl_int!(i32, i64, i128, u32, u64, u128, usize);
 
 #[cfg(test)]
 mod tests {
-    use crate::arithmetic_series;
+    use crate::{
+        arithmetic_series, geometric_series, geometric_series_mod, sum_of_cubes, sum_of_squares,
+    };
 
     #[test]
     fn test_sum_of_1_2_3_to_10() {
@@ -104,4 +269,45 @@ mod tests {
     fn test_negative_length() {
         arithmetic_series(42, -4, 3);
     }
+
+    #[test]
+    fn test_geometric_series() {
+        for a in -5_i64..=5 {
+            for r in -5_i64..=5 {
+                for n in 0..10 {
+                    let expected = (0..n).map(|i| a * r.pow(i as u32)).sum::<i64>();
+                    assert_eq!(geometric_series(a, r, n), Some(expected));
+                }
+            }
+        }
+        assert_eq!(geometric_series(-1, -1, std::i64::MAX), Some(-1));
+        assert_eq!(geometric_series(1, 3, 100_u64), None);
+    }
+
+    #[test]
+    fn test_geometric_series_mod() {
+        for m in 1..=30 {
+            for a in 0..5 {
+                for r in 0..10 {
+                    let mut expected = 0;
+                    let mut term = a % m;
+                    for n in 0..20 {
+                        assert_eq!(geometric_series_mod(a, r, n, m), expected);
+                        expected = (expected + term) % m;
+                        term = term * r % m;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sum_of_powers() {
+        for n in 0_u64..100 {
+            assert_eq!(sum_of_squares(n), Some((1..=n).map(|i| i * i).sum()));
+            assert_eq!(sum_of_cubes(n), Some((1..=n).map(|i| i * i * i).sum()));
+        }
+        assert_eq!(sum_of_squares(std::u64::MAX), None);
+        assert_eq!(sum_of_cubes(std::u64::MAX), None);
+    }
 }
@@ -0,0 +1,192 @@
+/// 整数行列 `a` の行列式を Bareiss のアルゴリズムで O(`n^3`) 時間で求めます。
+///
+/// 途中の値は全て `a` の小行列式になるので、有理数を使わずに割り算が割り切れたまま計算できます。ただし途中で小行列式の 2 乗程度の値が現れるので、それが `i128` に収まる必要があります。
+///
+/// # Panics
+///
+/// `a` が正方行列でない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use bareiss::determinant;
+///
+/// let a = vec![
+///     vec![2, -3, 1],
+///     vec![2, 0, -1],
+///     vec![1, 4, 5],
+/// ];
+/// assert_eq!(determinant(&a), 49);
+/// assert_eq!(determinant(&[vec![1, 2], vec![2, 4]]), 0);
+/// assert_eq!(determinant(&[]), 1);
+/// ```
+pub fn determinant(a: &[Vec<i64>]) -> i128 {
+    let n = a.len();
+    let mut a = to_i128(a, n);
+    let mut sign = 1;
+    let mut prev = 1;
+    for k in 0..n {
+        let Some(p) = (k..n).find(|&i| a[i][k] != 0) else {
+            return 0;
+        };
+        if p != k {
+            a.swap(p, k);
+            sign = -sign;
+        }
+        for i in (k + 1)..n {
+            for j in (k + 1)..n {
+                a[i][j] = (a[k][k] * a[i][j] - a[i][k] * a[k][j]) / prev;
+            }
+            a[i][k] = 0;
+        }
+        prev = a[k][k];
+    }
+    sign * prev
+}
+
+/// 連立一次方程式 `a x = b` の解を既約分数 `(分子, 分母)` の列として返します。分母は正です。
+///
+/// 行列式が `0` の場合 (解が存在しないまたは一意でない場合) は `None` を返します。
+///
+/// 分数を使わない Gauss-Jordan の消去法で O(`n^3`) 時間です。
+///
+/// # Panics
+///
+/// `a` が正方行列でない場合、または `a.len()` と `b.len()` が異なる場合パニックです。
+///
+/// # Examples
+/// ```
+/// use bareiss::solve;
+///
+/// // 2x + y = 1
+/// //  x + 3y = 2
+/// let a = vec![vec![2, 1], vec![1, 3]];
+/// assert_eq!(solve(&a, &[1, 2]), Some(vec![(1, 5), (3, 5)]));
+///
+/// let a = vec![vec![1, 2], vec![2, 4]];
+/// assert_eq!(solve(&a, &[1, 2]), None);
+/// ```
+pub fn solve(a: &[Vec<i64>], b: &[i64]) -> Option<Vec<(i128, i128)>> {
+    let n = a.len();
+    assert_eq!(b.len(), n);
+    // 拡大係数行列
+    let mut a = to_i128(a, n);
+    for (row, &b) in a.iter_mut().zip(b) {
+        row.push(i128::from(b));
+    }
+    let mut prev = 1;
+    for k in 0..n {
+        let p = (k..n).find(|&i| a[i][k] != 0)?;
+        a.swap(p, k);
+        for i in (0..n).filter(|&i| i != k) {
+            for j in (0..=n).filter(|&j| j != k) {
+                a[i][j] = (a[k][k] * a[i][j] - a[i][k] * a[k][j]) / prev;
+            }
+            a[i][k] = 0;
+        }
+        prev = a[k][k];
+    }
+    // 対角成分は全て ±det(a) になっている
+    let x = (0..n)
+        .map(|i| {
+            let (num, den) = (a[i][n], a[i][i]);
+            let g = gcd(num.abs(), den.abs());
+            let s = den.signum();
+            (s * num / g, s * den / g)
+        })
+        .collect();
+    Some(x)
+}
+
+fn to_i128(a: &[Vec<i64>], n: usize) -> Vec<Vec<i128>> {
+    a.iter()
+        .map(|row| {
+            assert_eq!(row.len(), n);
+            row.iter().map(|&x| i128::from(x)).collect()
+        })
+        .collect()
+}
+
+fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    // 余因子展開
+    fn naive_determinant(a: &[Vec<i64>]) -> i128 {
+        let n = a.len();
+        if n == 0 {
+            return 1;
+        }
+        let mut result = 0;
+        for j in 0..n {
+            let minor = a[1..]
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .enumerate()
+                        .filter(|&(k, _)| k != j)
+                        .map(|(_, &x)| x)
+                        .collect()
+                })
+                .collect::<Vec<_>>();
+            let sign = if j % 2 == 0 { 1 } else { -1 };
+            result += sign * i128::from(a[0][j]) * naive_determinant(&minor);
+        }
+        result
+    }
+
+    fn random_matrix(rng: &mut ThreadRng, n: usize, m: i64) -> Vec<Vec<i64>> {
+        (0..n)
+            .map(|_| (0..n).map(|_| rng.gen_range(-m, m + 1)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_determinant() {
+        let mut rng = thread_rng();
+        for n in 0..=6 {
+            for m in [1, 3, 100] {
+                for _ in 0..100 {
+                    let a = random_matrix(&mut rng, n, m);
+                    assert_eq!(determinant(&a), naive_determinant(&a), "{:?}", a);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut rng = thread_rng();
+        for n in 1..=6 {
+            for _ in 0..300 {
+                let a = random_matrix(&mut rng, n, 3);
+                let b = (0..n).map(|_| rng.gen_range(-3, 4)).collect::<Vec<_>>();
+                match solve(&a, &b) {
+                    Some(x) => {
+                        assert_ne!(determinant(&a), 0);
+                        let l = x.iter().fold(1, |acc, &(_, den)| acc / gcd(acc, den) * den);
+                        for i in 0..n {
+                            let lhs = (0..n)
+                                .map(|j| i128::from(a[i][j]) * x[j].0 * (l / x[j].1))
+                                .sum::<i128>();
+                            assert_eq!(lhs, i128::from(b[i]) * l);
+                        }
+                        for &(num, den) in &x {
+                            assert!(den > 0);
+                            assert_eq!(gcd(num.abs(), den), 1);
+                        }
+                    }
+                    None => assert_eq!(determinant(&a), 0),
+                }
+            }
+        }
+    }
+}
@@ -52,9 +52,74 @@ impl<T: Ord> BinarySearchRange<T> for [T] {
     }
 }
 
+/// `pred` が `lo` 以上 `hi` 未満の整数に対して `true, ..., true, false, ..., false` となっているとき、`pred(x)` が `false` となる最小の `x` を返します。`pred` が全て `true` のときは `hi` を返します。
+///
+/// [`slice::partition_point`] の値の範囲版です。`pred` を O(log(`hi` - `lo`)) 回呼びます。
+///
+/// # Panics
+///
+/// `lo > hi` の場合パニックです。
+///
+/// # Examples
+///
+/// ```
+/// use binary_search_range::partition_point_i64;
+///
+/// // x * x <= 50 を満たす最大の x は 7
+/// assert_eq!(partition_point_i64(0, 100, |x| x * x <= 50), 8);
+/// assert_eq!(partition_point_i64(0, 100, |_| true), 100);
+/// assert_eq!(partition_point_i64(0, 100, |_| false), 0);
+/// assert_eq!(partition_point_i64(i64::MIN, i64::MAX, |x| x < -5), -5);
+/// ```
+pub fn partition_point_i64<F>(lo: i64, hi: i64, mut pred: F) -> i64
+where
+    F: FnMut(i64) -> bool,
+{
+    assert!(lo <= hi);
+    let (mut lo, mut hi) = (lo, hi);
+    // pred(lo - 1) = true, pred(hi) = false とみなす
+    while lo < hi {
+        let mid = lo.wrapping_add((hi.wrapping_sub(lo) as u64 / 2) as i64);
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// `pred` が区間 `[lo, hi]` で `true` から `false` に変わるとき、その境界を二分法で求めます。
+///
+/// `iters` 回反復して、`pred(x)` が `true` となる `x` のうち最大のものの近似値を返します。反復ごとに区間の長さが半分になります。
+///
+/// # Examples
+///
+/// ```
+/// use binary_search_range::partition_point_f64;
+///
+/// let x = partition_point_f64(0.0, 10.0, 100, |x| x * x <= 2.0);
+/// assert!((x - 2_f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn partition_point_f64<F>(lo: f64, hi: f64, iters: usize, mut pred: F) -> f64
+where
+    F: FnMut(f64) -> bool,
+{
+    let (mut ok, mut ng) = (lo, hi);
+    for _ in 0..iters {
+        let mid = (ok + ng) / 2.0;
+        if pred(mid) {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    ok
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::BinarySearchRange;
+    use crate::{partition_point_f64, partition_point_i64, BinarySearchRange};
 
     #[test]
     #[should_panic]
@@ -97,4 +162,23 @@ mod tests {
         assert_eq!(a.range(3..7), 2..5);
         assert_eq!(a.range(3..8), 2..6);
     }
+
+    #[test]
+    fn test_partition_point_i64() {
+        for lo in -10..=10 {
+            for hi in lo..=10 {
+                for p in lo..=hi {
+                    assert_eq!(partition_point_i64(lo, hi, |x| x < p), p);
+                }
+            }
+        }
+        assert_eq!(partition_point_i64(i64::MIN, i64::MAX, |_| true), i64::MAX);
+        assert_eq!(partition_point_i64(i64::MIN, i64::MAX, |_| false), i64::MIN);
+    }
+
+    #[test]
+    fn test_partition_point_f64() {
+        let x = partition_point_f64(-1e9, 1e9, 200, |x| x.powi(3) <= -8.0);
+        assert!((x + 2.0).abs() < 1e-9);
+    }
 }
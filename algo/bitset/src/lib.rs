@@ -0,0 +1,285 @@
+use std::fmt;
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Shl, ShlAssign, Shr, ShrAssign};
+
+/// 長さ固定のビット列です。`u64` を 1 ワードとして 64 ビットずつまとめて演算します。
+///
+/// # Examples
+/// ```
+/// use bitset::BitSet;
+///
+/// let mut a = BitSet::new(100);
+/// a.set(3, true);
+/// a.set(70, true);
+/// let mut b = BitSet::new(100);
+/// b.set(70, true);
+/// b.set(99, true);
+/// a ^= &b;
+/// assert!(a.get(3));
+/// assert!(!a.get(70));
+/// assert!(a.get(99));
+/// assert_eq!(a.count_ones(), 2);
+/// assert_eq!(a.ones().collect::<Vec<_>>(), vec![3, 99]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    n: usize,
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    /// 長さ `n` の全て `0` のビット列を作ります。
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            words: vec![0; (n + 63) / 64],
+        }
+    }
+
+    /// ビット列の長さを返します。
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// `i` ビット目を返します。
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.n);
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// `i` ビット目を `value` にします。
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.n);
+        if value {
+            self.words[i / 64] |= 1 << (i % 64);
+        } else {
+            self.words[i / 64] &= !(1 << (i % 64));
+        }
+    }
+
+    /// `i` ビット目を反転します。
+    pub fn flip(&mut self, i: usize) {
+        assert!(i < self.n);
+        self.words[i / 64] ^= 1 << (i % 64);
+    }
+
+    /// `1` であるビットの個数を返します。
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// `1` であるビットの位置を昇順に返すイテレータを作ります。
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(k, &w)| {
+            let mut w = w;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let i = w.trailing_zeros() as usize;
+                w &= w - 1;
+                Some(k * 64 + i)
+            })
+        })
+    }
+
+    /// 全てのビットが `0` かどうかを返します。
+    pub fn none(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// ワード列を返します。`i` ビット目は `words()[i / 64]` の下から `i % 64` ビット目です。
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    // 長さ n を超える部分のビットを 0 にする
+    fn truncate(&mut self) {
+        if self.n % 64 != 0 {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (self.n % 64)) - 1;
+        }
+    }
+}
+
+impl FromIterator<bool> for BitSet {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let bits = iter.into_iter().collect::<Vec<_>>();
+        let mut result = Self::new(bits.len());
+        for (i, b) in bits.into_iter().enumerate() {
+            if b {
+                result.set(i, true);
+            }
+        }
+        result
+    }
+}
+
+macro_rules! impl_bit_assign {
+    ($tr:ident, $method:ident, $op:tt) => {
+        impl $tr<&BitSet> for BitSet {
+            fn $method(&mut self, rhs: &BitSet) {
+                assert_eq!(self.n, rhs.n);
+                for (w, &r) in self.words.iter_mut().zip(&rhs.words) {
+                    *w $op r;
+                }
+            }
+        }
+    };
+}
+
+impl_bit_assign!(BitAndAssign, bitand_assign, &=);
+impl_bit_assign!(BitOrAssign, bitor_assign, |=);
+impl_bit_assign!(BitXorAssign, bitxor_assign, ^=);
+
+/// `i` ビット目を `i + shift` ビット目に移します。長さを超えたビットは捨てます。
+///
+/// # Examples
+/// ```
+/// use bitset::BitSet;
+///
+/// let mut a = [true, false, true, true].into_iter().collect::<BitSet>();
+/// a <<= 1;
+/// assert_eq!(format!("{:?}", a), "0101");
+/// ```
+impl ShlAssign<usize> for BitSet {
+    fn shl_assign(&mut self, shift: usize) {
+        let (q, r) = (shift / 64, shift % 64);
+        let m = self.words.len();
+        for i in (0..m).rev() {
+            let mut w = 0;
+            if i >= q {
+                w = self.words[i - q] << r;
+                if r > 0 && i > q {
+                    w |= self.words[i - q - 1] >> (64 - r);
+                }
+            }
+            self.words[i] = w;
+        }
+        self.truncate();
+    }
+}
+
+/// `i` ビット目を `i - shift` ビット目に移します。`i < shift` のビットは捨てます。
+///
+/// # Examples
+/// ```
+/// use bitset::BitSet;
+///
+/// let mut a = [true, false, true, true].into_iter().collect::<BitSet>();
+/// a >>= 2;
+/// assert_eq!(format!("{:?}", a), "1100");
+/// ```
+impl ShrAssign<usize> for BitSet {
+    fn shr_assign(&mut self, shift: usize) {
+        let (q, r) = (shift / 64, shift % 64);
+        let m = self.words.len();
+        for i in 0..m {
+            let mut w = 0;
+            if i + q < m {
+                w = self.words[i + q] >> r;
+                if r > 0 && i + q + 1 < m {
+                    w |= self.words[i + q + 1] << (64 - r);
+                }
+            }
+            self.words[i] = w;
+        }
+    }
+}
+
+impl Shl<usize> for &BitSet {
+    type Output = BitSet;
+    fn shl(self, shift: usize) -> BitSet {
+        let mut result = self.clone();
+        result <<= shift;
+        result
+    }
+}
+
+impl Shr<usize> for &BitSet {
+    type Output = BitSet;
+    fn shr(self, shift: usize) -> BitSet {
+        let mut result = self.clone();
+        result >>= shift;
+        result
+    }
+}
+
+impl fmt::Debug for BitSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.n {
+            write!(f, "{}", if self.get(i) { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BitSet;
+
+    #[test]
+    fn test_set_get() {
+        let n = 200;
+        let mut b = BitSet::new(n);
+        let mut expected = vec![false; n];
+        for i in (0..n).step_by(7) {
+            b.set(i, true);
+            expected[i] = true;
+        }
+        for i in (0..n).step_by(21) {
+            b.flip(i);
+            expected[i] ^= true;
+        }
+        for (i, &e) in expected.iter().enumerate() {
+            assert_eq!(b.get(i), e);
+        }
+        assert_eq!(b.count_ones(), expected.iter().filter(|&&x| x).count());
+        assert_eq!(
+            b.ones().collect::<Vec<_>>(),
+            (0..n).filter(|&i| expected[i]).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = (0..130).map(|i| i % 2 == 0).collect::<BitSet>();
+        let b = (0..130).map(|i| i % 3 == 0).collect::<BitSet>();
+        let mut and = a.clone();
+        and &= &b;
+        let mut or = a.clone();
+        or |= &b;
+        let mut xor = a;
+        xor ^= &b;
+        for i in 0..130 {
+            assert_eq!(and.get(i), i % 2 == 0 && i % 3 == 0);
+            assert_eq!(or.get(i), i % 2 == 0 || i % 3 == 0);
+            assert_eq!(xor.get(i), (i % 2 == 0) ^ (i % 3 == 0));
+        }
+    }
+
+    #[test]
+    fn test_shift() {
+        for n in [1, 63, 64, 65, 130, 200] {
+            let a = (0..n).map(|i| i % 3 == 0 || i % 7 == 1).collect::<BitSet>();
+            for shift in [0, 1, 5, 63, 64, 65, 127, 128, 129, 250] {
+                let shl = &a << shift;
+                let shr = &a >> shift;
+                for i in 0..n {
+                    assert_eq!(shl.get(i), i >= shift && a.get(i - shift));
+                    assert_eq!(shr.get(i), i + shift < n && a.get(i + shift));
+                }
+                assert_eq!(shl.count_ones(), shl.ones().filter(|&i| i < n).count());
+            }
+        }
+    }
+
+    #[test]
+    fn test_debug() {
+        let a = [true, false, true, true].into_iter().collect::<BitSet>();
+        assert_eq!(format!("{:?}", a), "1011");
+    }
+}
@@ -0,0 +1,17 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=CGL_5_A
+// tags: geometry
+// error: 1e-6
+
+use proconio::input;
+
+use closest_pair::closest_pair;
+
+fn main() {
+    input! {
+        n: usize,
+        points: [(f64, f64); n],
+    }
+    let (i, j) = closest_pair(&points).unwrap();
+    let (dx, dy) = (points[i].0 - points[j].0, points[i].1 - points[j].1);
+    println!("{:.10}", (dx * dx + dy * dy).sqrt());
+}
@@ -0,0 +1,144 @@
+use std::ops::{Add, Mul, Sub};
+
+/// 最近点対を分割統治法で O(`n` log `n`) 時間で求めます。
+///
+/// 距離が最小となる 2 点の添字 `(i, j)` (`i < j`) を返します。`points.len() < 2` の場合は `None` を返します。
+///
+/// 座標は `i64` でも `f64` でも使えます。距離の 2 乗を計算するので、`i64` の場合は座標の差の 2 乗の和がオーバーフローしない必要があります。
+///
+/// # Examples
+/// ```
+/// use closest_pair::closest_pair;
+///
+/// let points = vec![(0, 0), (10, 10), (3, 4), (11, 9), (-5, 2)];
+/// assert_eq!(closest_pair(&points), Some((1, 3)));
+///
+/// let points = vec![(0.0, 0.0), (0.5, 0.5), (2.0, -1.0)];
+/// assert_eq!(closest_pair(&points), Some((0, 1)));
+///
+/// assert_eq!(closest_pair(&[(1, 2)]), None);
+/// ```
+pub fn closest_pair<T>(points: &[(T, T)]) -> Option<(usize, usize)>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    if points.len() < 2 {
+        return None;
+    }
+    let mut ps = points
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| (x, y, i))
+        .collect::<Vec<_>>();
+    ps.sort_by(|p, q| p.0.partial_cmp(&q.0).unwrap());
+    let mut best = None;
+    solve(&mut ps, &mut best);
+    best.map(|(_, i, j)| (i.min(j), i.max(j)))
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+// ps を x 座標でソートされた状態で受け取り、y 座標でソートして返す
+fn solve<T>(ps: &mut [(T, T, usize)], best: &mut Option<(T, usize, usize)>)
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    let n = ps.len();
+    if n <= 1 {
+        return;
+    }
+    let mid = n / 2;
+    let mid_x = ps[mid].0;
+    solve(&mut ps[..mid], best);
+    solve(&mut ps[mid..], best);
+
+    let mut merged = Vec::with_capacity(n);
+    let (mut i, mut j) = (0, mid);
+    while i < mid || j < n {
+        if j == n || (i < mid && ps[i].1 <= ps[j].1) {
+            merged.push(ps[i]);
+            i += 1;
+        } else {
+            merged.push(ps[j]);
+            j += 1;
+        }
+    }
+    ps.copy_from_slice(&merged);
+
+    // 境界から距離 best 未満の点だけを y 座標の順に見る
+    let mut strip: Vec<(T, T, usize)> = Vec::new();
+    for &p in ps.iter() {
+        let dx = abs_diff(p.0, mid_x);
+        if matches!(best, Some((d, _, _)) if dx * dx >= *d) {
+            continue;
+        }
+        for &q in strip.iter().rev() {
+            let dy = abs_diff(p.1, q.1);
+            if matches!(best, Some((d, _, _)) if dy * dy >= *d) {
+                break;
+            }
+            let dx = abs_diff(p.0, q.0);
+            let d = dx * dx + dy * dy;
+            if best.map_or(true, |(b, _, _)| d < b) {
+                *best = Some((d, p.2, q.2));
+            }
+        }
+        strip.push(p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::closest_pair;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_random() {
+        let mut rng = thread_rng();
+        for n in 2..100 {
+            for m in [3, 1000] {
+                let points = (0..n)
+                    .map(|_| (rng.gen_range(-m, m), rng.gen_range(-m, m)))
+                    .collect::<Vec<(i64, i64)>>();
+                let dist = |i: usize, j: usize| {
+                    let (dx, dy) = (points[i].0 - points[j].0, points[i].1 - points[j].1);
+                    dx * dx + dy * dy
+                };
+                let expected = (0..n)
+                    .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+                    .map(|(i, j)| dist(i, j))
+                    .min()
+                    .unwrap();
+                let (i, j) = closest_pair(&points).unwrap();
+                assert!(i < j);
+                assert_eq!(dist(i, j), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_f64() {
+        let mut rng = thread_rng();
+        for n in 2..100 {
+            let points = (0..n)
+                .map(|_| (rng.gen::<f64>(), rng.gen::<f64>()))
+                .collect::<Vec<_>>();
+            let dist = |i: usize, j: usize| {
+                let (dx, dy) = (points[i].0 - points[j].0, points[i].1 - points[j].1);
+                dx * dx + dy * dy
+            };
+            let expected = (0..n)
+                .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+                .map(|(i, j)| dist(i, j))
+                .fold(f64::INFINITY, f64::min);
+            let (i, j) = closest_pair(&points).unwrap();
+            assert_eq!(dist(i, j), expected);
+        }
+    }
+}
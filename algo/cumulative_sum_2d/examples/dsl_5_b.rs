@@ -0,0 +1,20 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_5_B
+
+use cumulative_sum_2d::RangeAdd2D;
+use proconio::input;
+
+fn main() {
+    input! {
+        n: usize,
+        rects: [(usize, usize, usize, usize); n],
+    };
+
+    const M: usize = 1000;
+    let mut imos = RangeAdd2D::new(M, M);
+    for (x1, y1, x2, y2) in rects {
+        imos.add(y1..y2, x1..x2, 1_i32);
+    }
+    let grid = imos.build();
+    let ans = grid.iter().flatten().max().unwrap();
+    println!("{}", ans);
+}
@@ -31,6 +31,7 @@ use std::ops::{Add, Range, Sub};
 pub struct CumulativeSum2D<T> {
     h: usize,
     w: usize,
+    zero: T,
     cum_sum: Vec<Vec<T>>,
 }
 
@@ -39,13 +40,58 @@ where
     T: Clone + Copy + Default + Add<Output = T> + Sub<Output = T>,
 {
     pub fn new(grid: &[Vec<T>]) -> Self {
+        Self::with_zero(grid, T::default())
+    }
+}
+
+impl<T> CumulativeSum2D<T>
+where
+    T: Clone + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// 零元 `zero` を指定して作ります。`T` が `Default` を実装していない場合に使います。
+    ///
+    /// # Examples
+    /// ```
+    /// use cumulative_sum_2d::CumulativeSum2D;
+    /// use mod_int::ModInt998244353;
+    ///
+    /// type Mint = ModInt998244353;
+    /// let grid = vec![
+    ///     vec![Mint::new(998244352), Mint::new(2)],
+    ///     vec![Mint::new(3), Mint::new(4)],
+    /// ];
+    /// let cum_sum = CumulativeSum2D::with_zero(&grid, Mint::new(0));
+    /// assert_eq!(cum_sum.sum(0..1, 0..2).val(), 1);
+    /// assert_eq!(cum_sum.sum(0..2, 0..2).val(), 8);
+    /// assert_eq!(cum_sum.sum(1..1, 0..2).val(), 0);
+    /// ```
+    pub fn with_zero(grid: &[Vec<T>], zero: T) -> Self {
         let h = grid.len();
-        assert!(h >= 1);
-        let w = grid[0].len();
+        let w = grid.first().map_or(0, |row| row.len());
         for row in grid {
             assert_eq!(row.len(), w);
         }
-        let mut cum_sum = grid.to_vec();
+        Self::from_fn(h, w, zero, |i, j| grid[i][j])
+    }
+
+    /// `i` 行 `j` 列の値が `f(i, j)` である `h` 行 `w` 列のグリッドから作ります。
+    ///
+    /// # Examples
+    /// ```
+    /// use cumulative_sum_2d::CumulativeSum2D;
+    ///
+    /// // 九九の表
+    /// let cum_sum = CumulativeSum2D::from_fn(9, 9, 0, |i, j| (i + 1) * (j + 1));
+    /// assert_eq!(cum_sum.sum(0..9, 0..9), 45 * 45);
+    /// assert_eq!(cum_sum.sum(1..3, 4..6), 2 * 5 + 2 * 6 + 3 * 5 + 3 * 6);
+    /// ```
+    pub fn from_fn<F>(h: usize, w: usize, zero: T, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let mut cum_sum = (0..h)
+            .map(|i| (0..w).map(|j| f(i, j)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
         #[allow(clippy::needless_range_loop)]
         for i in 0..h {
             for j in 1..w {
@@ -57,14 +103,19 @@ where
                 cum_sum[i][j] = cum_sum[i - 1][j] + cum_sum[i][j];
             }
         }
-        Self { h, w, cum_sum }
+        Self {
+            h,
+            w,
+            zero,
+            cum_sum,
+        }
     }
 
     pub fn sum(&self, y_range: Range<usize>, x_range: Range<usize>) -> T {
         let (y_start, y_end) = (y_range.start, y_range.end);
         let (x_start, x_end) = (x_range.start, x_range.end);
         if y_start >= y_end || x_start >= x_end {
-            return T::default();
+            return self.zero;
         }
         assert!(y_end <= self.h);
         assert!(x_end <= self.w);
@@ -86,9 +137,96 @@ where
     }
 }
 
+/// 二次元いもす法です。長方形領域への加算をまとめて行い、最後に各マスの値を求めます。
+///
+/// 途中で負の値が現れるので、`T` は符号付き整数などにしてください。
+///
+/// # Examples
+/// ```
+/// use cumulative_sum_2d::RangeAdd2D;
+///
+/// let mut imos = RangeAdd2D::new(3, 4);
+/// imos.add(0..2, 0..3, 1);
+/// imos.add(1..3, 2..4, 10);
+/// assert_eq!(
+///     imos.build(),
+///     vec![
+///         vec![1, 1, 1, 0],
+///         vec![1, 1, 11, 10],
+///         vec![0, 0, 10, 10],
+///     ]
+/// );
+/// ```
+pub struct RangeAdd2D<T> {
+    h: usize,
+    w: usize,
+    diff: Vec<Vec<T>>,
+}
+
+impl<T> RangeAdd2D<T>
+where
+    T: Clone + Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    /// 全てのマスが `T::default()` である `h` 行 `w` 列のグリッドを作ります。
+    pub fn new(h: usize, w: usize) -> Self {
+        Self::with_zero(h, w, T::default())
+    }
+}
+
+impl<T> RangeAdd2D<T>
+where
+    T: Clone + Copy + Add<Output = T> + Sub<Output = T>,
+{
+    /// 全てのマスが零元 `zero` である `h` 行 `w` 列のグリッドを作ります。`T` が `Default` を実装していない場合に使います。
+    pub fn with_zero(h: usize, w: usize, zero: T) -> Self {
+        Self {
+            h,
+            w,
+            diff: vec![vec![zero; w + 1]; h + 1],
+        }
+    }
+
+    /// 行が `y_range`、列が `x_range` の範囲にあるマス全てに `value` を足します。O(1) 時間です。
+    pub fn add(&mut self, y_range: Range<usize>, x_range: Range<usize>, value: T) {
+        let (y_start, y_end) = (y_range.start, y_range.end);
+        let (x_start, x_end) = (x_range.start, x_range.end);
+        if y_start >= y_end || x_start >= x_end {
+            return;
+        }
+        assert!(y_end <= self.h);
+        assert!(x_end <= self.w);
+        self.diff[y_start][x_start] = self.diff[y_start][x_start] + value;
+        self.diff[y_start][x_end] = self.diff[y_start][x_end] - value;
+        self.diff[y_end][x_start] = self.diff[y_end][x_start] - value;
+        self.diff[y_end][x_end] = self.diff[y_end][x_end] + value;
+    }
+
+    /// 各マスの値を求めて `h` 行 `w` 列のグリッドとして返します。O(`hw`) 時間です。
+    ///
+    /// 範囲の和を求めたい場合は、返り値から [`CumulativeSum2D`] を作ってください。
+    pub fn build(&self) -> Vec<Vec<T>> {
+        let mut grid = self.diff.clone();
+        for row in &mut grid {
+            for j in 1..=self.w {
+                row[j] = row[j] + row[j - 1];
+            }
+        }
+        for i in 1..=self.h {
+            for j in 0..=self.w {
+                grid[i][j] = grid[i - 1][j] + grid[i][j];
+            }
+        }
+        grid.truncate(self.h);
+        for row in &mut grid {
+            row.truncate(self.w);
+        }
+        grid
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::CumulativeSum2D;
+    use crate::{CumulativeSum2D, RangeAdd2D};
 
     #[test]
     fn test() {
@@ -116,4 +254,62 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_from_fn() {
+        let grid = vec![
+            vec![3, 1, 4, 1, 5],
+            vec![9, 2, 6, 5, 3],
+            vec![5, 8, 9, 7, 9],
+        ];
+        let cum_sum = CumulativeSum2D::from_fn(3, 5, 0, |i, j| grid[i][j]);
+        let expected = CumulativeSum2D::new(&grid);
+        for y_start in 0..=3 {
+            for y_end in y_start..=3 {
+                for x_start in 0..=5 {
+                    for x_end in x_start..=5 {
+                        assert_eq!(
+                            cum_sum.sum(y_start..y_end, x_start..x_end),
+                            expected.sum(y_start..y_end, x_start..x_end)
+                        );
+                    }
+                }
+            }
+        }
+        let empty = CumulativeSum2D::from_fn(0, 3, 0, |_, _| 1);
+        assert_eq!(empty.sum(0..0, 0..3), 0);
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid: Vec<Vec<i32>> = vec![];
+        let cum_sum = CumulativeSum2D::new(&grid);
+        assert_eq!(cum_sum.sum(0..0, 0..0), 0);
+        let cum_sum = CumulativeSum2D::<i32>::new(&[vec![], vec![]]);
+        assert_eq!(cum_sum.sum(0..2, 0..0), 0);
+    }
+
+    #[test]
+    fn test_range_add() {
+        let (h, w) = (4, 5);
+        let mut imos = RangeAdd2D::new(h, w);
+        let mut expected = vec![vec![0_i64; w]; h];
+        let mut value = 1;
+        for y_start in 0..=h {
+            for y_end in y_start..=h {
+                for x_start in 0..=w {
+                    for x_end in x_start..=w {
+                        imos.add(y_start..y_end, x_start..x_end, value);
+                        for row in &mut expected[y_start..y_end] {
+                            for x in &mut row[x_start..x_end] {
+                                *x += value;
+                            }
+                        }
+                        value = value * 3 % 101 - 50;
+                    }
+                }
+            }
+        }
+        assert_eq!(imos.build(), expected);
+    }
 }
@@ -1,4 +1,6 @@
 // problem: https://judge.yosupo.jp/problem/cycle_detection
+// tags: graph
+// checker_cpp: https://raw.githubusercontent.com/yosupo06/library-checker-problems/master/graph/cycle_detection/checker.cpp
 // judge_program_rs: ./judge_cycle_detection.rs
 
 use detect_cycle::detect_cycle_directed;
@@ -0,0 +1,27 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=ITP1_11_A
+
+use dice::{Dice, Direction};
+use proconio::input;
+use proconio::marker::Chars;
+
+fn main() {
+    input! {
+        labels: [u32; 6],
+        commands: Chars,
+    };
+
+    let mut dice = Dice::new(
+        labels[0], labels[1], labels[2], labels[3], labels[4], labels[5],
+    );
+    for c in commands {
+        let dir = match c {
+            'N' => Direction::North,
+            'S' => Direction::South,
+            'E' => Direction::East,
+            'W' => Direction::West,
+            _ => unreachable!(),
+        };
+        dice.roll(dir);
+    }
+    println!("{}", dice.top);
+}
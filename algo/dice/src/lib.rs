@@ -0,0 +1,200 @@
+/// サイコロを転がす向きです。北は奥、南は手前、東は右、西は左です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+/// サイコロです。各面に値 `T` が書かれています。
+///
+/// 上から見て、手前 (南) が `front`、右 (東) が `right` です。
+///
+/// # Examples
+/// ```
+/// use dice::{Dice, Direction};
+///
+/// // 普通のサイコロ
+/// let mut dice = Dice::new(1, 2, 3, 4, 5, 6);
+/// dice.roll(Direction::North);
+/// assert_eq!((dice.top, dice.front, dice.right), (2, 6, 3));
+/// dice.roll(Direction::East);
+/// assert_eq!((dice.top, dice.front, dice.right), (4, 6, 2));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dice<T> {
+    pub top: T,
+    pub front: T,
+    pub right: T,
+    pub left: T,
+    pub back: T,
+    pub bottom: T,
+}
+
+impl<T> Dice<T> {
+    pub fn new(top: T, front: T, right: T, left: T, back: T, bottom: T) -> Self {
+        Self {
+            top,
+            front,
+            right,
+            left,
+            back,
+            bottom,
+        }
+    }
+
+    /// `dir` の向きに 1 回転がします。
+    pub fn roll(&mut self, dir: Direction) {
+        use std::mem::swap;
+        match dir {
+            Direction::North => {
+                // top <- front <- bottom <- back <- top
+                swap(&mut self.top, &mut self.front);
+                swap(&mut self.front, &mut self.bottom);
+                swap(&mut self.bottom, &mut self.back);
+            }
+            Direction::South => {
+                swap(&mut self.top, &mut self.back);
+                swap(&mut self.back, &mut self.bottom);
+                swap(&mut self.bottom, &mut self.front);
+            }
+            Direction::East => {
+                // top <- left <- bottom <- right <- top
+                swap(&mut self.top, &mut self.left);
+                swap(&mut self.left, &mut self.bottom);
+                swap(&mut self.bottom, &mut self.right);
+            }
+            Direction::West => {
+                swap(&mut self.top, &mut self.right);
+                swap(&mut self.right, &mut self.bottom);
+                swap(&mut self.bottom, &mut self.left);
+            }
+        }
+    }
+
+    /// 上から見て時計回りに 90 度回します。上下の面は変わりません。
+    pub fn rotate_clockwise(&mut self) {
+        use std::mem::swap;
+        // front <- right <- back <- left <- front
+        swap(&mut self.front, &mut self.right);
+        swap(&mut self.right, &mut self.back);
+        swap(&mut self.back, &mut self.left);
+    }
+
+    /// 上から見て反時計回りに 90 度回します。上下の面は変わりません。
+    pub fn rotate_counterclockwise(&mut self) {
+        use std::mem::swap;
+        swap(&mut self.front, &mut self.left);
+        swap(&mut self.left, &mut self.back);
+        swap(&mut self.back, &mut self.right);
+    }
+}
+
+impl<T: Clone> Dice<T> {
+    /// 転がしたり回したりして得られる 24 通りの向きを全て返します。
+    ///
+    /// 2 つのサイコロが同じかどうかは、一方の `orientations()` にもう一方が含まれるかで判定できます。
+    ///
+    /// # Examples
+    /// ```
+    /// use dice::Dice;
+    ///
+    /// let dice = Dice::new(1, 2, 3, 4, 5, 6);
+    /// let orientations = dice.orientations();
+    /// assert_eq!(orientations.len(), 24);
+    /// assert!(orientations.contains(&Dice::new(6, 5, 3, 4, 2, 1)));
+    /// // 鏡像は含まれない
+    /// assert!(!orientations.contains(&Dice::new(1, 2, 4, 3, 5, 6)));
+    /// ```
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut result = Vec::with_capacity(24);
+        let mut dice = self.clone();
+        // 6 つの面をそれぞれ上にする
+        for i in 0..6 {
+            for _ in 0..4 {
+                result.push(dice.clone());
+                dice.rotate_clockwise();
+            }
+            if i % 2 == 0 {
+                dice.roll(Direction::North);
+            } else {
+                dice.roll(Direction::East);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIRECTIONS: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    #[test]
+    fn test_inverse() {
+        let dice = Dice::new(1, 2, 3, 4, 5, 6);
+        let pairs = [
+            (Direction::North, Direction::South),
+            (Direction::East, Direction::West),
+        ];
+        for (d1, d2) in pairs {
+            let mut d = dice.clone();
+            d.roll(d1);
+            assert_ne!(d, dice);
+            d.roll(d2);
+            assert_eq!(d, dice);
+            d.roll(d2);
+            d.roll(d1);
+            assert_eq!(d, dice);
+        }
+        let mut d = dice.clone();
+        d.rotate_clockwise();
+        assert_ne!(d, dice);
+        d.rotate_counterclockwise();
+        assert_eq!(d, dice);
+        for dir in DIRECTIONS {
+            let mut d = dice.clone();
+            for _ in 0..4 {
+                d.roll(dir);
+            }
+            assert_eq!(d, dice);
+        }
+    }
+
+    #[test]
+    fn test_orientations() {
+        let dice = Dice::new(1, 2, 3, 4, 5, 6);
+        let mut orientations = dice.orientations();
+        // 向かい合う面の和は 7
+        for d in &orientations {
+            assert_eq!(d.top + d.bottom, 7);
+            assert_eq!(d.front + d.back, 7);
+            assert_eq!(d.right + d.left, 7);
+        }
+        // 転がして到達できる向きと一致する
+        let mut reachable = vec![dice];
+        let mut i = 0;
+        while i < reachable.len() {
+            for dir in DIRECTIONS {
+                let mut d = reachable[i].clone();
+                d.roll(dir);
+                if !reachable.contains(&d) {
+                    reachable.push(d);
+                }
+            }
+            i += 1;
+        }
+        let key = |d: &Dice<i32>| (d.top, d.front, d.right);
+        orientations.sort_by_key(key);
+        orientations.dedup();
+        reachable.sort_by_key(key);
+        assert_eq!(orientations, reachable);
+    }
+}
@@ -0,0 +1,304 @@
+/// 状態 `next` への遷移と、その遷移に乗せる値 `value` です。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transition<V> {
+    pub next: usize,
+    pub value: V,
+}
+
+impl<V> Transition<V> {
+    pub fn new(next: usize, value: V) -> Self {
+        Self { next, value }
+    }
+}
+
+/// ダブリングです。遷移に値を乗せない場合は [`SuccessorDoubling`] のほうがメモリを使いません。
+///
+/// 状態 `0, 1, ..., n - 1` の各状態から 1 ステップで進む先が決まっているとき、`k` ステップ後の状態と、途中の遷移の値を `multiply` で畳み込んだ値を O(log `k`) 時間で求めます。
+///
+/// # Examples
+/// ```
+/// use doubling::{Doubling, Transition};
+///
+/// // i -> (i + 3) % 5 に進み、通った状態の番号を足していく
+/// let doubling = Doubling::new(5, 1_000_000_000_000, 0_u64, |a, b| a + b, |i| {
+///     Transition::new((i + 3) % 5, i as u64)
+/// });
+/// // 1 -> 4 -> 2 -> 0
+/// assert_eq!(doubling.fold(1, 3), Transition::new(0, 1 + 4 + 2));
+/// assert_eq!(doubling.advance(1, 1_000_000_000_000), 1);
+/// ```
+pub struct Doubling<V, F> {
+    n: usize,
+    max_steps: u64,
+    // table[j * n + i]: 状態 i から 2^j ステップ進んだ先と、その間の値
+    table: Vec<Transition<V>>,
+    e: V,
+    multiply: F,
+}
+
+impl<V, F> Doubling<V, F>
+where
+    V: Clone,
+    F: Fn(&V, &V) -> V,
+{
+    /// 状態数 `n`、最大ステップ数 `max_steps` でダブリングの表を作ります。
+    ///
+    /// `step(i)` は状態 `i` から 1 ステップで進む遷移です。`e` と `multiply` は値を畳み込む単位元と二項演算で、`multiply(a, b)` は `a` の遷移のあとに `b` の遷移をしたときの値です。
+    ///
+    /// O(`n` log `max_steps`) 時間です。
+    pub fn new<S>(n: usize, max_steps: u64, e: V, multiply: F, step: S) -> Self
+    where
+        S: Fn(usize) -> Transition<V>,
+    {
+        let log = (64 - max_steps.leading_zeros()).max(1) as usize;
+        let mut table = Vec::with_capacity(n * log);
+        for i in 0..n {
+            let t = step(i);
+            assert!(t.next < n);
+            table.push(t);
+        }
+        for j in 1..log {
+            for i in 0..n {
+                let first = &table[(j - 1) * n + i];
+                let second = &table[(j - 1) * n + first.next];
+                let t = Transition::new(second.next, multiply(&first.value, &second.value));
+                table.push(t);
+            }
+        }
+        Self {
+            n,
+            max_steps,
+            table,
+            e,
+            multiply,
+        }
+    }
+
+    fn levels(&self) -> usize {
+        if self.n == 0 {
+            0
+        } else {
+            self.table.len() / self.n
+        }
+    }
+
+    /// 状態 `start` から `k` ステップ進んだ先と、途中の遷移の値を畳み込んだ値を返します。
+    ///
+    /// # Panics
+    ///
+    /// `k` が `max_steps` より大きい場合パニックです。
+    pub fn fold(&self, start: usize, k: u64) -> Transition<V> {
+        assert!(start < self.n);
+        assert!(k <= self.max_steps);
+        let mut cur = start;
+        let mut acc = self.e.clone();
+        for j in 0..self.levels() {
+            if k >> j & 1 == 1 {
+                let t = &self.table[j * self.n + cur];
+                acc = (self.multiply)(&acc, &t.value);
+                cur = t.next;
+            }
+        }
+        Transition::new(cur, acc)
+    }
+
+    /// 状態 `start` から `k` ステップ進んだ先を返します。値は畳み込みません。
+    ///
+    /// # Panics
+    ///
+    /// `k` が `max_steps` より大きい場合パニックです。
+    pub fn advance(&self, start: usize, k: u64) -> usize {
+        assert!(start < self.n);
+        assert!(k <= self.max_steps);
+        let mut cur = start;
+        for j in 0..self.levels() {
+            if k >> j & 1 == 1 {
+                cur = self.table[j * self.n + cur].next;
+            }
+        }
+        cur
+    }
+
+    /// 状態 `start` から `k` ステップ進む間の値を畳み込んだ値を `v_k` として、`pred(&v_k)` が `true` となる最大の `k` (`max_steps` 以下) を返します。
+    ///
+    /// `pred` は単調である、つまり `pred(&v_k)` が `true` なら `pred(&v_{k - 1})` も `true` であるとします。`pred(&e)` が `false` でも `0` を返します。
+    ///
+    /// # Examples
+    /// ```
+    /// use doubling::{Doubling, Transition};
+    ///
+    /// // i -> i + 1 に進み、距離 d[i] を足していく
+    /// let d = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    /// let n = d.len();
+    /// let doubling = Doubling::new(n + 1, n as u64, 0, |a, b| a + b, |i| {
+    ///     if i < n {
+    ///         Transition::new(i + 1, d[i])
+    ///     } else {
+    ///         Transition::new(n, 0)
+    ///     }
+    /// });
+    /// // 状態 2 から合計 10 以下で何ステップ進めるか: 4 + 1 + 5
+    /// assert_eq!(doubling.max_steps_while(2, |&sum| sum <= 10), 3);
+    /// ```
+    pub fn max_steps_while<P>(&self, start: usize, mut pred: P) -> u64
+    where
+        P: FnMut(&V) -> bool,
+    {
+        assert!(start < self.n);
+        let mut cur = start;
+        let mut acc = self.e.clone();
+        let mut steps = 0;
+        for j in (0..self.levels()).rev() {
+            if steps + (1 << j) > self.max_steps {
+                continue;
+            }
+            let t = &self.table[j * self.n + cur];
+            let next_acc = (self.multiply)(&acc, &t.value);
+            if pred(&next_acc) {
+                acc = next_acc;
+                cur = t.next;
+                steps += 1 << j;
+            }
+        }
+        steps
+    }
+}
+
+/// 値を持たないダブリングです。各状態の遷移先だけを持ちます。
+///
+/// [`Doubling`] と違い、`2^j` ステップ先の表は必要になったときに作ります。`k` ステップ先を求めると、`k` のビット長までの表が作られます。
+///
+/// # Examples
+/// ```
+/// use doubling::SuccessorDoubling;
+///
+/// let mut doubling = SuccessorDoubling::new(vec![1, 2, 0, 0]);
+/// assert_eq!(doubling.advance(3, 0), 3);
+/// assert_eq!(doubling.advance(3, 2), 1);
+/// // 3 -> 0 -> 1 -> 2 -> 0 -> ...
+/// assert_eq!(doubling.advance(3, 1_000_000_000_000_000_000), 0);
+/// ```
+pub struct SuccessorDoubling {
+    // table[j][i]: 状態 i から 2^j ステップ進んだ先
+    table: Vec<Vec<usize>>,
+}
+
+impl SuccessorDoubling {
+    /// 状態 `i` から 1 ステップで `next[i]` に進むとして作ります。
+    ///
+    /// # Panics
+    ///
+    /// `next` の要素が `next.len()` 以上の場合パニックです。
+    pub fn new(next: Vec<usize>) -> Self {
+        let n = next.len();
+        assert!(next.iter().all(|&x| x < n));
+        Self { table: vec![next] }
+    }
+
+    /// 状態 `start` から `k` ステップ進んだ先を返します。
+    ///
+    /// 必要な表がまだなければ作るので、O(`n` log `k`) 時間かかることがあります。表ができていれば O(log `k`) 時間です。
+    pub fn advance(&mut self, start: usize, k: u64) -> usize {
+        assert!(start < self.table[0].len());
+        let log = (64 - k.leading_zeros()) as usize;
+        while self.table.len() < log {
+            let last = self.table.last().unwrap();
+            let next = last.iter().map(|&x| last[x]).collect();
+            self.table.push(next);
+        }
+        let mut cur = start;
+        for j in 0..log {
+            if k >> j & 1 == 1 {
+                cur = self.table[j][cur];
+            }
+        }
+        cur
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_fold() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1, 10);
+            let next = (0..n).map(|_| rng.gen_range(0, n)).collect::<Vec<_>>();
+            let value = (0..n).map(|_| rng.gen_range(0, 100)).collect::<Vec<u64>>();
+            let max_steps = rng.gen_range(0, 100);
+            // 値を順番どおりに並べる
+            let doubling = Doubling::new(
+                n,
+                max_steps,
+                Vec::new(),
+                |a: &Vec<u64>, b: &Vec<u64>| a.iter().chain(b).copied().collect(),
+                |i| Transition::new(next[i], vec![value[i]]),
+            );
+            for start in 0..n {
+                let mut cur = start;
+                let mut values = Vec::new();
+                for k in 0..=max_steps {
+                    assert_eq!(
+                        doubling.fold(start, k),
+                        Transition::new(cur, values.clone())
+                    );
+                    assert_eq!(doubling.advance(start, k), cur);
+                    values.push(value[cur]);
+                    cur = next[cur];
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_steps_while() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1, 10);
+            let next = (0..n).map(|_| rng.gen_range(0, n)).collect::<Vec<_>>();
+            let value = (0..n).map(|_| rng.gen_range(0, 10)).collect::<Vec<u64>>();
+            let max_steps = rng.gen_range(0, 100);
+            let doubling = Doubling::new(
+                n,
+                max_steps,
+                0,
+                |a, b| a + b,
+                |i| Transition::new(next[i], value[i]),
+            );
+            for start in 0..n {
+                let limit = rng.gen_range(0, 200);
+                let expected = (0..=max_steps)
+                    .take_while(|&k| doubling.fold(start, k).value <= limit)
+                    .last()
+                    .unwrap();
+                assert_eq!(doubling.max_steps_while(start, |&s| s <= limit), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_successor_doubling() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1, 10);
+            let next = (0..n).map(|_| rng.gen_range(0, n)).collect::<Vec<_>>();
+            let mut doubling = SuccessorDoubling::new(next.clone());
+            for start in 0..n {
+                let mut cur = start;
+                for k in 0..100 {
+                    assert_eq!(doubling.advance(start, k), cur);
+                    cur = next[cur];
+                }
+            }
+            let expected =
+                Doubling::new(n, u64::MAX, (), |_, _| (), |i| Transition::new(next[i], ()));
+            for start in 0..n {
+                let k = rng.gen::<u64>();
+                assert_eq!(doubling.advance(start, k), expected.advance(start, k));
+            }
+        }
+    }
+}
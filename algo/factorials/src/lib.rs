@@ -1,3 +1,5 @@
+use mod_int::ModInt;
+
 /// 階乗とその乗法逆元、そして二項係数を扱います。
 pub struct Factorial {
     factorial: Vec<u64>,
@@ -142,11 +144,297 @@ impl Factorial {
         }
         self.binomial(n, k)
     }
+
+    /// 二項係数を返します。`n` が構築時の `size` 以上でもよく、O(`k`) 時間で計算します。`n` が `k` より小さいときは `0` です。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let modulo = 1_000_000_007;
+    /// let f = Factorial::new_checking_modulo_prime(10, modulo);
+    /// assert_eq!(f.binomial_large_n(1_000_000_000_000_000_000, 0), 1);
+    /// assert_eq!(f.binomial_large_n(1_000_000_000_000_000_000, 1), 1_000_000_000_000_000_000 % modulo);
+    /// assert_eq!(f.binomial_large_n(100, 3), 161700);
+    /// assert_eq!(f.binomial_large_n(2, 3), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `k` が構築時の `size` 以上の場合パニックです。
+    pub fn binomial_large_n(&self, n: u64, k: usize) -> u64 {
+        assert!(k < self.inversion_of_factorial.len());
+        if n < k as u64 {
+            return 0;
+        }
+        (0..k as u64).fold(self.inversion(k), |acc, i| {
+            acc * ((n - i) % self.modulo) % self.modulo
+        })
+    }
+
+    /// Lucas の定理を使って二項係数を O(log `n`) 時間で計算します。`n` が `k` より小さいときは `0` です。
+    ///
+    /// 構築時の `size` と `modulo` が等しい必要があります。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let f = Factorial::new_checking_modulo_prime(7, 7);
+    /// // 10 = 1 * 7 + 3, 4 = 0 * 7 + 4
+    /// // C(1, 0) * C(3, 4) = 0
+    /// assert_eq!(f.binomial_lucas(10, 4), 0);
+    /// // C(10, 3) = 120
+    /// assert_eq!(f.binomial_lucas(10, 3), 120 % 7);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// 構築時の `size` と `modulo` が異なる場合パニックです。
+    pub fn binomial_lucas(&self, n: u64, k: u64) -> u64 {
+        assert_eq!(self.factorial.len() as u64, self.modulo);
+        let p = self.modulo;
+        let (mut n, mut k) = (n, k);
+        let mut result = 1;
+        while k > 0 {
+            let (ni, ki) = ((n % p) as usize, (k % p) as usize);
+            if ni < ki {
+                return 0;
+            }
+            result = result * self.binomial(ni, ki) % p;
+            n /= p;
+            k /= p;
+        }
+        result
+    }
+
+    /// 多項係数 `(k[0] + k[1] + ... + k[m - 1])! / (k[0]! * k[1]! * ... * k[m - 1]!)` を返します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let f = Factorial::new_checking_modulo_prime(10, 107);
+    /// // "aabbbc" の並べ替え
+    /// assert_eq!(f.multinomial(&[2, 3, 1]), 60);
+    /// assert_eq!(f.multinomial(&[3, 4]), f.binomial(7, 3));
+    /// assert_eq!(f.multinomial(&[]), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `k` の総和が構築時の `size` 以上の場合パニックです。
+    pub fn multinomial(&self, k: &[usize]) -> u64 {
+        let n = k.iter().sum::<usize>();
+        assert!(n < self.factorial.len());
+        k.iter().fold(self.factorial(n), |acc, &k| {
+            acc * self.inversion(k) % self.modulo
+        })
+    }
+
+    /// カタラン数 `(2n)! / (n! * (n + 1)!)` を返します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let f = Factorial::new_checking_modulo_prime(20, 1_000_000_007);
+    /// let catalan = (0..6).map(|n| f.catalan(n)).collect::<Vec<_>>();
+    /// assert_eq!(catalan, vec![1, 1, 2, 5, 14, 42]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `2n` が構築時の `size` 以上の場合パニックです。
+    pub fn catalan(&self, n: usize) -> u64 {
+        assert!(n * 2 < self.factorial.len());
+        self.factorial(n * 2) * self.inversion(n) % self.modulo * self.inversion(n + 1)
+            % self.modulo
+    }
+
+    /// 下降階乗 `n * (n - 1) * ... * (n - k + 1)` を返します。`n` が `k` より小さいときは `0` です。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let f = Factorial::new_checking_modulo_prime(10, 107);
+    /// assert_eq!(f.falling(5, 2), 20);
+    /// assert_eq!(f.falling(5, 0), 1);
+    /// assert_eq!(f.falling(2, 5), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `n` が構築時の `size` 以上の場合パニックです。
+    pub fn falling(&self, n: usize, k: usize) -> u64 {
+        assert!(n < self.factorial.len());
+        if n < k {
+            return 0;
+        }
+        self.factorial(n) * self.inversion(n - k) % self.modulo
+    }
+
+    /// 上昇階乗 `n * (n + 1) * ... * (n + k - 1)` を返します。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use factorials::Factorial;
+    ///
+    /// let f = Factorial::new_checking_modulo_prime(10, 107);
+    /// assert_eq!(f.rising(2, 3), 24);
+    /// assert_eq!(f.rising(5, 0), 1);
+    /// assert_eq!(f.rising(0, 3), 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// `k >= 1` かつ `n + k - 1` が構築時の `size` 以上の場合パニックです。
+    pub fn rising(&self, n: usize, k: usize) -> u64 {
+        if k == 0 {
+            return 1;
+        }
+        if n == 0 {
+            return 0;
+        }
+        self.falling(n + k - 1, k)
+    }
+}
+
+/// 素数 `p` を法とする二項係数を Lucas の定理を使って O(`p` + log `n`) 時間で計算します。
+///
+/// 同じ `p` について何度も計算する場合は [`Factorial::binomial_lucas`] を使ってください。
+///
+/// # Examples
+///
+/// ```
+/// use factorials::binomial_lucas;
+///
+/// assert_eq!(binomial_lucas(5, 2, 3), 10 % 3);
+/// assert_eq!(binomial_lucas(1_000_000_000_000_000_000, 1, 3), 1);
+/// assert_eq!(binomial_lucas(1_000_000_000_000_000_000, 2, 3), 0);
+/// ```
+///
+/// [`Factorial::binomial_lucas`]: struct.Factorial.html#method.binomial_lucas
+pub fn binomial_lucas(n: u64, k: u64, p: u64) -> u64 {
+    assert!(p >= 2);
+    Factorial::new(p as usize, p).binomial_lucas(n, k)
+}
+
+/// 完全順列の数 (モンモール数) `D(0), D(1), ..., D(n - 1)` (mod `modulo`) を O(`n`) 時間で求めます。
+///
+/// `D(i)` は `i` 要素の置換 `p` のうち、全ての `j` について `p(j) != j` となるものの個数です。
+///
+/// # Examples
+///
+/// ```
+/// use factorials::derangements;
+///
+/// assert_eq!(derangements(7, 1_000_000_007), vec![1, 0, 1, 2, 9, 44, 265]);
+/// ```
+pub fn derangements(n: usize, modulo: u64) -> Vec<u64> {
+    let mut d = vec![0; n];
+    if n >= 1 {
+        d[0] = 1 % modulo;
+    }
+    for i in 2..n {
+        // D(i) = (i - 1) * (D(i - 1) + D(i - 2))
+        d[i] = (i as u64 - 1) % modulo * ((d[i - 1] + d[i - 2]) % modulo) % modulo;
+    }
+    d
+}
+
+/// [`Factorial`] の [`ModInt`] 版です。各メソッドは `ModInt<M>` を返すので、そのまま `ModInt` の式に混ぜて使えます。
+///
+/// # Examples
+///
+/// ```
+/// use factorials::ModIntFactorial;
+/// use mod_int::ModInt998244353;
+///
+/// let f = ModIntFactorial::<998244353>::new(100);
+/// let x: ModInt998244353 = f.binomial(5, 2) * 3 + f.factorial(4);
+/// assert_eq!(x.val(), 10 * 3 + 24);
+/// assert_eq!((f.factorial(10) * f.inversion(10)).val(), 1);
+/// ```
+///
+/// [`Factorial`]: struct.Factorial.html
+/// [`ModInt`]: ../mod_int/struct.ModInt.html
+pub struct ModIntFactorial<const M: i64> {
+    factorial: Vec<ModInt<M>>,
+    inversion_of_factorial: Vec<ModInt<M>>,
+}
+
+impl<const M: i64> ModIntFactorial<M> {
+    /// `0` 以上 `size` 未満の `n` について、`n` の階乗 (mod `M`) と、その乗法逆元を O(`size`) 時間で計算します。
+    ///
+    /// # Panics
+    ///
+    /// `M` が `size` より小さい場合パニックです。
+    pub fn new(size: usize) -> Self {
+        assert!(size >= 1);
+        assert!(M >= size as i64);
+        let mut fac = vec![ModInt::new(1); size];
+        for i in 1..size {
+            fac[i] = fac[i - 1] * i;
+        }
+        let mut inv_of_fac = vec![ModInt::new(1); size];
+        inv_of_fac[size - 1] = fac[size - 1].inv();
+        for i in (1..size).rev() {
+            inv_of_fac[i - 1] = inv_of_fac[i] * i;
+        }
+        Self {
+            factorial: fac,
+            inversion_of_factorial: inv_of_fac,
+        }
+    }
+
+    pub fn factorial(&self, n: usize) -> ModInt<M> {
+        assert!(n < self.factorial.len());
+        self.factorial[n]
+    }
+
+    pub fn inversion(&self, n: usize) -> ModInt<M> {
+        assert!(n < self.inversion_of_factorial.len());
+        self.inversion_of_factorial[n]
+    }
+
+    /// 二項係数を返します。
+    ///
+    /// # Panics
+    ///
+    /// [`Factorial::binomial`] と同じ条件でパニックです。
+    ///
+    /// [`Factorial::binomial`]: struct.Factorial.html#method.binomial
+    pub fn binomial(&self, n: usize, k: usize) -> ModInt<M> {
+        assert!(n < self.factorial.len());
+        assert!(n >= k);
+        self.factorial(n) * self.inversion(k) * self.inversion(n - k)
+    }
+
+    /// [`binomial`] とほとんど同じですが `n` が `k` より小さいときパニックせずに `0` を返します。
+    ///
+    /// [`binomial`]: struct.ModIntFactorial.html#method.binomial
+    pub fn binomial_or_zero(&self, n: usize, k: usize) -> ModInt<M> {
+        assert!(n < self.factorial.len());
+        assert!(k < self.inversion_of_factorial.len());
+        if n < k {
+            return ModInt::new(0);
+        }
+        self.binomial(n, k)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Factorial;
+    use super::{derangements, Factorial, ModIntFactorial};
+    use next_permutation::NextPermutation;
     #[test]
     fn test_mod_is_103() {
         let p = 103;
@@ -174,4 +462,102 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn test_binomial_large_n() {
+        let p = 13;
+        let f = Factorial::new(5, p);
+        let big = Factorial::new(13, p);
+        for n in 0..13 {
+            for k in 0..5 {
+                assert_eq!(
+                    f.binomial_large_n(n as u64, k),
+                    big.binomial_or_zero(n, k),
+                    "n = {}, k = {}",
+                    n,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_binomial_lucas() {
+        for p in [2, 3, 5, 7] {
+            let f = Factorial::new(p as usize, p);
+            let mut binom = vec![vec![0; 60]; 60];
+            for n in 0..60 {
+                binom[n][0] = 1;
+                for k in 1..=n {
+                    binom[n][k] = (binom[n - 1][k - 1] + binom[n - 1][k]) % p;
+                }
+            }
+            for (n, row) in binom.iter().enumerate() {
+                for (k, &b) in row.iter().enumerate() {
+                    assert_eq!(f.binomial_lucas(n as u64, k as u64), b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_falling_rising() {
+        let p = 1_000_000_000 + 7;
+        let f = Factorial::new(20, p);
+        for n in 0..10 {
+            for k in 0..10 {
+                let falling =
+                    (0..k as u64).fold(1, |acc, i| acc * (n as u64).saturating_sub(i) % p);
+                assert_eq!(f.falling(n, k), falling);
+                let rising = (0..k as u64).fold(1, |acc, i| acc * (n as u64 + i) % p);
+                assert_eq!(f.rising(n, k), rising);
+            }
+        }
+    }
+
+    #[test]
+    fn test_catalan() {
+        let p = 1_000_000_000 + 7;
+        let expected = [1, 1, 2, 5, 14, 42, 132];
+        // size = 2n + 1 まで使える
+        let f = Factorial::new(13, p);
+        for (n, &c) in expected.iter().enumerate() {
+            assert_eq!(f.catalan(n), c);
+        }
+    }
+
+    #[test]
+    fn test_mod_int_factorial() {
+        let p = 1_000_000_000 + 7;
+        let f = Factorial::new(100, p);
+        let g = ModIntFactorial::<1_000_000_007>::new(100);
+        for n in 0..100 {
+            assert_eq!(f.factorial(n), g.factorial(n).val() as u64);
+            assert_eq!(f.inversion(n), g.inversion(n).val() as u64);
+            for k in 0..100 {
+                assert_eq!(
+                    f.binomial_or_zero(n, k),
+                    g.binomial_or_zero(n, k).val() as u64
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_derangements() {
+        let d = derangements(9, 1_000_000_007);
+        for (n, &expected) in d.iter().enumerate() {
+            let mut p = (0..n).collect::<Vec<_>>();
+            let mut count = 0;
+            loop {
+                if p.iter().enumerate().all(|(i, &x)| i != x) {
+                    count += 1;
+                }
+                if !p.next_permutation() {
+                    break;
+                }
+            }
+            assert_eq!(expected, count);
+        }
+    }
 }
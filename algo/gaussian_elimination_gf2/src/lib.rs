@@ -0,0 +1,184 @@
+use bitset::BitSet;
+
+/// GF(2) 上の行列 `a` の階数を返します。`a[i]` が `i` 行目です。
+///
+/// 行数 `m`、列数 `n` として O(`min(m, n) * m * n / 64`) 時間です。
+///
+/// # Examples
+/// ```
+/// use bitset::BitSet;
+/// use gaussian_elimination_gf2::rank;
+///
+/// let a = vec![
+///     [true, true, false].into_iter().collect::<BitSet>(),
+///     [false, true, true].into_iter().collect::<BitSet>(),
+///     [true, false, true].into_iter().collect::<BitSet>(), // 1 行目 + 2 行目
+/// ];
+/// assert_eq!(rank(&a), 2);
+/// ```
+pub fn rank(a: &[BitSet]) -> usize {
+    let mut a = a.to_vec();
+    let mut rhs = vec![false; a.len()];
+    eliminate(&mut a, &mut rhs).len()
+}
+
+/// GF(2) 上の連立一次方程式 `a x = b` を解きます。
+///
+/// 解が存在する場合は、解のひとつ `x` と、`a y = 0` の解空間の基底 `kernel` を `Some((x, kernel))` として返します。解全体は `x` に `kernel` の要素の線形結合を足したものです。解が存在しない場合は `None` を返します。
+///
+/// # Panics
+///
+/// `a` の行の長さが揃っていない場合、または `a.len()` と `b.len()` が異なる場合パニックです。
+///
+/// # Examples
+/// ```
+/// use bitset::BitSet;
+/// use gaussian_elimination_gf2::solve;
+///
+/// // x0 + x1      = 1
+/// //      x1 + x2 = 0
+/// let a = vec![
+///     [true, true, false].into_iter().collect::<BitSet>(),
+///     [false, true, true].into_iter().collect::<BitSet>(),
+/// ];
+/// let (x, kernel) = solve(&a, &[true, false]).unwrap();
+/// assert_eq!(x.ones().collect::<Vec<_>>(), vec![0]);
+/// assert_eq!(kernel.len(), 1);
+/// assert_eq!(kernel[0].ones().collect::<Vec<_>>(), vec![0, 1, 2]);
+///
+/// // x0 = 1
+/// // x0 = 0
+/// let a = vec![
+///     [true].into_iter().collect::<BitSet>(),
+///     [true].into_iter().collect::<BitSet>(),
+/// ];
+/// assert!(solve(&a, &[true, false]).is_none());
+/// ```
+pub fn solve(a: &[BitSet], b: &[bool]) -> Option<(BitSet, Vec<BitSet>)> {
+    assert_eq!(a.len(), b.len());
+    let n = a.first().map_or(0, |row| row.len());
+    let mut a = a.to_vec();
+    let mut rhs = b.to_vec();
+    let pivots = eliminate(&mut a, &mut rhs);
+    if rhs[pivots.len()..].iter().any(|&b| b) {
+        return None;
+    }
+    let mut x = BitSet::new(n);
+    for (i, &c) in pivots.iter().enumerate() {
+        x.set(c, rhs[i]);
+    }
+    let mut is_pivot = vec![false; n];
+    for &c in &pivots {
+        is_pivot[c] = true;
+    }
+    let mut kernel = Vec::new();
+    for f in (0..n).filter(|&f| !is_pivot[f]) {
+        // 自由変数 f だけを 1 にする
+        let mut y = BitSet::new(n);
+        y.set(f, true);
+        for (i, &c) in pivots.iter().enumerate() {
+            if a[i].get(f) {
+                y.set(c, true);
+            }
+        }
+        kernel.push(y);
+    }
+    Some((x, kernel))
+}
+
+// 行基本変形で a を簡約階段形にし、ピボット列を返す
+fn eliminate(a: &mut [BitSet], rhs: &mut [bool]) -> Vec<usize> {
+    let m = a.len();
+    let n = a.first().map_or(0, |row| row.len());
+    for row in a.iter() {
+        assert_eq!(row.len(), n);
+    }
+    let mut pivots = Vec::new();
+    for c in 0..n {
+        let r = pivots.len();
+        if r == m {
+            break;
+        }
+        let Some(p) = (r..m).find(|&i| a[i].get(c)) else {
+            continue;
+        };
+        a.swap(r, p);
+        rhs.swap(r, p);
+        let (pivot_row, pivot_rhs) = (a[r].clone(), rhs[r]);
+        for i in 0..m {
+            if i != r && a[i].get(c) {
+                a[i] ^= &pivot_row;
+                rhs[i] ^= pivot_rhs;
+            }
+        }
+        pivots.push(c);
+    }
+    pivots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(m: usize, n: usize, seed: u64) -> Vec<BitSet> {
+        let mut x = seed;
+        (0..m)
+            .map(|_| {
+                (0..n)
+                    .map(|_| {
+                        x ^= x << 13;
+                        x ^= x >> 7;
+                        x ^= x << 17;
+                        x % 3 == 0
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn mul(a: &[BitSet], x: &BitSet) -> Vec<bool> {
+        a.iter()
+            .map(|row| row.ones().filter(|&j| x.get(j)).count() % 2 == 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_solve_brute_force() {
+        for seed in 1..=200 {
+            let m = (seed % 5 + 1) as usize;
+            let n = (seed % 7 + 1) as usize;
+            let a = matrix(m, n, seed);
+            let b = (0..m).map(|i| (seed >> i) & 1 == 1).collect::<Vec<_>>();
+            let solutions = (0..1 << n)
+                .map(|s: usize| (0..n).map(|j| s >> j & 1 == 1).collect::<BitSet>())
+                .filter(|x| mul(&a, x) == b)
+                .count();
+            let kernel_size = (0..1 << n)
+                .map(|s: usize| (0..n).map(|j| s >> j & 1 == 1).collect::<BitSet>())
+                .filter(|x| mul(&a, x).iter().all(|&b| !b))
+                .count();
+            assert_eq!(1 << (n - rank(&a)), kernel_size);
+            match solve(&a, &b) {
+                Some((x, kernel)) => {
+                    assert_eq!(mul(&a, &x), b);
+                    assert_eq!(1 << kernel.len(), solutions);
+                    for y in &kernel {
+                        assert!(mul(&a, y).iter().all(|&b| !b));
+                    }
+                }
+                None => assert_eq!(solutions, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let n = 300;
+        let a = matrix(n, n, 42);
+        let x = matrix(1, n, 43).pop().unwrap();
+        let b = mul(&a, &x);
+        let (y, kernel) = solve(&a, &b).unwrap();
+        assert_eq!(mul(&a, &y), b);
+        assert_eq!(kernel.len(), n - rank(&a));
+    }
+}
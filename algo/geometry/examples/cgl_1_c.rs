@@ -0,0 +1,24 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=CGL_1_C
+// tags: geometry
+use geometry::{ccw, Ccw, Point};
+use proconio::{fastout, input};
+
+#[fastout]
+fn main() {
+    input! {
+        p0: (i64, i64),
+        p1: (i64, i64),
+        q: usize,
+        p2: [(i64, i64); q],
+    }
+    for p2 in p2 {
+        let ans = match ccw(Point::from(p0), Point::from(p1), Point::from(p2)) {
+            Ccw::CounterClockwise => "COUNTER_CLOCKWISE",
+            Ccw::Clockwise => "CLOCKWISE",
+            Ccw::OnlineBack => "ONLINE_BACK",
+            Ccw::OnlineFront => "ONLINE_FRONT",
+            Ccw::OnSegment => "ON_SEGMENT",
+        };
+        println!("{}", ans);
+    }
+}
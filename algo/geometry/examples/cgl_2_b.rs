@@ -0,0 +1,17 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=CGL_2_B
+// tags: geometry
+use geometry::{segments_intersect, Point};
+use proconio::{fastout, input};
+
+#[fastout]
+fn main() {
+    input! {
+        q: usize,
+        queries: [[(i64, i64); 4]; q],
+    }
+    for ps in queries {
+        let ps = ps.into_iter().map(Point::from).collect::<Vec<_>>();
+        let ans = segments_intersect(ps[0], ps[1], ps[2], ps[3]);
+        println!("{}", if ans { 1 } else { 0 });
+    }
+}
@@ -0,0 +1,23 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=CGL_3_C
+// tags: geometry
+use geometry::{point_in_polygon, Containment, Point};
+use proconio::{fastout, input};
+
+#[fastout]
+fn main() {
+    input! {
+        n: usize,
+        polygon: [(i64, i64); n],
+        q: usize,
+        points: [(i64, i64); q],
+    }
+    let polygon = polygon.into_iter().map(Point::from).collect::<Vec<_>>();
+    for p in points {
+        let ans = match point_in_polygon(Point::from(p), &polygon) {
+            Containment::Inside => 2,
+            Containment::OnBoundary => 1,
+            Containment::Outside => 0,
+        };
+        println!("{}", ans);
+    }
+}
@@ -0,0 +1,90 @@
+use std::cmp::Ordering;
+
+use crate::point::Point;
+
+// 原点なら 0, 偏角が [0, π) なら 1, [π, 2π) なら 2
+fn half(p: Point) -> u8 {
+    if p.x == 0 && p.y == 0 {
+        0
+    } else if p.y > 0 || (p.y == 0 && p.x > 0) {
+        1
+    } else {
+        2
+    }
+}
+
+/// 原点から見た偏角で `a` と `b` を比較します。`atan2` を使わず整数の外積だけで比較するので誤差がありません。
+///
+/// 偏角は `[0, 2π)` の範囲で考えます。つまり x 軸の正の部分が最小で、反時計回りに大きくなります。原点は偏角を持たないので、原点以外のどの点よりも小さいとします。
+/// 偏角が等しい点どうしは `Ordering::Equal` です。
+///
+/// # Examples
+/// ```
+/// use geometry::{cmp_by_arg, Point};
+/// use std::cmp::Ordering;
+///
+/// let p = |x, y| Point::new(x, y);
+/// assert_eq!(cmp_by_arg(p(1, 0), p(0, 1)), Ordering::Less);
+/// assert_eq!(cmp_by_arg(p(-1, 0), p(0, -1)), Ordering::Less);
+/// assert_eq!(cmp_by_arg(p(0, -1), p(1, 1)), Ordering::Greater);
+/// assert_eq!(cmp_by_arg(p(1, 1), p(3, 3)), Ordering::Equal);
+/// assert_eq!(cmp_by_arg(p(0, 0), p(1, 0)), Ordering::Less);
+/// ```
+pub fn cmp_by_arg(a: Point, b: Point) -> Ordering {
+    half(a).cmp(&half(b)).then_with(|| 0.cmp(&a.cross(b)))
+}
+
+/// `points` を原点から見た偏角の昇順に安定ソートします。偏角の定義は [`cmp_by_arg`] と同じです。
+///
+/// [`cmp_by_arg`]: fn.cmp_by_arg.html
+///
+/// # Examples
+/// ```
+/// use geometry::{sort_by_arg, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// let mut points = vec![p(0, -1), p(-1, 0), p(2, 0), p(1, 1), p(-1, -1), p(1, 0)];
+/// sort_by_arg(&mut points);
+/// assert_eq!(points, vec![p(2, 0), p(1, 0), p(1, 1), p(-1, 0), p(-1, -1), p(0, -1)]);
+/// ```
+pub fn sort_by_arg(points: &mut [Point]) {
+    points.sort_by(|&a, &b| cmp_by_arg(a, b));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cmp_by_arg() {
+        let mut points = Vec::new();
+        for x in -5..=5 {
+            for y in -5..=5 {
+                points.push(Point::new(x, y));
+            }
+        }
+        let arg = |p: Point| {
+            let t = (p.y as f64).atan2(p.x as f64);
+            if t < 0.0 {
+                t + std::f64::consts::PI * 2.0
+            } else {
+                t
+            }
+        };
+        let origin = Point::new(0, 0);
+        for &a in &points {
+            for &b in &points {
+                let (s, t) = (arg(a), arg(b));
+                // 原点はほかのどの点よりも小さい
+                let expected = if a == origin || b == origin {
+                    (a != origin).cmp(&(b != origin))
+                } else if (s - t).abs() < 1e-12 {
+                    Ordering::Equal
+                } else {
+                    s.partial_cmp(&t).unwrap()
+                };
+                assert_eq!(cmp_by_arg(a, b), expected, "{:?} {:?}", a, b);
+            }
+        }
+    }
+}
@@ -0,0 +1,532 @@
+//! 座標が `f64` の幾何です。円など、整数座標では扱えないものを扱います。
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// 誤差の許容範囲です。
+pub const EPS: f64 = 1e-9;
+
+/// 座標が `f64` の点 (またはベクトル) です。
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    /// 内積を返します。
+    pub fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// 外積を返します。
+    pub fn cross(self, other: Self) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// 長さの 2 乗を返します。
+    pub fn norm2(self) -> f64 {
+        self.dot(self)
+    }
+
+    /// 長さを返します。
+    pub fn norm(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// 反時計回りに 90 度回転したベクトルを返します。
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f64> for Point {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<f64> for Point {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl From<crate::Point> for Point {
+    fn from(p: crate::Point) -> Self {
+        Self::new(p.x as f64, p.y as f64)
+    }
+}
+
+/// 中心 `center`、半径 `r` の円です。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub r: f64,
+}
+
+impl Circle {
+    pub fn new(center: Point, r: f64) -> Self {
+        Self { center, r }
+    }
+}
+
+/// 点 `p` から直線 `ab` に下ろした垂線の足を返します。
+///
+/// # Panics
+///
+/// `a` と `b` が同じ点の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{projection, Point};
+///
+/// let (a, b) = (Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+/// assert!((projection(Point::new(2.0, 0.0), a, b) - Point::new(1.0, 1.0)).norm() < 1e-9);
+/// // 線分の外側でもよい
+/// assert!((projection(Point::new(-3.0, -1.0), a, b) - Point::new(-2.0, -2.0)).norm() < 1e-9);
+/// ```
+pub fn projection(p: Point, a: Point, b: Point) -> Point {
+    let ab = b - a;
+    assert!(ab.norm2() > 0.0);
+    a + ab * ((p - a).dot(ab) / ab.norm2())
+}
+
+/// 直線 `ab` に関して点 `p` と対称な点を返します。
+///
+/// # Panics
+///
+/// `a` と `b` が同じ点の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{reflection, Point};
+///
+/// let (a, b) = (Point::new(0.0, 0.0), Point::new(2.0, 2.0));
+/// assert!((reflection(Point::new(2.0, 0.0), a, b) - Point::new(0.0, 2.0)).norm() < 1e-9);
+/// ```
+pub fn reflection(p: Point, a: Point, b: Point) -> Point {
+    projection(p, a, b) * 2.0 - p
+}
+
+/// 点 `p` と線分 `ab` の距離を返します。`a` と `b` は同じ点でもよいです。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{distance_point_segment, Point};
+///
+/// let (a, b) = (Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+/// assert!((distance_point_segment(Point::new(1.0, 2.0), a, b) - 2.0).abs() < 1e-9);
+/// assert!((distance_point_segment(Point::new(7.0, 4.0), a, b) - 5.0).abs() < 1e-9);
+/// ```
+pub fn distance_point_segment(p: Point, a: Point, b: Point) -> f64 {
+    let ab = b - a;
+    if ab.norm2() < EPS * EPS || (p - a).dot(ab) < 0.0 {
+        (p - a).norm()
+    } else if (p - b).dot(a - b) < 0.0 {
+        (p - b).norm()
+    } else {
+        ab.cross(p - a).abs() / ab.norm()
+    }
+}
+
+/// 線分 `ab` と線分 `cd` の距離を返します。共有点を持つ場合は `0` です。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{distance_segments, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// assert!((distance_segments(p(0.0, 0.0), p(4.0, 0.0), p(1.0, 1.0), p(3.0, 2.0)) - 1.0).abs() < 1e-9);
+/// // 交差する
+/// assert!(distance_segments(p(0.0, 0.0), p(4.0, 4.0), p(0.0, 4.0), p(4.0, 0.0)).abs() < 1e-9);
+/// ```
+pub fn distance_segments(a: Point, b: Point, c: Point, d: Point) -> f64 {
+    let sign = |x: f64| {
+        if x > EPS {
+            1
+        } else if x < -EPS {
+            -1
+        } else {
+            0
+        }
+    };
+    let (ab, cd) = (b - a, d - c);
+    // 真に交差する場合。端点で接する場合などは端点と線分の距離が 0 になる
+    if sign(ab.cross(c - a)) * sign(ab.cross(d - a)) < 0
+        && sign(cd.cross(a - c)) * sign(cd.cross(b - c)) < 0
+    {
+        return 0.0;
+    }
+    [
+        distance_point_segment(a, c, d),
+        distance_point_segment(b, c, d),
+        distance_point_segment(c, a, b),
+        distance_point_segment(d, a, b),
+    ]
+    .into_iter()
+    .fold(f64::INFINITY, f64::min)
+}
+
+/// 2 円の交点を返します。接する場合は 1 点、交わらない場合と 2 円が一致する場合は空です。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{circle_circle_intersection, Circle, Point};
+///
+/// let c1 = Circle::new(Point::new(0.0, 0.0), 2.0);
+/// let c2 = Circle::new(Point::new(2.0, 0.0), 2.0);
+/// let ps = circle_circle_intersection(c1, c2);
+/// assert_eq!(ps.len(), 2);
+/// assert!((ps[0] - Point::new(1.0, -3_f64.sqrt())).norm() < 1e-9);
+/// assert!((ps[1] - Point::new(1.0, 3_f64.sqrt())).norm() < 1e-9);
+///
+/// // 外接
+/// let c3 = Circle::new(Point::new(5.0, 0.0), 3.0);
+/// assert_eq!(circle_circle_intersection(c1, c3).len(), 1);
+/// ```
+pub fn circle_circle_intersection(c1: Circle, c2: Circle) -> Vec<Point> {
+    let v = c2.center - c1.center;
+    let d2 = v.norm2();
+    if d2 < EPS * EPS {
+        return Vec::new();
+    }
+    let d = d2.sqrt();
+    if d > c1.r + c2.r + EPS || d < (c1.r - c2.r).abs() - EPS {
+        return Vec::new();
+    }
+    // c1.center から交点を結ぶ線分の中点までの距離
+    let p = (d2 + c1.r * c1.r - c2.r * c2.r) / (2.0 * d);
+    let h2 = c1.r * c1.r - p * p;
+    let mid = c1.center + v * (p / d);
+    if h2 <= EPS {
+        return vec![mid];
+    }
+    let h = v.perp() * (h2.sqrt() / d);
+    vec![mid - h, mid + h]
+}
+
+/// 円 `c` と直線 `ab` の交点を返します。接する場合は 1 点、交わらない場合は空です。
+///
+/// # Panics
+///
+/// `a` と `b` が同じ点の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{circle_line_intersection, Circle, Point};
+///
+/// let c = Circle::new(Point::new(0.0, 0.0), 5.0);
+/// let ps = circle_line_intersection(c, Point::new(-10.0, 3.0), Point::new(10.0, 3.0));
+/// assert_eq!(ps.len(), 2);
+/// assert!((ps[0] - Point::new(-4.0, 3.0)).norm() < 1e-9);
+/// assert!((ps[1] - Point::new(4.0, 3.0)).norm() < 1e-9);
+/// ```
+pub fn circle_line_intersection(c: Circle, a: Point, b: Point) -> Vec<Point> {
+    let ab = b - a;
+    // 中心から直線に下ろした垂線の足
+    let foot = projection(c.center, a, b);
+    let dist2 = (c.center - foot).norm2();
+    let h2 = c.r * c.r - dist2;
+    if h2 < -EPS {
+        return Vec::new();
+    }
+    if h2 <= EPS {
+        return vec![foot];
+    }
+    let h = ab * (h2.sqrt() / ab.norm());
+    vec![foot - h, foot + h]
+}
+
+/// 点 `p` から円 `c` に引いた接線の接点を返します。`p` が円周上にあれば `p` 自身、円の内部にあれば空です。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{tangent_points, Circle, Point};
+///
+/// let c = Circle::new(Point::new(0.0, 0.0), 1.0);
+/// let ps = tangent_points(c, Point::new(2.0, 0.0));
+/// assert_eq!(ps.len(), 2);
+/// for q in ps {
+///     // 接点で半径と接線が直交する
+///     assert!(q.dot(q - Point::new(2.0, 0.0)).abs() < 1e-9);
+/// }
+/// ```
+pub fn tangent_points(c: Circle, p: Point) -> Vec<Point> {
+    let d = (p - c.center).norm();
+    if d < c.r - EPS {
+        return Vec::new();
+    }
+    // 中心 p、半径 sqrt(d^2 - r^2) の円との交点
+    let l = (d * d - c.r * c.r).max(0.0).sqrt();
+    circle_circle_intersection(c, Circle::new(p, l))
+}
+
+/// 2 円の共通接線を、それぞれの円との接点の組 `(c1 上の点, c2 上の点)` の列として返します。
+///
+/// 共通接線の本数は 2 円の位置関係によって 0 本から 4 本です。接する場合の、接点を通る接線では 2 つの点は同じになります。2 円が一致する場合は空です。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{common_tangents, Circle, Point};
+///
+/// let c1 = Circle::new(Point::new(0.0, 0.0), 1.0);
+/// let c2 = Circle::new(Point::new(4.0, 0.0), 1.0);
+/// assert_eq!(common_tangents(c1, c2).len(), 4);
+/// let c3 = Circle::new(Point::new(2.0, 0.0), 1.0);
+/// assert_eq!(common_tangents(c1, c3).len(), 3);
+/// let c4 = Circle::new(Point::new(2.0, 0.0), 3.0);
+/// assert_eq!(common_tangents(c1, c4).len(), 1);
+/// let c5 = Circle::new(Point::new(0.0, 0.0), 3.0);
+/// assert_eq!(common_tangents(c1, c5).len(), 0);
+/// ```
+pub fn common_tangents(c1: Circle, c2: Circle) -> Vec<(Point, Point)> {
+    let mut result = Vec::new();
+    // 共通外接線, 共通内接線
+    for r2 in [c2.r, -c2.r] {
+        let d = c2.center - c1.center;
+        let dr = c1.r - r2;
+        let d2 = d.norm2();
+        let h2 = d2 - dr * dr;
+        if d2 < EPS * EPS || h2 < -EPS {
+            continue;
+        }
+        let h = h2.max(0.0).sqrt();
+        let signs: &[f64] = if h2 <= EPS { &[1.0] } else { &[-1.0, 1.0] };
+        for &sign in signs {
+            // 接点への単位ベクトル
+            let v = (d * dr + d.perp() * (h * sign)) / d2;
+            result.push((c1.center + v * c1.r, c2.center + v * r2));
+        }
+    }
+    result
+}
+
+/// 凸多角形 `polygon` を有向直線 `a -> b` で切断し、直線の左側 (直線上を含む) の部分を返します。
+///
+/// `polygon` の頂点は反時計回りに並んでいる必要があります。返り値も反時計回りです。
+///
+/// # Examples
+/// ```
+/// use geometry::float::{convex_cut, Point};
+///
+/// let square = vec![
+///     Point::new(0.0, 0.0),
+///     Point::new(2.0, 0.0),
+///     Point::new(2.0, 2.0),
+///     Point::new(0.0, 2.0),
+/// ];
+/// // x = 1 の左側
+/// let left = convex_cut(&square, Point::new(1.0, 0.0), Point::new(1.0, 1.0));
+/// assert_eq!(
+///     left,
+///     vec![
+///         Point::new(0.0, 0.0),
+///         Point::new(1.0, 0.0),
+///         Point::new(1.0, 2.0),
+///         Point::new(0.0, 2.0),
+///     ]
+/// );
+/// ```
+pub fn convex_cut(polygon: &[Point], a: Point, b: Point) -> Vec<Point> {
+    let n = polygon.len();
+    let ab = b - a;
+    let mut result = Vec::new();
+    for i in 0..n {
+        let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+        let cp = ab.cross(p - a);
+        let cq = ab.cross(q - a);
+        if cp >= -EPS {
+            result.push(p);
+        }
+        if (cp > EPS && cq < -EPS) || (cp < -EPS && cq > EPS) {
+            // 辺 pq と直線の交点
+            result.push(p + (q - p) * (cp / (cp - cq)));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn on_circle(c: Circle, p: Point) -> bool {
+        ((p - c.center).norm() - c.r).abs() < 1e-6
+    }
+
+    #[test]
+    fn test_circle_circle_intersection() {
+        for x in -5..=5 {
+            for y in -5..=5 {
+                for r in 1..=4_i32 {
+                    let c1 = Circle::new(Point::new(0.0, 0.0), 3.0);
+                    let c2 = Circle::new(Point::new(x as f64, y as f64), r as f64);
+                    let ps = circle_circle_intersection(c1, c2);
+                    for &p in &ps {
+                        assert!(on_circle(c1, p) && on_circle(c2, p));
+                    }
+                    let d = ((x * x + y * y) as f64).sqrt();
+                    let expected = if (x == 0 && y == 0)
+                        || d > (3 + r) as f64 + 1e-9
+                        || d < (3 - r).abs() as f64 - 1e-9
+                    {
+                        0
+                    } else if (d - (3 + r) as f64).abs() < 1e-9
+                        || (d - (3 - r).abs() as f64).abs() < 1e-9
+                    {
+                        1
+                    } else {
+                        2
+                    };
+                    assert_eq!(ps.len(), expected, "{:?} {:?}", c1, c2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_common_tangents() {
+        for x in -5..=5 {
+            for y in -5..=5 {
+                for r in 1..=4_i32 {
+                    let c1 = Circle::new(Point::new(0.0, 0.0), 2.0);
+                    let c2 = Circle::new(Point::new(x as f64, y as f64), r as f64);
+                    let tangents = common_tangents(c1, c2);
+                    for &(p, q) in &tangents {
+                        assert!(on_circle(c1, p) && on_circle(c2, q));
+                        // 接線の方向
+                        let dir = if (q - p).norm() > 1e-9 {
+                            q - p
+                        } else {
+                            (p - c1.center).perp()
+                        };
+                        assert!((p - c1.center).dot(dir).abs() < 1e-6);
+                        assert!((q - c2.center).dot(dir).abs() < 1e-6);
+                    }
+                    // 共通接線の本数は交点の数から決まる
+                    let n = circle_circle_intersection(c1, c2).len();
+                    let d = ((x * x + y * y) as f64).sqrt();
+                    let expected = if x == 0 && y == 0 {
+                        0
+                    } else if n == 2 {
+                        2
+                    } else if n == 1 {
+                        if d > (2 + r) as f64 - 1e-9 {
+                            3
+                        } else {
+                            1
+                        }
+                    } else if d > (2 + r) as f64 {
+                        4
+                    } else {
+                        0
+                    };
+                    assert_eq!(tangents.len(), expected, "{:?} {:?}", c1, c2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_projection_reflection() {
+        let (a, b) = (Point::new(1.0, -2.0), Point::new(4.0, 3.0));
+        for x in -5..=5 {
+            for y in -5..=5 {
+                let p = Point::new(x as f64, y as f64);
+                let q = projection(p, a, b);
+                assert!((q - a).cross(b - a).abs() < 1e-9);
+                assert!((p - q).dot(b - a).abs() < 1e-9);
+                let r = reflection(p, a, b);
+                assert!(((p + r) / 2.0 - q).norm() < 1e-9);
+                assert!((reflection(r, a, b) - p).norm() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance_segments() {
+        let p = crate::Point::new;
+        // 線分上の点を細かく取って距離の最小値を求める
+        let sample = |a: Point, b: Point| {
+            (0..=200)
+                .map(move |i| a + (b - a) * (i as f64 / 200.0))
+                .collect::<Vec<_>>()
+        };
+        let segments = [
+            (p(0, 0), p(3, 0)),
+            (p(1, 1), p(2, 3)),
+            (p(-1, 2), p(3, -2)),
+            (p(2, 2), p(2, 2)),
+            (p(4, 0), p(6, 0)),
+            (p(3, 1), p(0, 4)),
+        ];
+        for &(a, b) in &segments {
+            for &(c, d) in &segments {
+                let actual = distance_segments(a.into(), b.into(), c.into(), d.into());
+                if crate::segments_intersect(a, b, c, d) {
+                    assert!(actual.abs() < 1e-9);
+                    continue;
+                }
+                let brute = sample(a.into(), b.into())
+                    .into_iter()
+                    .flat_map(|q| {
+                        sample(c.into(), d.into())
+                            .into_iter()
+                            .map(move |r| (q - r).norm())
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                assert!(actual <= brute + 1e-9);
+                assert!(actual > brute - 0.05);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convex_cut() {
+        let polygon = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(5.0, 3.0),
+            Point::new(1.0, 4.0),
+        ];
+        let area = |ps: &[Point]| {
+            let n = ps.len();
+            (0..n).map(|i| ps[i].cross(ps[(i + 1) % n])).sum::<f64>() / 2.0
+        };
+        for k in 0..36 {
+            let theta = std::f64::consts::PI * 2.0 * k as f64 / 36.0;
+            let a = Point::new(2.5, 2.0);
+            let b = a + Point::new(theta.cos(), theta.sin());
+            let left = convex_cut(&polygon, a, b);
+            let right = convex_cut(&polygon, b, a);
+            assert!((area(&left) + area(&right) - area(&polygon)).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::point::Point;
+use crate::polygon::area2;
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// 線分 `pq` 上 (端点を含む) の格子点の個数を返します。
+///
+/// # Examples
+/// ```
+/// use geometry::{lattice_points_on_segment, Point};
+///
+/// assert_eq!(lattice_points_on_segment(Point::new(0, 0), Point::new(6, 4)), 3);
+/// assert_eq!(lattice_points_on_segment(Point::new(1, 1), Point::new(1, 1)), 1);
+/// assert_eq!(lattice_points_on_segment(Point::new(-2, 5), Point::new(3, 5)), 6);
+/// ```
+pub fn lattice_points_on_segment(p: Point, q: Point) -> i64 {
+    let d = q - p;
+    gcd(d.x, d.y) + 1
+}
+
+/// 頂点が格子点である単純多角形 `polygon` の内部 (境界を除く) にある格子点の個数をピックの定理で返します。
+///
+/// 面積を `S`、内部の格子点の個数を `i`、境界上の格子点の個数を `b` として `S = i + b / 2 - 1` が成り立ちます。
+///
+/// # Examples
+/// ```
+/// use geometry::{interior_lattice_points, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// // 3x3 の正方形の内部には 2x2 個の格子点がある
+/// assert_eq!(interior_lattice_points(&[p(0, 0), p(3, 0), p(3, 3), p(0, 3)]), 4);
+/// assert_eq!(interior_lattice_points(&[p(0, 0), p(4, 0), p(0, 4)]), 3);
+/// ```
+pub fn interior_lattice_points(polygon: &[Point]) -> i128 {
+    let n = polygon.len();
+    let boundary = (0..n)
+        .map(|i| {
+            let d = polygon[(i + 1) % n] - polygon[i];
+            i128::from(gcd(d.x, d.y))
+        })
+        .sum::<i128>();
+    // 2S = 2i + b - 2
+    (area2(polygon).abs() - boundary + 2) / 2
+}
+
+/// 方向ベクトル `v` を正規化します。成分を最大公約数で割り、さらに `v` と `-v` が同じ結果になるように x 成分 (x 成分が `0` なら y 成分) を正にします。
+///
+/// 直線の傾きで重複を除くときに使えます。`v` がゼロベクトルのときはそのまま返します。
+///
+/// # Examples
+/// ```
+/// use geometry::{normalize_direction, Point};
+///
+/// assert_eq!(normalize_direction(Point::new(4, -6)), Point::new(2, -3));
+/// assert_eq!(normalize_direction(Point::new(-4, 6)), Point::new(2, -3));
+/// assert_eq!(normalize_direction(Point::new(0, -5)), Point::new(0, 1));
+/// assert_eq!(normalize_direction(Point::new(0, 0)), Point::new(0, 0));
+/// ```
+pub fn normalize_direction(v: Point) -> Point {
+    let g = gcd(v.x, v.y);
+    if g == 0 {
+        return v;
+    }
+    let v = Point::new(v.x / g, v.y / g);
+    if v.x < 0 || (v.x == 0 && v.y < 0) {
+        -v
+    } else {
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{on_segment, point_in_polygon, Containment};
+
+    #[test]
+    fn test_lattice_points_on_segment() {
+        for x in -4..=4 {
+            for y in -4..=4 {
+                let (p, q) = (Point::new(1, -1), Point::new(x, y));
+                let expected = (-5..=5)
+                    .flat_map(|x| (-5..=5).map(move |y| Point::new(x, y)))
+                    .filter(|&r| on_segment(r, p, q))
+                    .count();
+                assert_eq!(lattice_points_on_segment(p, q), expected as i64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interior_lattice_points() {
+        let p = Point::new;
+        let polygons = vec![
+            vec![p(0, 0), p(5, 1), p(2, 4)],
+            vec![p(0, 0), p(6, 0), p(6, 5), p(3, 2), p(0, 5)],
+            vec![p(-3, -1), p(0, -3), p(3, -1), p(3, 2), p(1, 4), p(-2, 3)],
+        ];
+        for polygon in polygons {
+            let expected = (-6..=6)
+                .flat_map(|x| (-6..=6).map(move |y| p(x, y)))
+                .filter(|&q| point_in_polygon(q, &polygon) == Containment::Inside)
+                .count();
+            assert_eq!(interior_lattice_points(&polygon), expected as i128);
+            let reversed = polygon.iter().rev().copied().collect::<Vec<_>>();
+            assert_eq!(interior_lattice_points(&reversed), expected as i128);
+        }
+    }
+}
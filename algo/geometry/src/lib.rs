@@ -0,0 +1,20 @@
+//! 平面幾何のライブラリです。
+//!
+//! 座標が整数の点 [`Point`] に対する判定は、浮動小数点数を使わずに誤差なしで行います。円など座標が `f64` のものは [`float`] モジュールにあります。
+//!
+//! 内積や外積は `i128` で計算するので、座標の絶対値が `10^9` 程度まではオーバーフローしません。
+
+mod angle;
+pub mod float;
+mod lattice;
+mod point;
+mod polygon;
+mod segment;
+mod sweep;
+
+pub use angle::{cmp_by_arg, sort_by_arg};
+pub use lattice::{interior_lattice_points, lattice_points_on_segment, normalize_direction};
+pub use point::{ccw, in_circle, Ccw, Point};
+pub use polygon::{area2, minkowski_sum, point_in_convex_polygon, point_in_polygon, Containment};
+pub use segment::{line_intersection, on_segment, segments_intersect};
+pub use sweep::any_segments_intersect;
@@ -0,0 +1,213 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::polygon::Containment;
+
+/// 座標が整数の点 (またはベクトル) です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// 内積を返します。オーバーフローしないように `i128` で計算します。
+    pub fn dot(self, other: Self) -> i128 {
+        i128::from(self.x) * i128::from(other.x) + i128::from(self.y) * i128::from(other.y)
+    }
+
+    /// 外積 `self.x * other.y - self.y * other.x` を返します。`other` が `self` から見て反時計回りの側にあるとき正です。
+    ///
+    /// オーバーフローしないように `i128` で計算します。
+    pub fn cross(self, other: Self) -> i128 {
+        i128::from(self.x) * i128::from(other.y) - i128::from(self.y) * i128::from(other.x)
+    }
+
+    /// 長さの 2 乗を返します。
+    pub fn norm2(self) -> i128 {
+        self.dot(self)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// 3 点 `a`, `b`, `c` の位置関係です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ccw {
+    /// `a -> b -> c` が反時計回り
+    CounterClockwise,
+    /// `a -> b -> c` が時計回り
+    Clockwise,
+    /// `c, a, b` の順に一直線上にある
+    OnlineBack,
+    /// `a, b, c` の順に一直線上にある
+    OnlineFront,
+    /// `c` が線分 `ab` 上にある
+    OnSegment,
+}
+
+/// 3 点 `a`, `b`, `c` の位置関係を返します。
+///
+/// # Examples
+/// ```
+/// use geometry::{ccw, Ccw, Point};
+///
+/// let a = Point::new(0, 0);
+/// let b = Point::new(2, 0);
+/// assert_eq!(ccw(a, b, Point::new(1, 1)), Ccw::CounterClockwise);
+/// assert_eq!(ccw(a, b, Point::new(1, -1)), Ccw::Clockwise);
+/// assert_eq!(ccw(a, b, Point::new(-1, 0)), Ccw::OnlineBack);
+/// assert_eq!(ccw(a, b, Point::new(3, 0)), Ccw::OnlineFront);
+/// assert_eq!(ccw(a, b, Point::new(1, 0)), Ccw::OnSegment);
+/// ```
+pub fn ccw(a: Point, b: Point, c: Point) -> Ccw {
+    let (b, c) = (b - a, c - a);
+    let cross = b.cross(c);
+    if cross > 0 {
+        Ccw::CounterClockwise
+    } else if cross < 0 {
+        Ccw::Clockwise
+    } else if b.dot(c) < 0 {
+        Ccw::OnlineBack
+    } else if b.norm2() < c.norm2() {
+        Ccw::OnlineFront
+    } else {
+        Ccw::OnSegment
+    }
+}
+
+/// 点 `d` と、3 点 `a`, `b`, `c` を通る円の位置関係を返します。
+///
+/// 行列式を `i128` で計算するので、座標の絶対値が `10^9` 以下であればオーバーフローせず誤差もありません。
+///
+/// # Panics
+///
+/// `a`, `b`, `c` が一直線上にある場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::{in_circle, Containment, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// // 原点中心、半径 5 の円
+/// let (a, b, c) = (p(5, 0), p(0, 5), p(-3, -4));
+/// assert_eq!(in_circle(a, b, c, p(1, 1)), Containment::Inside);
+/// assert_eq!(in_circle(a, b, c, p(4, 3)), Containment::OnBoundary);
+/// assert_eq!(in_circle(a, b, c, p(4, 4)), Containment::Outside);
+/// // 向きによらない
+/// assert_eq!(in_circle(c, b, a, p(1, 1)), Containment::Inside);
+/// ```
+pub fn in_circle(a: Point, b: Point, c: Point, d: Point) -> Containment {
+    let orientation = (b - a).cross(c - a).signum();
+    assert_ne!(orientation, 0);
+    let (a, b, c) = (a - d, b - d, c - d);
+    let det = a.norm2() * b.cross(c) + b.norm2() * c.cross(a) + c.norm2() * a.cross(b);
+    match (det * orientation).signum() {
+        1 => Containment::Inside,
+        0 => Containment::OnBoundary,
+        _ => Containment::Outside,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let p = Point::new(1, 2);
+        let q = Point::new(3, -4);
+        assert_eq!(p + q, Point::new(4, -2));
+        assert_eq!(p - q, Point::new(-2, 6));
+        assert_eq!(-p, Point::new(-1, -2));
+        assert_eq!(p * 3, Point::new(3, 6));
+        assert_eq!(p.dot(q), -5);
+        assert_eq!(p.cross(q), -10);
+        assert_eq!(q.norm2(), 25);
+    }
+
+    #[test]
+    fn test_ccw_rotation() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, 2);
+        for c in [Point::new(0, 5), Point::new(3, -2), Point::new(7, 4)] {
+            // 巡回しても向きは変わらない
+            assert_eq!(ccw(a, b, c), ccw(b, c, a));
+            assert_eq!(ccw(a, b, c), ccw(c, a, b));
+        }
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let m = 1_000_000_000;
+        let (a, b, c) = (Point::new(-m, -m), Point::new(m, -m), Point::new(m, m));
+        assert_eq!(ccw(a, b, c), Ccw::CounterClockwise);
+        assert_eq!(ccw(a, c, Point::new(m - 1, m - 1)), Ccw::OnSegment);
+        assert_eq!((b - a).cross(c - a), 4 * i128::from(m) * i128::from(m));
+        assert_eq!(
+            in_circle(a, b, c, Point::new(-m, m)),
+            Containment::OnBoundary
+        );
+        assert_eq!(in_circle(a, b, c, Point::new(0, 0)), Containment::Inside);
+        assert_eq!(
+            in_circle(a, b, c, Point::new(-m, m - 1)),
+            Containment::Inside
+        );
+        assert_eq!(
+            in_circle(a, b, c, Point::new(m, m - 1)),
+            Containment::Inside
+        );
+    }
+
+    #[test]
+    fn test_in_circle() {
+        // 原点中心、半径 5 の円
+        let (a, b, c) = (Point::new(5, 0), Point::new(0, 5), Point::new(-3, -4));
+        for x in -7..=7 {
+            for y in -7..=7 {
+                let expected = match (x * x + y * y).cmp(&25) {
+                    std::cmp::Ordering::Less => Containment::Inside,
+                    std::cmp::Ordering::Equal => Containment::OnBoundary,
+                    std::cmp::Ordering::Greater => Containment::Outside,
+                };
+                assert_eq!(in_circle(a, b, c, Point::new(x, y)), expected);
+                assert_eq!(in_circle(b, a, c, Point::new(x, y)), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::cmp::Ordering;
+
+use crate::point::Point;
+use crate::segment::on_segment;
+
+/// 点と多角形の位置関係です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Containment {
+    Inside,
+    OnBoundary,
+    Outside,
+}
+
+/// 多角形 `polygon` の符号付き面積の 2 倍を返します。頂点が反時計回りに並んでいるとき正です。
+///
+/// # Examples
+/// ```
+/// use geometry::{area2, Point};
+///
+/// let polygon = vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 3)];
+/// assert_eq!(area2(&polygon), 6);
+/// let reversed = polygon.into_iter().rev().collect::<Vec<_>>();
+/// assert_eq!(area2(&reversed), -6);
+/// ```
+pub fn area2(polygon: &[Point]) -> i128 {
+    let n = polygon.len();
+    (0..n).map(|i| polygon[i].cross(polygon[(i + 1) % n])).sum()
+}
+
+/// 点 `p` と単純多角形 `polygon` の位置関係を O(`n`) 時間で返します。頂点は時計回りでも反時計回りでも構いません。
+///
+/// # Examples
+/// ```
+/// use geometry::{point_in_polygon, Containment, Point};
+///
+/// // 凹多角形
+/// let p = |x, y| Point::new(x, y);
+/// let polygon = vec![p(0, 0), p(4, 0), p(4, 4), p(2, 1), p(0, 4)];
+/// assert_eq!(point_in_polygon(p(1, 1), &polygon), Containment::Inside);
+/// assert_eq!(point_in_polygon(p(2, 3), &polygon), Containment::Outside);
+/// assert_eq!(point_in_polygon(p(2, 1), &polygon), Containment::OnBoundary);
+/// assert_eq!(point_in_polygon(p(4, 2), &polygon), Containment::OnBoundary);
+/// ```
+pub fn point_in_polygon(p: Point, polygon: &[Point]) -> Containment {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if on_segment(p, a, b) {
+            return Containment::OnBoundary;
+        }
+        // p から右に伸ばした半直線と辺 ab が交わるか
+        let (a, b) = if a.y <= b.y { (a, b) } else { (b, a) };
+        if a.y <= p.y && p.y < b.y && (b - a).cross(p - a) > 0 {
+            inside = !inside;
+        }
+    }
+    if inside {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+/// 点 `p` と凸多角形 `polygon` の位置関係を O(log `n`) 時間で返します。
+///
+/// 頂点は反時計回りに並んでいて、3 つ以上ある必要があります。また、一直線上に並ぶ 3 頂点があってはいけません。
+///
+/// # Panics
+///
+/// 頂点が 3 つ未満の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::{point_in_convex_polygon, Containment, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// let polygon = vec![p(0, 0), p(4, 0), p(6, 3), p(3, 5), p(0, 3)];
+/// assert_eq!(point_in_convex_polygon(p(3, 2), &polygon), Containment::Inside);
+/// assert_eq!(point_in_convex_polygon(p(5, 4), &polygon), Containment::Outside);
+/// assert_eq!(point_in_convex_polygon(p(2, 0), &polygon), Containment::OnBoundary);
+/// assert_eq!(point_in_convex_polygon(p(0, 2), &polygon), Containment::OnBoundary);
+/// ```
+pub fn point_in_convex_polygon(p: Point, polygon: &[Point]) -> Containment {
+    let n = polygon.len();
+    assert!(n >= 3);
+    let a = polygon[0];
+    let first = (polygon[1] - a).cross(p - a);
+    let last = (polygon[n - 1] - a).cross(p - a);
+    if first < 0 || last > 0 {
+        return Containment::Outside;
+    }
+    if first == 0 || last == 0 {
+        let b = if first == 0 {
+            polygon[1]
+        } else {
+            polygon[n - 1]
+        };
+        return if on_segment(p, a, b) {
+            Containment::OnBoundary
+        } else {
+            Containment::Outside
+        };
+    }
+    // cross(polygon[i] - a, p - a) >= 0 となる最大の i
+    let (mut ok, mut ng) = (1, n - 1);
+    while ng - ok > 1 {
+        let mid = (ok + ng) / 2;
+        if (polygon[mid] - a).cross(p - a) >= 0 {
+            ok = mid;
+        } else {
+            ng = mid;
+        }
+    }
+    match (polygon[ok + 1] - polygon[ok])
+        .cross(p - polygon[ok])
+        .cmp(&0)
+    {
+        Ordering::Greater => Containment::Inside,
+        Ordering::Equal => Containment::OnBoundary,
+        Ordering::Less => Containment::Outside,
+    }
+}
+
+/// 凸多角形 `p` と `q` のミンコフスキー和 `{a + b | a ∈ p, b ∈ q}` を O(`n` + `m`) 時間で返します。
+///
+/// `p`, `q` の頂点は反時計回りに並んでいる必要があります。返り値は y 座標 (同じなら x 座標) が最小の頂点から始まる反時計回りの凸多角形で、同じ向きの辺はまとめられます。
+///
+/// # Panics
+///
+/// `p` または `q` が空の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::{minkowski_sum, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// let square = vec![p(0, 0), p(1, 0), p(1, 1), p(0, 1)];
+/// let triangle = vec![p(0, 0), p(2, 0), p(0, 2)];
+/// assert_eq!(
+///     minkowski_sum(&square, &triangle),
+///     vec![p(0, 0), p(3, 0), p(3, 1), p(1, 3), p(0, 3)]
+/// );
+/// ```
+pub fn minkowski_sum(p: &[Point], q: &[Point]) -> Vec<Point> {
+    assert!(!p.is_empty() && !q.is_empty());
+    let rotate = |ps: &[Point]| {
+        let start = (0..ps.len()).min_by_key(|&i| (ps[i].y, ps[i].x)).unwrap();
+        let mut ps = ps.to_vec();
+        ps.rotate_left(start);
+        ps
+    };
+    let (mut p, mut q) = (rotate(p), rotate(q));
+    let (n, m) = (p.len(), q.len());
+    // 辺 p[i + 1] - p[i] を i = n でも使えるようにする
+    p.extend([p[0], p[1 % n]]);
+    q.extend([q[0], q[1 % m]]);
+    let mut result = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        result.push(p[i] + q[j]);
+        let c = (p[i + 1] - p[i]).cross(q[j + 1] - q[j]);
+        // 偏角の小さい方の辺を進む
+        if c >= 0 && i < n {
+            i += 1;
+        }
+        if c <= 0 && j < m {
+            j += 1;
+        }
+    }
+    // 一直線上に並ぶ頂点を除く
+    let mut hull: Vec<Point> = Vec::with_capacity(result.len());
+    for v in result {
+        while hull.len() >= 2 {
+            let k = hull.len();
+            if (hull[k - 1] - hull[k - 2]).cross(v - hull[k - 1]) == 0 {
+                hull.pop();
+            } else {
+                break;
+            }
+        }
+        hull.push(v);
+    }
+    while hull.len() >= 3 {
+        let k = hull.len();
+        if (hull[k - 1] - hull[k - 2]).cross(hull[0] - hull[k - 1]) == 0 {
+            hull.pop();
+        } else {
+            break;
+        }
+    }
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convex_and_general() {
+        let p = Point::new;
+        let polygons = vec![
+            vec![p(0, 0), p(3, 0), p(0, 3)],
+            vec![p(0, 0), p(4, 0), p(4, 4), p(0, 4)],
+            vec![p(-3, -1), p(0, -3), p(3, -1), p(3, 2), p(1, 4), p(-2, 3)],
+        ];
+        for polygon in polygons {
+            assert!(area2(&polygon) > 0);
+            for x in -6..=6 {
+                for y in -6..=6 {
+                    let q = p(x, y);
+                    assert_eq!(
+                        point_in_convex_polygon(q, &polygon),
+                        point_in_polygon(q, &polygon),
+                        "{:?} {:?}",
+                        q,
+                        polygon
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick() {
+        // ピックの定理 S = i + b / 2 - 1 で内部の格子点の数を確かめる
+        let p = Point::new;
+        let polygon = vec![p(0, 0), p(6, 0), p(6, 5), p(3, 2), p(0, 5)];
+        let mut inside = 0;
+        let mut boundary = 0;
+        for x in -1..=7 {
+            for y in -1..=6 {
+                match point_in_polygon(p(x, y), &polygon) {
+                    Containment::Inside => inside += 1,
+                    Containment::OnBoundary => boundary += 1,
+                    Containment::Outside => {}
+                }
+            }
+        }
+        assert_eq!(area2(&polygon), 2 * inside + boundary - 2);
+    }
+
+    #[test]
+    fn test_minkowski_sum() {
+        let p = Point::new;
+        let polygons = vec![
+            vec![p(0, 0)],
+            vec![p(0, 0), p(3, 0), p(0, 3)],
+            vec![p(1, 1), p(4, 1), p(4, 4), p(1, 4)],
+            vec![p(-3, -1), p(0, -3), p(3, -1), p(3, 2), p(1, 4), p(-2, 3)],
+            vec![p(0, 0), p(2, -1), p(3, 1)],
+        ];
+        for a in &polygons {
+            for b in &polygons {
+                let sum = minkowski_sum(a, b);
+                // 全ての a[i] + b[j] が和の内部か境界にあり、和の頂点はどれかの a[i] + b[j]
+                for &u in a {
+                    for &v in b {
+                        if sum.len() >= 3 {
+                            assert_ne!(point_in_polygon(u + v, &sum), Containment::Outside);
+                        }
+                    }
+                }
+                for &w in &sum {
+                    assert!(a.iter().any(|&u| b.iter().any(|&v| u + v == w)));
+                }
+                // 面積は a と b の混合面積を含む
+                assert!(area2(&sum) >= area2(a) + area2(b));
+            }
+        }
+    }
+}
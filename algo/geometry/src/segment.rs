@@ -0,0 +1,141 @@
+use crate::point::Point;
+
+/// 点 `p` が線分 `ab` 上 (端点を含む) にあるかどうかを返します。
+///
+/// # Examples
+/// ```
+/// use geometry::{on_segment, Point};
+///
+/// let a = Point::new(0, 0);
+/// let b = Point::new(4, 2);
+/// assert!(on_segment(Point::new(2, 1), a, b));
+/// assert!(on_segment(Point::new(4, 2), a, b));
+/// assert!(!on_segment(Point::new(6, 3), a, b));
+/// assert!(!on_segment(Point::new(1, 1), a, b));
+/// ```
+pub fn on_segment(p: Point, a: Point, b: Point) -> bool {
+    let (a, b) = (a - p, b - p);
+    a.cross(b) == 0 && a.dot(b) <= 0
+}
+
+/// 線分 `ab` と線分 `cd` が共有点を持つかどうかを返します。端点で接する場合や重なる場合も `true` です。
+///
+/// # Examples
+/// ```
+/// use geometry::{segments_intersect, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// assert!(segments_intersect(p(0, 0), p(2, 2), p(0, 2), p(2, 0)));
+/// // 端点で接する
+/// assert!(segments_intersect(p(0, 0), p(2, 2), p(2, 2), p(3, 0)));
+/// // 同一直線上で重なる
+/// assert!(segments_intersect(p(0, 0), p(2, 0), p(1, 0), p(3, 0)));
+/// assert!(!segments_intersect(p(0, 0), p(1, 0), p(2, 0), p(3, 0)));
+/// assert!(!segments_intersect(p(0, 0), p(2, 2), p(3, 0), p(3, 3)));
+/// ```
+pub fn segments_intersect(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let d1 = (b - a).cross(c - a).signum();
+    let d2 = (b - a).cross(d - a).signum();
+    let d3 = (d - c).cross(a - c).signum();
+    let d4 = (d - c).cross(b - c).signum();
+    if d1 * d2 < 0 && d3 * d4 < 0 {
+        return true;
+    }
+    on_segment(c, a, b) || on_segment(d, a, b) || on_segment(a, c, d) || on_segment(b, c, d)
+}
+
+/// 直線 `ab` と直線 `cd` の交点を有理数で返します。
+///
+/// 交点を `(x_num / x_den, y_num / y_den)` として `Some(((x_num, x_den), (y_num, y_den)))` を返します。分数は既約で、分母は正です。
+/// 2 直線が平行 (一致する場合を含む) のときは `None` を返します。
+///
+/// # Panics
+///
+/// `a == b` または `c == d` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use geometry::{line_intersection, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// assert_eq!(line_intersection(p(0, 0), p(2, 2), p(0, 2), p(2, 0)), Some(((1, 1), (1, 1))));
+/// // y = x と y = -2x + 1 の交点は (1/3, 1/3)
+/// assert_eq!(line_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, -1)), Some(((1, 3), (1, 3))));
+/// assert_eq!(line_intersection(p(0, 0), p(1, 1), p(0, 1), p(1, 2)), None);
+/// ```
+pub fn line_intersection(
+    a: Point,
+    b: Point,
+    c: Point,
+    d: Point,
+) -> Option<((i128, i128), (i128, i128))> {
+    assert_ne!(a, b);
+    assert_ne!(c, d);
+    let (u, v) = (b - a, d - c);
+    let den = u.cross(v);
+    if den == 0 {
+        return None;
+    }
+    // a + u * t, t = cross(c - a, v) / cross(u, v)
+    let t = (c - a).cross(v);
+    let x = i128::from(a.x) * den + i128::from(u.x) * t;
+    let y = i128::from(a.y) * den + i128::from(u.y) * t;
+    Some((reduce(x, den), reduce(y, den)))
+}
+
+fn reduce(num: i128, den: i128) -> (i128, i128) {
+    fn gcd(a: i128, b: i128) -> i128 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+    let g = gcd(num.abs(), den.abs());
+    let s = den.signum();
+    (s * num / g, s * den / g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments_intersect_brute_force() {
+        let range = -1..=2;
+        let points = range
+            .clone()
+            .flat_map(|x| range.clone().map(move |y| Point::new(x, y)))
+            .collect::<Vec<_>>();
+        for &a in &points {
+            for &b in &points {
+                for &c in &points {
+                    for &d in &points {
+                        assert_eq!(
+                            segments_intersect(a, b, c, d),
+                            brute_force(a, b, c, d),
+                            "{:?} {:?} {:?} {:?}",
+                            a,
+                            b,
+                            c,
+                            d
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // 線分 ab 上の点 a + (b - a) * p / q を分母 q ごとにすべて試して、線分 cd 上にあるものを探す
+    // 座標が -1..=2 なら、共有点の p / q は分母を |cross(b - a, d - c)| <= 18 にとれる (平行なら端点が共有点になる)
+    fn brute_force(a: Point, b: Point, c: Point, d: Point) -> bool {
+        (1..=18).any(|q| {
+            (0..=q).any(|p| {
+                // q 倍に拡大した座標で考える
+                let x = a * q + (b - a) * p;
+                let (c, d) = (c * q - x, d * q - x);
+                c.cross(d) == 0 && c.dot(d) <= 0
+            })
+        })
+    }
+}
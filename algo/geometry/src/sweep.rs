@@ -0,0 +1,157 @@
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+use crate::point::Point;
+use crate::segment::segments_intersect;
+
+#[derive(Clone, Copy)]
+struct Key {
+    // p <= q
+    p: Point,
+    q: Point,
+    id: usize,
+}
+
+impl Key {
+    // x での y 座標を分数 (分子, 分母) で返す。垂直な線分は下端の y 座標
+    fn y_at(&self, x: i64) -> (i128, i128) {
+        let dx = i128::from(self.q.x - self.p.x);
+        if dx == 0 {
+            return (i128::from(self.p.y), 1);
+        }
+        let dy = i128::from(self.q.y - self.p.y);
+        (
+            i128::from(self.p.y) * dx + dy * i128::from(x - self.p.x),
+            dx,
+        )
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    // 2 つの線分が両方とも存在する x 座標で y 座標を比べる
+    fn cmp(&self, other: &Self) -> Ordering {
+        let x = self.p.x.max(other.p.x);
+        let (a, b) = self.y_at(x);
+        let (c, d) = other.y_at(x);
+        (a * d).cmp(&(c * b)).then(self.id.cmp(&other.id))
+    }
+}
+
+/// 線分の集合の中に共有点を持つ 2 本があるかを平面走査で判定し、あれば添字の組 `(i, j)` (`i < j`) を返します。
+///
+/// Shamos–Hoey のアルゴリズムで O(`n` log `n`) 時間です。端点で接する場合や重なる場合も共有点を持つとみなします。
+/// 共有点を持つ組が複数あるときは、そのうちのどれかひとつを返します。
+///
+/// # Examples
+/// ```
+/// use geometry::{any_segments_intersect, Point};
+///
+/// let p = |x, y| Point::new(x, y);
+/// let segments = vec![
+///     (p(0, 0), p(4, 0)),
+///     (p(0, 2), p(4, 3)),
+///     (p(1, 5), p(3, 1)),
+/// ];
+/// assert_eq!(any_segments_intersect(&segments), Some((1, 2)));
+/// assert_eq!(any_segments_intersect(&segments[..2]), None);
+/// ```
+pub fn any_segments_intersect(segments: &[(Point, Point)]) -> Option<(usize, usize)> {
+    let keys = segments
+        .iter()
+        .enumerate()
+        .map(|(id, &(a, b))| {
+            let (p, q) = if a <= b { (a, b) } else { (b, a) };
+            Key { p, q, id }
+        })
+        .collect::<Vec<_>>();
+    // (x 座標, 0: 追加 / 1: 削除, 線分)。同じ x 座標では追加を先に処理する
+    let mut events = Vec::with_capacity(keys.len() * 2);
+    for k in &keys {
+        events.push((k.p.x, 0, k.p.y, k.id));
+        events.push((k.q.x, 1, k.q.y, k.id));
+    }
+    events.sort();
+    let check = |a: &Key, b: &Key| {
+        if segments_intersect(a.p, a.q, b.p, b.q) {
+            Some((a.id.min(b.id), a.id.max(b.id)))
+        } else {
+            None
+        }
+    };
+    let mut set = BTreeSet::new();
+    for (_, kind, _, id) in events {
+        let k = keys[id];
+        if kind == 0 {
+            let next = set.range(k..).next();
+            let prev = set.range(..k).next_back();
+            for other in next.into_iter().chain(prev) {
+                if let Some(found) = check(&k, other) {
+                    return Some(found);
+                }
+            }
+            set.insert(k);
+        } else {
+            let next = set.range(k..).nth(1);
+            let prev = set.range(..k).next_back();
+            if let (Some(a), Some(b)) = (prev, next) {
+                if let Some(found) = check(a, b) {
+                    return Some(found);
+                }
+            }
+            set.remove(&k);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_brute_force() {
+        let mut rng = thread_rng();
+        for m in [2, 5, 100] {
+            for n in 0..=8 {
+                for _ in 0..2000 {
+                    let segments = (0..n)
+                        .map(|_| {
+                            let mut p = || Point::new(rng.gen_range(0, m), rng.gen_range(0, m));
+                            (p(), p())
+                        })
+                        .collect::<Vec<_>>();
+                    let expected = (0..n).any(|i| {
+                        ((i + 1)..n).any(|j| {
+                            let (a, b) = segments[i];
+                            let (c, d) = segments[j];
+                            segments_intersect(a, b, c, d)
+                        })
+                    });
+                    let actual = any_segments_intersect(&segments);
+                    assert_eq!(actual.is_some(), expected, "{:?}", segments);
+                    if let Some((i, j)) = actual {
+                        assert!(i < j);
+                        let (a, b) = segments[i];
+                        let (c, d) = segments[j];
+                        assert!(segments_intersect(a, b, c, d));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! 二次元グリッド `Vec<Vec<T>>` を操作する関数です。
+//!
+//! グリッドは `h` 行 `w` 列の長方形である、つまり全ての行の長さが等しいとします。
+
+fn width<T>(grid: &[Vec<T>]) -> usize {
+    let w = grid.first().map_or(0, |row| row.len());
+    for row in grid {
+        assert_eq!(row.len(), w);
+    }
+    w
+}
+
+/// 転置したグリッドを返します。`h` 行 `w` 列のグリッドは `w` 行 `h` 列になります。
+///
+/// 列数が 0 のグリッドは 0 行のグリッドになります。0 行のグリッドは列数を持てないので、`h` 行 0 列のグリッドに 2 回適用しても元には戻りません。
+///
+/// # Panics
+///
+/// 行の長さが揃っていない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use grid_tools::transpose;
+///
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(transpose(&grid), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+/// ```
+pub fn transpose<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let w = width(grid);
+    (0..w)
+        .map(|j| grid.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// 時計回りに 90 度回転したグリッドを返します。`h` 行 `w` 列のグリッドは `w` 行 `h` 列になります。
+///
+/// 反時計回りに回転したい場合は 3 回適用してください。
+///
+/// 列数が 0 のグリッドは 0 行のグリッドになります。0 行のグリッドは列数を持てないので、`h` 行 0 列のグリッドに 4 回適用しても元には戻りません。
+///
+/// # Panics
+///
+/// 行の長さが揃っていない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use grid_tools::rotate90;
+///
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(rotate90(&grid), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+/// ```
+pub fn rotate90<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    let w = width(grid);
+    (0..w)
+        .map(|j| grid.iter().rev().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// 左右を反転したグリッドを返します。
+///
+/// # Examples
+/// ```
+/// use grid_tools::flip_h;
+///
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(flip_h(&grid), vec![vec![3, 2, 1], vec![6, 5, 4]]);
+/// ```
+pub fn flip_h<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter()
+        .map(|row| row.iter().rev().cloned().collect())
+        .collect()
+}
+
+/// 上下を反転したグリッドを返します。
+///
+/// # Examples
+/// ```
+/// use grid_tools::flip_v;
+///
+/// let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// assert_eq!(flip_v(&grid), vec![vec![4, 5, 6], vec![1, 2, 3]]);
+/// ```
+pub fn flip_v<T: Clone>(grid: &[Vec<T>]) -> Vec<Vec<T>> {
+    grid.iter().rev().cloned().collect()
+}
+
+/// 周囲を幅 1 の `border` で囲んだグリッドを返します。`h` 行 `w` 列のグリッドは `h + 2` 行 `w + 2` 列になります。
+///
+/// 番兵として使うと、隣接するマスを調べるときに範囲外の判定が要らなくなります。
+///
+/// # Panics
+///
+/// 行の長さが揃っていない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use grid_tools::pad;
+///
+/// let grid = vec![vec!['.', '#']];
+/// assert_eq!(
+///     pad(&grid, '#'),
+///     vec![
+///         vec!['#', '#', '#', '#'],
+///         vec!['#', '.', '#', '#'],
+///         vec!['#', '#', '#', '#'],
+///     ]
+/// );
+/// ```
+pub fn pad<T: Clone>(grid: &[Vec<T>], border: T) -> Vec<Vec<T>> {
+    let w = width(grid);
+    let mut result = Vec::with_capacity(grid.len() + 2);
+    result.push(vec![border.clone(); w + 2]);
+    for row in grid {
+        let mut new_row = Vec::with_capacity(w + 2);
+        new_row.push(border.clone());
+        new_row.extend(row.iter().cloned());
+        new_row.push(border.clone());
+        result.push(new_row);
+    }
+    result.push(vec![border; w + 2]);
+    result
+}
+
+/// 文字列の各行を文字のグリッドにします。
+///
+/// # Examples
+/// ```
+/// use grid_tools::parse_char_grid;
+///
+/// let input = "#..\n.#.\n";
+/// let grid = parse_char_grid(input.lines());
+/// assert_eq!(grid, vec![vec!['#', '.', '.'], vec!['.', '#', '.']]);
+/// ```
+pub fn parse_char_grid<I, S>(lines: I) -> Vec<Vec<char>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    lines
+        .into_iter()
+        .map(|line| line.as_ref().chars().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate() {
+        let grid = vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]];
+        let mut g = rotate90(&grid);
+        for _ in 0..3 {
+            g = rotate90(&g);
+        }
+        assert_eq!(g, grid);
+        // 180 度回転は上下左右の反転
+        assert_eq!(rotate90(&rotate90(&grid)), flip_v(&flip_h(&grid)));
+        // 時計回りの回転は転置して左右反転
+        assert_eq!(rotate90(&grid), flip_h(&transpose(&grid)));
+        assert_eq!(transpose(&transpose(&grid)), grid);
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid: Vec<Vec<i32>> = vec![];
+        assert_eq!(transpose(&grid), Vec::<Vec<i32>>::new());
+        assert_eq!(rotate90(&grid), Vec::<Vec<i32>>::new());
+        assert_eq!(pad(&grid, 0), vec![vec![0, 0], vec![0, 0]]);
+    }
+
+    #[test]
+    fn test_zero_width() {
+        let grid: Vec<Vec<i32>> = vec![vec![], vec![], vec![]];
+        assert_eq!(transpose(&grid), Vec::<Vec<i32>>::new());
+        assert_eq!(rotate90(&grid), Vec::<Vec<i32>>::new());
+        assert_eq!(flip_h(&grid), grid);
+        assert_eq!(flip_v(&grid), grid);
+        assert_eq!(pad(&grid, 0), vec![vec![0, 0]; 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_jagged() {
+        transpose(&[vec![1, 2], vec![3]]);
+    }
+}
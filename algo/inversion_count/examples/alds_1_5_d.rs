@@ -0,0 +1,13 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=ALDS1_5_D
+
+use proconio::input;
+
+use inversion_count::inversion_count;
+
+fn main() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+    println!("{}", inversion_count(&a));
+}
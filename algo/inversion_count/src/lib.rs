@@ -0,0 +1,122 @@
+use fenwick_tree::FenwickTree;
+use zarts::SortedSeq;
+
+/// 列 `a` の転倒数、つまり `i < j` かつ `a[i] > a[j]` を満たす組 `(i, j)` の個数を返します。
+///
+/// 座標圧縮して Fenwick Tree で数えるので O(`n` log `n`) 時間です。
+///
+/// # Examples
+/// ```
+/// use inversion_count::inversion_count;
+///
+/// assert_eq!(inversion_count(&[3, 1, 2]), 2);
+/// assert_eq!(inversion_count(&[5, 4, 3, 2, 1]), 10);
+/// // 等しい値の組は数えない
+/// assert_eq!(inversion_count(&[2, 2, 1, 1]), 4);
+/// assert_eq!(inversion_count::<u32>(&[]), 0);
+/// ```
+pub fn inversion_count<T: Ord>(a: &[T]) -> u64 {
+    let seq = SortedSeq::new(a.iter());
+    let mut ft = FenwickTree::new(seq.size(), 0_u64);
+    let mut result = 0;
+    for (i, x) in a.iter().enumerate() {
+        let k = seq.ord(&x);
+        // 前にある x 以下の値を除く
+        result += i as u64 - ft.sum(..=k);
+        ft.add(k, 1);
+    }
+    result
+}
+
+/// 隣接する 2 項の交換を繰り返して列 `a` を列 `b` に並べ替えるときの、交換回数の最小値を返します。
+///
+/// 等しい値は、`a` で `k` 番目に現れるものを `b` で `k` 番目に現れるものに対応させます。`b` が昇順のときは [`inversion_count`] と同じです。
+///
+/// # Panics
+///
+/// `b` が `a` の並べ替えでない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use inversion_count::inversion_count_between;
+///
+/// assert_eq!(inversion_count_between(&[1, 2, 3], &[3, 1, 2]), 2);
+/// assert_eq!(inversion_count_between(&['a', 'b', 'a'], &['a', 'a', 'b']), 1);
+/// ```
+pub fn inversion_count_between<T: Ord>(a: &[T], b: &[T]) -> u64 {
+    assert_eq!(a.len(), b.len());
+    let mut ia = (0..a.len()).collect::<Vec<_>>();
+    let mut ib = (0..b.len()).collect::<Vec<_>>();
+    ia.sort_by_key(|&i| &a[i]);
+    ib.sort_by_key(|&i| &b[i]);
+    // target[i]: a[i] を移す先の位置
+    let mut target = vec![0; a.len()];
+    for (&i, &j) in ia.iter().zip(&ib) {
+        assert!(a[i] == b[j], "b is not a permutation of a");
+        target[i] = j;
+    }
+    inversion_count(&target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    fn brute_force(a: &[u32]) -> u64 {
+        let n = a.len();
+        let mut result = 0;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                if a[i] > a[j] {
+                    result += 1;
+                }
+            }
+        }
+        result
+    }
+
+    // バブルソートの交換回数
+    fn bubble_sort_swaps(a: &[u32], b: &[u32]) -> u64 {
+        let mut a = a.to_vec();
+        let mut result = 0;
+        for (i, x) in b.iter().enumerate() {
+            let j = (i..a.len()).find(|&j| &a[j] == x).unwrap();
+            for k in (i..j).rev() {
+                a.swap(k, k + 1);
+                result += 1;
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_inversion_count() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 30);
+            let m = rng.gen_range(1, 10);
+            let a = (0..n).map(|_| rng.gen_range(0, m)).collect::<Vec<u32>>();
+            assert_eq!(inversion_count(&a), brute_force(&a));
+        }
+    }
+
+    #[test]
+    fn test_inversion_count_between() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 30);
+            let m = rng.gen_range(1, 10);
+            let a = (0..n).map(|_| rng.gen_range(0, m)).collect::<Vec<u32>>();
+            let mut b = a.clone();
+            b.shuffle(&mut rng);
+            assert_eq!(inversion_count_between(&a, &b), bubble_sort_swaps(&a, &b));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_not_permutation() {
+        inversion_count_between(&[1, 2, 2], &[1, 1, 2]);
+    }
+}
@@ -0,0 +1,127 @@
+use mod_int::ModInt;
+
+/// きたまさ法で線形漸化式の第 `k` 項を O(`d^2 log k`) 時間で求めます。`d` は漸化式の次数 `a.len()` です。
+///
+/// 数列 `x` を次で定めます。
+///
+/// - `x[i] = a[i]` (`0 <= i < d`)
+/// - `x[i + d] = c[0] * x[i] + c[1] * x[i + 1] + ... + c[d - 1] * x[i + d - 1]`
+///
+/// [実装の参考資料](https://qiita.com/tmaehara/items/0687af2cfb807cde7860)
+///
+/// # Panics
+///
+/// `a` が空の場合、または `a` と `c` の長さが異なる場合パニックです。
+///
+/// # Examples
+///
+/// ```
+/// use kitamasa::kitamasa;
+/// use mod_int::ModInt998244353;
+///
+/// // フィボナッチ数列
+/// // x[i + 2] = x[i] + x[i + 1]
+/// let a = vec![ModInt998244353::new(0), ModInt998244353::new(1)];
+/// let c = vec![ModInt998244353::new(1), ModInt998244353::new(1)];
+/// assert_eq!(kitamasa(&a, &c, 0).val(), 0);
+/// assert_eq!(kitamasa(&a, &c, 1).val(), 1);
+/// assert_eq!(kitamasa(&a, &c, 10).val(), 55);
+/// assert_eq!(kitamasa(&a, &c, 1_000_000_000_000_000_000).val(), 23849548);
+/// ```
+pub fn kitamasa<const M: i64>(a: &[ModInt<M>], c: &[ModInt<M>], k: u64) -> ModInt<M> {
+    assert!(!a.is_empty());
+    assert_eq!(a.len(), c.len());
+
+    let d = a.len();
+    if k < d as u64 {
+        return a[k as usize];
+    }
+
+    // x^k mod (x^d - c[d - 1] * x^(d - 1) - ... - c[0]) の係数
+    let mut coef = vec![ModInt::new(0); d];
+    coef[0] = ModInt::new(1);
+    for i in (0..u64::BITS - k.leading_zeros()).rev() {
+        coef = square(&coef, c);
+        if k >> i & 1 == 1 {
+            coef = mul_x(&coef, c);
+        }
+    }
+    coef.iter()
+        .zip(a)
+        .fold(ModInt::new(0), |acc, (&p, &x)| acc + p * x)
+}
+
+// p(x) * x mod f(x)
+fn mul_x<const M: i64>(p: &[ModInt<M>], c: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let d = p.len();
+    let top = p[d - 1];
+    let mut result = vec![ModInt::new(0); d];
+    for i in 0..d {
+        if i >= 1 {
+            result[i] = p[i - 1];
+        }
+        result[i] += top * c[i];
+    }
+    result
+}
+
+// p(x) * p(x) mod f(x)
+fn square<const M: i64>(p: &[ModInt<M>], c: &[ModInt<M>]) -> Vec<ModInt<M>> {
+    let d = p.len();
+    let mut prod = vec![ModInt::new(0); d * 2 - 1];
+    for i in 0..d {
+        for j in 0..d {
+            prod[i + j] += p[i] * p[j];
+        }
+    }
+    // x^d = c[0] + c[1] * x + ... + c[d - 1] * x^(d - 1)
+    for i in (d..(d * 2 - 1)).rev() {
+        let t = prod[i];
+        for j in 0..d {
+            prod[i - d + j] += t * c[j];
+        }
+    }
+    prod.truncate(d);
+    prod
+}
+
+#[cfg(test)]
+mod tests {
+    use super::kitamasa;
+    use mod_int::ModInt;
+
+    type Mint = ModInt<1_000_000_007>;
+
+    #[test]
+    fn test_fibonacci() {
+        let a = vec![Mint::new(0), Mint::new(1)];
+        let c = vec![Mint::new(1), Mint::new(1)];
+        let mut fib = vec![0, 1];
+        for i in 2..100 {
+            fib.push((fib[i - 2] + fib[i - 1]) % 1_000_000_007);
+        }
+        for (k, &f) in fib.iter().enumerate() {
+            assert_eq!(kitamasa(&a, &c, k as u64).val(), f);
+        }
+    }
+
+    #[test]
+    fn test_naive() {
+        for d in 1..=5 {
+            let a = (0..d)
+                .map(|i| Mint::new(i as i64 * 3 + 1))
+                .collect::<Vec<_>>();
+            let c = (0..d)
+                .map(|i| Mint::new(i as i64 * 7 - 2))
+                .collect::<Vec<_>>();
+            let mut x = a.clone();
+            for i in d..200 {
+                let next = (0..d).fold(Mint::new(0), |acc, j| acc + c[j] * x[i - d + j]);
+                x.push(next);
+            }
+            for (k, y) in x.iter().enumerate() {
+                assert_eq!(kitamasa(&a, &c, k as u64).val(), y.val());
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=ALDS1_10_C
+
+use longest_common_subsequence::lcs_length;
+use proconio::input;
+use proconio::marker::Bytes;
+
+fn main() {
+    input! {
+        q: usize,
+    };
+    for _ in 0..q {
+        input! {
+            x: Bytes,
+            y: Bytes,
+        };
+        println!("{}", lcs_length(&x, &y));
+    }
+}
@@ -0,0 +1,188 @@
+/// 列 `a` と列 `b` の最長共通部分列の長さを返します。
+///
+/// O(`nm`) 時間、O(`m`) 空間です (`n = a.len()`, `m = b.len()`)。
+///
+/// # Examples
+/// ```
+/// use longest_common_subsequence::lcs_length;
+///
+/// assert_eq!(lcs_length(b"abcbdab", b"bdcaba"), 4);
+/// assert_eq!(lcs_length(b"abc", b""), 0);
+/// ```
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    lcs_row(a, b)[b.len()]
+}
+
+/// 列 `a` と列 `b` の最長共通部分列をひとつ求めて、対応する添字の組 `(i, j)` (`a[i] == b[j]`) の列として返します。`i` も `j` も昇順です。
+///
+/// O(`nm`) 時間、O(`nm`) 空間です。`n` と `m` が大きくメモリが足りない場合は [`lcs_hirschberg`] を使ってください。
+///
+/// # Examples
+/// ```
+/// use longest_common_subsequence::lcs;
+///
+/// let a = b"abcbdab";
+/// let b = b"bdcaba";
+/// let pairs = lcs(a, b);
+/// assert_eq!(pairs.len(), 4);
+/// let s = pairs.iter().map(|&(i, _)| a[i] as char).collect::<String>();
+/// assert_eq!(s, "bcba");
+/// ```
+pub fn lcs<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    // dp[i][j]: a[..i] と b[..j] の最長共通部分列の長さ
+    let mut dp = vec![vec![0; m + 1]; n + 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            dp[i + 1][j + 1] = if x == y {
+                dp[i][j] + 1
+            } else {
+                dp[i][j + 1].max(dp[i + 1][j])
+            };
+        }
+    }
+    let mut result = Vec::with_capacity(dp[n][m]);
+    let (mut i, mut j) = (n, m);
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            result.push((i - 1, j - 1));
+            i -= 1;
+            j -= 1;
+        } else if dp[i - 1][j] >= dp[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    result.reverse();
+    result
+}
+
+/// [`lcs`] と同じく最長共通部分列をひとつ求めますが、Hirschberg のアルゴリズムで O(`n + m`) 空間で計算します。
+///
+/// 時間は O(`nm`) ですが、[`lcs`] より 2 倍程度遅いです。返す部分列は [`lcs`] と同じとは限りません。
+///
+/// # Examples
+/// ```
+/// use longest_common_subsequence::lcs_hirschberg;
+///
+/// let a = b"abcbdab";
+/// let b = b"bdcaba";
+/// let pairs = lcs_hirschberg(a, b);
+/// assert_eq!(pairs.len(), 4);
+/// assert!(pairs.iter().all(|&(i, j)| a[i] == b[j]));
+/// ```
+pub fn lcs_hirschberg<T: Eq>(a: &[T], b: &[T]) -> Vec<(usize, usize)> {
+    let mut result = Vec::new();
+    hirschberg(a, b, 0, 0, &mut result);
+    result
+}
+
+fn hirschberg<T: Eq>(
+    a: &[T],
+    b: &[T],
+    a_offset: usize,
+    b_offset: usize,
+    result: &mut Vec<(usize, usize)>,
+) {
+    let (n, m) = (a.len(), b.len());
+    if n == 0 || m == 0 {
+        return;
+    }
+    if n == 1 {
+        if let Some(j) = b.iter().position(|x| x == &a[0]) {
+            result.push((a_offset, b_offset + j));
+        }
+        return;
+    }
+    let mid = n / 2;
+    // b[..k] と a[..mid]、b[k..] と a[mid..] に分ける
+    // 再帰の前に作業用の配列を解放して、使う空間を O(n + m) に抑える
+    let k = {
+        let forward = lcs_row(&a[..mid], b);
+        let a_rev = a[mid..].iter().rev().collect::<Vec<_>>();
+        let b_rev = b.iter().rev().collect::<Vec<_>>();
+        let backward = lcs_row(&a_rev, &b_rev);
+        (0..=m)
+            .max_by_key(|&k| forward[k] + backward[m - k])
+            .unwrap()
+    };
+    hirschberg(&a[..mid], &b[..k], a_offset, b_offset, result);
+    hirschberg(&a[mid..], &b[k..], a_offset + mid, b_offset + k, result);
+}
+
+// row[j]: a と b[..j] の最長共通部分列の長さ
+fn lcs_row<T: Eq>(a: &[T], b: &[T]) -> Vec<usize> {
+    let m = b.len();
+    let mut row = vec![0; m + 1];
+    for x in a {
+        // prev: 更新前の row[j]
+        let mut prev = 0;
+        for (j, y) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if x == y {
+                prev + 1
+            } else {
+                row[j + 1].max(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    fn check(a: &[u8], b: &[u8], pairs: &[(usize, usize)], expected_len: usize) {
+        assert_eq!(pairs.len(), expected_len);
+        for &(i, j) in pairs {
+            assert_eq!(a[i], b[j]);
+        }
+        for w in pairs.windows(2) {
+            assert!(w[0].0 < w[1].0 && w[0].1 < w[1].1);
+        }
+    }
+
+    // a の部分列を全て試す
+    fn brute_force(a: &[u8], b: &[u8]) -> usize {
+        let n = a.len();
+        (0..(1 << n))
+            .filter(|&bits: &usize| {
+                let sub = (0..n).filter(|&i| bits >> i & 1 == 1).map(|i| a[i]);
+                let mut it = b.iter();
+                sub.into_iter().all(|x| it.any(|&y| y == x))
+            })
+            .map(|bits| bits.count_ones() as usize)
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let n = rng.gen_range(0, 10);
+            let m = rng.gen_range(0, 10);
+            let k = rng.gen_range(1, 4);
+            let a = (0..n).map(|_| rng.gen_range(0, k)).collect::<Vec<u8>>();
+            let b = (0..m).map(|_| rng.gen_range(0, k)).collect::<Vec<u8>>();
+            let expected = brute_force(&a, &b);
+            assert_eq!(lcs_length(&a, &b), expected);
+            check(&a, &b, &lcs(&a, &b), expected);
+            check(&a, &b, &lcs_hirschberg(&a, &b), expected);
+        }
+    }
+
+    #[test]
+    fn test_long() {
+        let mut rng = thread_rng();
+        let a = (0..500).map(|_| rng.gen_range(0, 4)).collect::<Vec<u8>>();
+        let b = (0..700).map(|_| rng.gen_range(0, 4)).collect::<Vec<u8>>();
+        let expected = lcs_length(&a, &b);
+        check(&a, &b, &lcs(&a, &b), expected);
+        check(&a, &b, &lcs_hirschberg(&a, &b), expected);
+    }
+}
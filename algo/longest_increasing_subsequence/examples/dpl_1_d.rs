@@ -0,0 +1,13 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DPL_1_D
+
+use proconio::input;
+
+use longest_increasing_subsequence::lis_length;
+
+fn main() {
+    input! {
+        n: usize,
+        a: [u32; n],
+    }
+    println!("{}", lis_length(&a, true));
+}
@@ -0,0 +1,126 @@
+/// 列 `a` の最長増加部分列の長さを O(`n` log `n`) 時間で返します。
+///
+/// `strict` が `true` なら狭義単調増加 (`a[i] < a[j]`)、`false` なら広義単調増加 (`a[i] <= a[j]`) な部分列を考えます。
+///
+/// # Examples
+/// ```
+/// use longest_increasing_subsequence::lis_length;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(lis_length(&a, true), 4); // 3 4 5 9
+///
+/// let a = vec![2, 2, 1, 2, 3, 3];
+/// assert_eq!(lis_length(&a, true), 3); // 1 2 3
+/// assert_eq!(lis_length(&a, false), 5); // 2 2 2 3 3
+/// ```
+pub fn lis_length<T: Ord>(a: &[T], strict: bool) -> usize {
+    // tails[k]: 長さ k + 1 の増加部分列の末尾としてありうる最小値
+    let mut tails: Vec<&T> = Vec::new();
+    for x in a {
+        let k = position(&tails, x, strict);
+        if k == tails.len() {
+            tails.push(x);
+        } else {
+            tails[k] = x;
+        }
+    }
+    tails.len()
+}
+
+/// 列 `a` の最長増加部分列をひとつ求めて、その添字の列を返します。添字は昇順です。
+///
+/// `strict` の意味は [`lis_length`] と同じです。O(`n` log `n`) 時間です。
+///
+/// # Examples
+/// ```
+/// use longest_increasing_subsequence::lis_indices;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// let indices = lis_indices(&a, true);
+/// assert_eq!(indices.len(), 4);
+/// assert!(indices.windows(2).all(|w| w[0] < w[1] && a[w[0]] < a[w[1]]));
+///
+/// let a = vec![2, 2, 1, 2, 3, 3];
+/// assert_eq!(lis_indices(&a, false), vec![0, 1, 3, 4, 5]);
+/// ```
+pub fn lis_indices<T: Ord>(a: &[T], strict: bool) -> Vec<usize> {
+    let mut tails: Vec<&T> = Vec::new();
+    // tail_indices[k]: tails[k] の添字
+    let mut tail_indices: Vec<usize> = Vec::new();
+    // prev[i]: a[i] で終わる最長増加部分列のひとつ前の添字
+    let mut prev = vec![usize::MAX; a.len()];
+    for (i, x) in a.iter().enumerate() {
+        let k = position(&tails, x, strict);
+        if k > 0 {
+            prev[i] = tail_indices[k - 1];
+        }
+        if k == tails.len() {
+            tails.push(x);
+            tail_indices.push(i);
+        } else {
+            tails[k] = x;
+            tail_indices[k] = i;
+        }
+    }
+    let mut result = Vec::with_capacity(tails.len());
+    let mut i = tail_indices.last().copied().unwrap_or(usize::MAX);
+    while i != usize::MAX {
+        result.push(i);
+        i = prev[i];
+    }
+    result.reverse();
+    result
+}
+
+// x を末尾に付け加えられる増加部分列の長さの最大値
+fn position<T: Ord>(tails: &[&T], x: &T, strict: bool) -> usize {
+    if strict {
+        tails.partition_point(|&t| t < x)
+    } else {
+        tails.partition_point(|&t| t <= x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    fn brute_force(a: &[u32], strict: bool) -> usize {
+        let n = a.len();
+        // dp[i]: a[i] で終わる増加部分列の長さの最大値
+        let mut dp = vec![1; n];
+        for i in 0..n {
+            for j in 0..i {
+                if a[j] < a[i] || (!strict && a[j] == a[i]) {
+                    dp[i] = dp[i].max(dp[j] + 1);
+                }
+            }
+        }
+        dp.into_iter().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 20);
+            let m = rng.gen_range(1, 10);
+            let a = (0..n).map(|_| rng.gen_range(0, m)).collect::<Vec<u32>>();
+            for strict in [true, false] {
+                let expected = brute_force(&a, strict);
+                assert_eq!(lis_length(&a, strict), expected);
+                let indices = lis_indices(&a, strict);
+                assert_eq!(indices.len(), expected);
+                for w in indices.windows(2) {
+                    assert!(w[0] < w[1]);
+                    if strict {
+                        assert!(a[w[0]] < a[w[1]]);
+                    } else {
+                        assert!(a[w[0]] <= a[w[1]]);
+                    }
+                }
+            }
+        }
+    }
+}
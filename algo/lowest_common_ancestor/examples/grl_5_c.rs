@@ -1,4 +1,5 @@
 // problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=GRL_5_C
+// tags: graph
 use lowest_common_ancestor::LowestCommonAncestor;
 use proconio::{fastout, input};
 
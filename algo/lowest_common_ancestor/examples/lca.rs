@@ -1,4 +1,5 @@
 // problem: https://judge.yosupo.jp/problem/lca
+// tags: graph
 use lowest_common_ancestor::LowestCommonAncestor;
 use proconio::{fastout, input};
 
@@ -0,0 +1,201 @@
+use std::ops::{AddAssign, SubAssign};
+
+use least_prime_factors::least_prime_factors;
+
+/// `0` 以上 `n` 未満の全ての `k` についてメビウス関数 `μ(k)` を計算します。`μ(0)` は `0` とします。
+///
+/// - `μ(1) = 1`
+/// - `k` が平方因子をもつとき `μ(k) = 0`
+/// - `k` が相異なる `r` 個の素数の積のとき `μ(k) = (-1)^r`
+///
+/// # Examples
+/// ```
+/// use mobius::mobius_table;
+///
+/// let mu = mobius_table(11);
+/// assert_eq!(mu, vec![0, 1, -1, -1, 0, -1, 1, -1, 0, 0, 1]);
+/// ```
+pub fn mobius_table(n: usize) -> Vec<i8> {
+    let lpf = least_prime_factors(n);
+    let mut mu = vec![0; n];
+    if n >= 2 {
+        mu[1] = 1;
+    }
+    for k in 2..n {
+        let p = lpf[k];
+        let m = k / p;
+        mu[k] = if m % p == 0 { 0 } else { -mu[m] };
+    }
+    mu
+}
+
+/// 約数についてのゼータ変換です。`1 <= i < a.len()` の全ての `i` について `a[i]` を `Σ_{d | i} a[d]` で置き換えます。`a[0]` は変更しません。
+///
+/// O(`n` log log `n`) 時間です。
+///
+/// # Examples
+/// ```
+/// use mobius::divisor_zeta;
+///
+/// let mut a = vec![0, 1, 1, 1, 1, 1, 1, 1];
+/// divisor_zeta(&mut a);
+/// // 約数の個数
+/// assert_eq!(a, vec![0, 1, 2, 2, 3, 2, 4, 2]);
+/// ```
+pub fn divisor_zeta<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    let n = a.len();
+    for p in primes(n) {
+        for i in 1..=((n - 1) / p) {
+            let x = a[i];
+            a[i * p] += x;
+        }
+    }
+}
+
+/// [`divisor_zeta`] の逆変換です。`a[i]` を `Σ_{d | i} μ(i / d) a[d]` で置き換えます。
+///
+/// # Examples
+/// ```
+/// use mobius::divisor_mobius;
+///
+/// let mut a = vec![0, 1, 2, 2, 3, 2, 4, 2];
+/// divisor_mobius(&mut a);
+/// assert_eq!(a, vec![0, 1, 1, 1, 1, 1, 1, 1]);
+/// ```
+///
+/// [`divisor_zeta`]: fn.divisor_zeta.html
+pub fn divisor_mobius<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    let n = a.len();
+    for p in primes(n) {
+        for i in (1..=((n - 1) / p)).rev() {
+            let x = a[i];
+            a[i * p] -= x;
+        }
+    }
+}
+
+/// 倍数についてのゼータ変換です。`1 <= i < a.len()` の全ての `i` について `a[i]` を `Σ_{i | j, j < a.len()} a[j]` で置き換えます。`a[0]` は変更しません。
+///
+/// O(`n` log log `n`) 時間です。
+///
+/// # Examples
+///
+/// gcd が `g` となる組の個数を数える例です。
+///
+/// ```
+/// use mobius::{multiple_mobius, multiple_zeta};
+///
+/// let values = vec![2, 4, 6, 3, 9];
+/// let n = 10;
+/// let mut count = vec![0_i64; n];
+/// for &v in &values {
+///     count[v] += 1;
+/// }
+/// // count[g]: g の倍数の個数
+/// multiple_zeta(&mut count);
+/// // pairs[g]: gcd が g の倍数になる組 (i < j) の個数
+/// let mut pairs = count.iter().map(|&c| c * (c - 1) / 2).collect::<Vec<_>>();
+/// // pairs[g]: gcd がちょうど g になる組の個数
+/// multiple_mobius(&mut pairs);
+/// assert_eq!(pairs[1], 4); // (2, 3), (2, 9), (4, 3), (4, 9)
+/// assert_eq!(pairs[2], 3); // (2, 4), (2, 6), (4, 6)
+/// assert_eq!(pairs[3], 3); // (6, 3), (6, 9), (3, 9)
+/// ```
+pub fn multiple_zeta<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    let n = a.len();
+    for p in primes(n) {
+        for i in (1..=((n - 1) / p)).rev() {
+            let x = a[i * p];
+            a[i] += x;
+        }
+    }
+}
+
+/// [`multiple_zeta`] の逆変換です。`a[i]` を `Σ_{i | j, j < a.len()} μ(j / i) a[j]` で置き換えます。
+///
+/// [`multiple_zeta`]: fn.multiple_zeta.html
+pub fn multiple_mobius<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    let n = a.len();
+    for p in primes(n) {
+        for i in 1..=((n - 1) / p) {
+            let x = a[i * p];
+            a[i] -= x;
+        }
+    }
+}
+
+fn primes(n: usize) -> impl Iterator<Item = usize> {
+    let lpf = least_prime_factors(n);
+    (2..n).filter(move |&i| lpf[i] == i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mobius_table() {
+        let n = 1000;
+        let mu = mobius_table(n);
+        assert_eq!(mu[0], 0);
+        for k in 1..n {
+            // Σ_{d | k} μ(d) = [k = 1]
+            let sum = (1..=k)
+                .filter(|d| k % d == 0)
+                .map(|d| mu[d] as i32)
+                .sum::<i32>();
+            assert_eq!(sum, if k == 1 { 1 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn test_divisor() {
+        let n = 200;
+        let a = (0..n as i64).map(|i| i * i % 17 - 5).collect::<Vec<_>>();
+        let mut b = a.clone();
+        divisor_zeta(&mut b);
+        for (i, &x) in b.iter().enumerate().skip(1) {
+            let expected = (1..=i).filter(|d| i % d == 0).map(|d| a[d]).sum::<i64>();
+            assert_eq!(x, expected);
+        }
+        divisor_mobius(&mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_multiple() {
+        let n = 200;
+        let a = (0..n as i64).map(|i| i * i % 17 - 5).collect::<Vec<_>>();
+        let mut b = a.clone();
+        multiple_zeta(&mut b);
+        for (i, &x) in b.iter().enumerate().skip(1) {
+            let expected = (i..n).step_by(i).map(|j| a[j]).sum::<i64>();
+            assert_eq!(x, expected);
+        }
+        multiple_mobius(&mut b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_small() {
+        for n in 0..3 {
+            assert_eq!(mobius_table(n).len(), n);
+            let mut a = vec![1; n];
+            divisor_zeta(&mut a);
+            multiple_zeta(&mut a);
+            assert_eq!(a, vec![1; n]);
+        }
+    }
+}
@@ -9,8 +9,10 @@
 //! assert_eq!(x, y.val());
 //! ```
 
-use std::fmt::Debug;
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::fmt::{self, Debug, Display};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
 use ext_gcd::ext_gcd;
 
@@ -107,6 +109,60 @@ impl<const M: i64> ModInt<M> {
         assert_eq!(g, 1, "{} is not prime!", M);
         Self::new(x)
     }
+
+    /// `y * y % p = x` となる `y` を Tonelli-Shanks のアルゴリズムで求めます。そのような `y` が存在しない場合は `None` を返します。
+    ///
+    /// 解が 2 つある場合は小さいほうを返します。`p` は素数である必要があります。
+    ///
+    /// [実装の参考資料](https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm)
+    ///
+    /// # Examples
+    /// ```
+    /// use mod_int::ModInt998244353;
+    /// let y = ModInt998244353::new(2).sqrt().unwrap();
+    /// assert_eq!((y * y).val(), 2);
+    /// assert_eq!(ModInt998244353::new(4).sqrt().unwrap().val(), 2);
+    /// assert_eq!(ModInt998244353::new(0).sqrt().unwrap().val(), 0);
+    /// // 3 は平方剰余でない
+    /// assert!(ModInt998244353::new(3).sqrt().is_none());
+    /// ```
+    pub fn sqrt(self) -> Option<Self> {
+        if self.0 == 0 || M == 2 {
+            return Some(self);
+        }
+        // オイラーの規準
+        if self.pow(((M - 1) / 2) as u32).0 != 1 {
+            return None;
+        }
+        // M - 1 = q * 2^s
+        let s = (M - 1).trailing_zeros();
+        let q = (M - 1) >> s;
+        let z = (2..M)
+            .map(Self::new_raw)
+            .find(|z| z.pow(((M - 1) / 2) as u32).0 == M - 1)
+            .unwrap();
+        let mut m = s;
+        let mut c = z.pow(q as u32);
+        let mut t = self.pow(q as u32);
+        let mut r = self.pow(((q + 1) / 2) as u32);
+        while t.0 != 1 {
+            // t^(2^i) = 1 となる最小の i
+            let mut i = 0;
+            let mut t2 = t;
+            while t2.0 != 1 {
+                t2 *= t2;
+                i += 1;
+            }
+            debug_assert!(i < m);
+            let b = c.pow(1 << (m - i - 1));
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+        debug_assert_eq!((r * r).0, self.0);
+        Some(if r.0 <= M - r.0 { r } else { -r })
+    }
 }
 
 impl<const M: i64, T: Into<ModInt<M>>> AddAssign<T> for ModInt<M> {
@@ -181,6 +237,79 @@ impl<const M: i64, T: Into<ModInt<M>>> Div<T> for ModInt<M> {
     }
 }
 
+impl<const M: i64> Neg for ModInt<M> {
+    type Output = ModInt<M>;
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self::new_raw(M - self.0)
+        }
+    }
+}
+
+impl<const M: i64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new_raw(0), |acc, x| acc + x)
+    }
+}
+
+impl<'a, const M: i64> Sum<&'a ModInt<M>> for ModInt<M> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<const M: i64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+impl<'a, const M: i64> Product<&'a ModInt<M>> for ModInt<M> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<const M: i64> Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// 10 進表記の整数を読みます。`i64` に収まらない桁数の整数も読めます。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt1000000007;
+/// let x: ModInt1000000007 = "123".parse().unwrap();
+/// assert_eq!(x.val(), 123);
+/// let y: ModInt1000000007 = "-1".parse().unwrap();
+/// assert_eq!(y.val(), 1000000006);
+/// // 10^30
+/// let z: ModInt1000000007 = "1000000000000000000000000000000".parse().unwrap();
+/// assert_eq!(z.val(), 999657007);
+/// assert!("12a".parse::<ModInt1000000007>().is_err());
+/// ```
+impl<const M: i64> FromStr for ModInt<M> {
+    type Err = std::num::ParseIntError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            // 不正な入力に対するエラーを i64 のパースに任せる
+            return s.parse::<i64>().map(Self::new);
+        }
+        let x = digits
+            .bytes()
+            .fold(Self::new_raw(0), |acc, b| acc * 10 + i64::from(b - b'0'));
+        Ok(if negative { -x } else { x })
+    }
+}
+
 macro_rules! impl_from_int {
     ($($t:ty),+) => {
         $(
@@ -212,6 +341,304 @@ impl_from_large_int!(u64, usize, isize);
 pub type ModInt1000000007 = ModInt<1_000_000_007>;
 pub type ModInt998244353 = ModInt<998_244_353>;
 
+/// `ModInt64` は 64 bit 整数の四則演算を mod `M` で行う構造体です。
+///
+/// `ModInt` では `M` が大きいと掛け算の途中でオーバーフローしますが、`ModInt64` はモンゴメリ乗算を使うので `2^62` 未満の奇数 `M` を法にできます。[実装の参考資料](https://en.wikipedia.org/wiki/Montgomery_modular_multiplication)
+///
+/// `M` が偶数または `2^62` 以上の場合コンパイルエラーです。
+///
+/// ```compile_fail
+/// use mod_int::ModInt64;
+/// ModInt64::<1000000000>::new(1);
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use mod_int::ModInt64;
+/// type Mint = ModInt64<{ (1 << 61) - 1 }>;
+/// let p = (1_u128 << 61) - 1;
+/// let (a, b) = (1_u64 << 60, (1_u64 << 60) + 12345);
+/// let x = u128::from(a) * u128::from(b) % p;
+/// let y = Mint::new(a) * b;
+/// assert_eq!(x as u64, y.val());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ModInt64<const M: u64>(u64);
+
+impl<const M: u64> ModInt64<M> {
+    const VALID: () = assert!(M % 2 == 1 && M < (1 << 62));
+    // M * M_INV = 1 (mod 2^64)
+    const M_INV: u64 = {
+        let mut inv = M;
+        let mut i = 0;
+        while i < 5 {
+            inv = inv.wrapping_mul(2_u64.wrapping_sub(M.wrapping_mul(inv)));
+            i += 1;
+        }
+        inv
+    };
+    // 2^128 mod M
+    const R2: u64 = {
+        let r = ((1_u128 << 64) % M as u128) as u64;
+        ((r as u128) * (r as u128) % M as u128) as u64
+    };
+
+    /// 整数を `0 <= x < modulo` に正規化してインスタンスを作ります。
+    pub fn new(x: u64) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::VALID;
+        Self(Self::reduce(u128::from(x % M) * u128::from(Self::R2)))
+    }
+
+    // t * 2^(-64) mod M
+    fn reduce(t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(Self::M_INV.wrapping_neg());
+        let t = ((t + u128::from(m) * u128::from(M)) >> 64) as u64;
+        if t >= M {
+            t - M
+        } else {
+            t
+        }
+    }
+
+    /// `ModInt64` に格納されている値を返します。
+    ///
+    /// # Examples
+    /// ```
+    /// use mod_int::ModInt64;
+    /// assert_eq!(ModInt64::<1000000007>::new(123).val(), 123);
+    /// ```
+    pub fn val(self) -> u64 {
+        Self::reduce(u128::from(self.0))
+    }
+
+    /// 法を返します。
+    pub fn modulo() -> u64 {
+        M
+    }
+
+    /// 二分累乗法で `x^exp % M` を計算します。
+    ///
+    /// # Examples
+    /// ```
+    /// use mod_int::ModInt64;
+    /// type Mint = ModInt64<{ (1 << 61) - 1 }>;
+    /// // フェルマーの小定理
+    /// assert_eq!(Mint::new(3).pow((1 << 61) - 2).val(), 1);
+    /// ```
+    pub fn pow(self, exp: u64) -> Self {
+        let mut res = Self::new(1);
+        let mut base = self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    /// `x * y % M = 1` となる `y` を返します。
+    ///
+    /// # Panics
+    ///
+    /// `x` と `M` が互いに素でない場合パニックです。
+    pub fn inv(self) -> Self {
+        let x = self.val();
+        assert_ne!(x, 0, "Don't divide by zero!");
+        let (y, _, g) = ext_gcd(x as i64, M as i64);
+        assert_eq!(g, 1, "{} and {} are not coprime!", x, M);
+        Self::new(y.rem_euclid(M as i64) as u64)
+    }
+}
+
+impl<const M: u64> Debug for ModInt64<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ModInt64({})", self.val())
+    }
+}
+
+impl<const M: u64> Display for ModInt64<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.val())
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> AddAssign<T> for ModInt64<M> {
+    fn add_assign(&mut self, rhs: T) {
+        self.0 += rhs.into().0;
+        if self.0 >= M {
+            self.0 -= M;
+        }
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> Add<T> for ModInt64<M> {
+    type Output = ModInt64<M>;
+    fn add(self, rhs: T) -> Self::Output {
+        let mut result = self;
+        result += rhs.into();
+        result
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> SubAssign<T> for ModInt64<M> {
+    fn sub_assign(&mut self, rhs: T) {
+        let rhs = rhs.into().0;
+        self.0 = if self.0 >= rhs {
+            self.0 - rhs
+        } else {
+            self.0 + M - rhs
+        };
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> Sub<T> for ModInt64<M> {
+    type Output = ModInt64<M>;
+    fn sub(self, rhs: T) -> Self::Output {
+        let mut result = self;
+        result -= rhs.into();
+        result
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> MulAssign<T> for ModInt64<M> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.0 = Self::reduce(u128::from(self.0) * u128::from(rhs.into().0));
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> Mul<T> for ModInt64<M> {
+    type Output = ModInt64<M>;
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut result = self;
+        result *= rhs.into();
+        result
+    }
+}
+
+#[allow(clippy::suspicious_op_assign_impl)]
+impl<const M: u64, T: Into<ModInt64<M>>> DivAssign<T> for ModInt64<M> {
+    fn div_assign(&mut self, rhs: T) {
+        *self *= rhs.into().inv();
+    }
+}
+
+impl<const M: u64, T: Into<ModInt64<M>>> Div<T> for ModInt64<M> {
+    type Output = ModInt64<M>;
+    fn div(self, rhs: T) -> Self::Output {
+        let mut result = self;
+        result /= rhs.into();
+        result
+    }
+}
+
+impl<const M: u64> Neg for ModInt64<M> {
+    type Output = ModInt64<M>;
+    fn neg(self) -> Self::Output {
+        Self::new(0) - self
+    }
+}
+
+impl<const M: u64> Sum for ModInt64<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), |acc, x| acc + x)
+    }
+}
+
+impl<const M: u64> Product for ModInt64<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+macro_rules! impl_from_unsigned_int_64 {
+    ($($t:ty),+) => {
+        $(
+            impl<const M: u64> From<$t> for ModInt64<M> {
+                fn from(x: $t) -> Self {
+                    Self::new(u64::from(x) % M)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_unsigned_int_64!(u8, u16, u32, u64);
+
+impl<const M: u64> From<usize> for ModInt64<M> {
+    fn from(x: usize) -> Self {
+        Self::new(x as u64 % M)
+    }
+}
+
+macro_rules! impl_from_signed_int_64 {
+    ($($t:ty),+) => {
+        $(
+            impl<const M: u64> From<$t> for ModInt64<M> {
+                fn from(x: $t) -> Self {
+                    Self::new(i128::from(x).rem_euclid(M as i128) as u64)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_signed_int_64!(i8, i16, i32, i64);
+
+/// `a * b mod m` を返します。途中の計算は `u128` で行うのでオーバーフローしません。
+///
+/// 法がコンパイル時に決まらない場合など、[`ModInt`] や [`ModInt64`] を使うまでもない場面で使います。
+///
+/// # Panics
+///
+/// `m` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use mod_int::mul_mod_u64;
+/// let m = (1 << 61) - 1;
+/// assert_eq!(mul_mod_u64(1 << 60, 4, m), 2);
+/// assert_eq!(mul_mod_u64(u64::MAX, u64::MAX, u64::MAX), 0);
+/// ```
+pub fn mul_mod_u64(a: u64, b: u64, m: u64) -> u64 {
+    assert!(m >= 1);
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+/// `a^exp mod m` を O(log `exp`) 時間で返します。途中の計算は `u128` で行うのでオーバーフローしません。
+///
+/// # Panics
+///
+/// `m` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use mod_int::pow_mod_u64;
+/// assert_eq!(pow_mod_u64(3, 4, 100), 81);
+/// assert_eq!(pow_mod_u64(5, 0, 1), 0);
+/// // フェルマーの小定理
+/// let p = 1_000_000_000_000_000_003;
+/// assert_eq!(pow_mod_u64(123456789, p - 1, p), 1);
+/// ```
+pub fn pow_mod_u64(a: u64, exp: u64, m: u64) -> u64 {
+    assert!(m >= 1);
+    let mut result = 1 % m;
+    let mut base = a % m;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u64(result, base, m);
+        }
+        base = mul_mod_u64(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -254,4 +681,160 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn sum_product_test() {
+        type Mint = ModInt<19>;
+        let a = (1..=10).map(Mint::new).collect::<Vec<_>>();
+        assert_eq!(a.iter().sum::<Mint>().val(), 55 % 19);
+        assert_eq!(a.iter().copied().sum::<Mint>().val(), 55 % 19);
+        assert_eq!(a.iter().product::<Mint>().val(), 3628800 % 19);
+        assert_eq!(a.iter().copied().product::<Mint>().val(), 3628800 % 19);
+        assert_eq!(Vec::<Mint>::new().iter().sum::<Mint>().val(), 0);
+        assert_eq!(Vec::<Mint>::new().iter().product::<Mint>().val(), 1);
+    }
+
+    #[test]
+    fn neg_test() {
+        type Mint = ModInt<19>;
+        for a in 0..50 {
+            assert_eq!((-Mint::new(a)).val(), (-a).rem_euclid(19));
+        }
+    }
+
+    #[test]
+    fn display_test() {
+        type Mint = ModInt<19>;
+        assert_eq!(Mint::new(-1).to_string(), "18");
+        assert_eq!(format!("{} {}", Mint::new(3), Mint::new(20)), "3 1");
+    }
+
+    #[test]
+    fn from_str_test() {
+        type Mint = ModInt<19>;
+        for a in -50_i64..50 {
+            assert_eq!(
+                a.to_string().parse::<Mint>().unwrap().val(),
+                a.rem_euclid(19)
+            );
+        }
+        assert_eq!("+5".parse::<Mint>().unwrap().val(), 5);
+        assert!("".parse::<Mint>().is_err());
+        assert!("-".parse::<Mint>().is_err());
+        assert!("1 2".parse::<Mint>().is_err());
+        assert!("0x12".parse::<Mint>().is_err());
+    }
+
+    #[test]
+    fn mod_int_64_ops_test() {
+        const P: u64 = (1 << 61) - 1;
+        type Mint = ModInt64<P>;
+        let values = [
+            0,
+            1,
+            2,
+            3,
+            P - 2,
+            P - 1,
+            P,
+            P + 1,
+            1 << 60,
+            123456789123456789,
+        ];
+        for &a in &values {
+            for &b in &values {
+                let (a128, b128, p128) = (u128::from(a), u128::from(b), u128::from(P));
+                assert_eq!((Mint::new(a) + b).val() as u128, (a128 + b128) % p128);
+                assert_eq!(
+                    (Mint::new(a) - b).val() as u128,
+                    (a128 % p128 + p128 - b128 % p128) % p128
+                );
+                assert_eq!((Mint::new(a) * b).val() as u128, a128 * b128 % p128);
+                if b % P != 0 {
+                    assert_eq!((Mint::new(a) / b * b).val(), a % P);
+                }
+            }
+        }
+        assert_eq!((-Mint::new(1)).val(), P - 1);
+        assert_eq!(Mint::from(-1_i64).val(), P - 1);
+    }
+
+    #[test]
+    fn mod_int_64_from_unsigned_test() {
+        type Mint = ModInt64<1_000_000_007>;
+        assert_eq!(Mint::from(10_u8).val(), 10);
+        assert_eq!(Mint::from(u8::MAX).val(), 255);
+        assert_eq!(Mint::from(10_u16).val(), 10);
+        assert_eq!(Mint::from(u16::MAX).val(), 65535);
+        assert_eq!(Mint::from(10_u32).val(), 10);
+        assert_eq!(
+            Mint::from(u32::MAX).val(),
+            u64::from(u32::MAX) % 1_000_000_007
+        );
+        assert_eq!(Mint::from(u64::MAX).val(), u64::MAX % 1_000_000_007);
+        assert_eq!(Mint::from(10_usize).val(), 10);
+        assert_eq!(ModInt64::<7>::from(10_u8).val(), 3);
+    }
+
+    #[test]
+    fn mod_int_64_small_test() {
+        type Mint = ModInt64<19>;
+        for a in 0..50 {
+            for b in 0..50 {
+                assert_eq!((Mint::new(a) * Mint::new(b)).val(), a * b % 19);
+                assert_eq!((Mint::new(a) - Mint::new(b)).val(), (a + 19 * 3 - b) % 19);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_test() {
+        for p in [2, 3, 5, 7, 13, 17, 97] {
+            for x in 0..p {
+                let expect = (0..p).find(|&y| y * y % p == x);
+                let actual = match p {
+                    2 => ModInt::<2>::new(x).sqrt().map(|y| y.val()),
+                    3 => ModInt::<3>::new(x).sqrt().map(|y| y.val()),
+                    5 => ModInt::<5>::new(x).sqrt().map(|y| y.val()),
+                    7 => ModInt::<7>::new(x).sqrt().map(|y| y.val()),
+                    13 => ModInt::<13>::new(x).sqrt().map(|y| y.val()),
+                    17 => ModInt::<17>::new(x).sqrt().map(|y| y.val()),
+                    97 => ModInt::<97>::new(x).sqrt().map(|y| y.val()),
+                    _ => unreachable!(),
+                };
+                assert_eq!(actual, expect, "p = {}, x = {}", p, x);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_large_test() {
+        type Mint = ModInt998244353;
+        for x in 0..1000 {
+            let y = Mint::new(x * x);
+            let root = y.sqrt().unwrap();
+            assert_eq!((root * root).val(), y.val());
+            assert!(root.val() * 2 <= Mint::modulo());
+        }
+    }
+
+    #[test]
+    fn pow_mod_u64_test() {
+        for m in 1..50_u64 {
+            for a in 0..50 {
+                let mut expect = 1 % m;
+                for exp in 0..50 {
+                    assert_eq!(
+                        pow_mod_u64(a, exp, m),
+                        expect,
+                        "a = {}, exp = {}, m = {}",
+                        a,
+                        exp,
+                        m
+                    );
+                    expect = expect * a % m;
+                }
+            }
+        }
+    }
 }
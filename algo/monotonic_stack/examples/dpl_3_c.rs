@@ -0,0 +1,24 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DPL_3_C
+
+use monotonic_stack::{next_smaller_indices, previous_smaller_indices};
+use proconio::input;
+
+fn main() {
+    input! {
+        n: usize,
+        h: [u64; n],
+    };
+
+    let prev = previous_smaller_indices(&h);
+    let next = next_smaller_indices(&h);
+    // h[i] を高さとする最大の長方形
+    let ans = (0..n)
+        .map(|i| {
+            let l = prev[i].map_or(0, |j| j + 1);
+            let r = next[i].unwrap_or(n);
+            h[i] * (r - l) as u64
+        })
+        .max()
+        .unwrap();
+    println!("{}", ans);
+}
@@ -0,0 +1,134 @@
+/// 各 `i` について、`j < i` かつ `a[j] < a[i]` を満たす最大の `j` を返します。存在しなければ `None` です。
+///
+/// スタックを使って O(`n`) 時間です。
+///
+/// # Examples
+/// ```
+/// use monotonic_stack::previous_smaller_indices;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(
+///     previous_smaller_indices(&a),
+///     vec![None, None, Some(1), None, Some(3), Some(4), Some(3), Some(6)]
+/// );
+/// ```
+pub fn previous_smaller_indices<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    previous_indices(a.len(), |j, i| a[j] < a[i])
+}
+
+/// 各 `i` について、`j > i` かつ `a[j] < a[i]` を満たす最小の `j` を返します。存在しなければ `None` です。
+///
+/// # Examples
+/// ```
+/// use monotonic_stack::next_smaller_indices;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(
+///     next_smaller_indices(&a),
+///     vec![Some(1), None, Some(3), None, Some(6), Some(6), None, None]
+/// );
+/// ```
+pub fn next_smaller_indices<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    next_indices(a.len(), |j, i| a[j] < a[i])
+}
+
+/// 各 `i` について、`j < i` かつ `a[j] > a[i]` を満たす最大の `j` を返します。存在しなければ `None` です。
+///
+/// # Examples
+/// ```
+/// use monotonic_stack::previous_greater_indices;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(
+///     previous_greater_indices(&a),
+///     vec![None, Some(0), None, Some(2), None, None, Some(5), Some(5)]
+/// );
+/// ```
+pub fn previous_greater_indices<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    previous_indices(a.len(), |j, i| a[j] > a[i])
+}
+
+/// 各 `i` について、`j > i` かつ `a[j] > a[i]` を満たす最小の `j` を返します。存在しなければ `None` です。
+///
+/// # Examples
+/// ```
+/// use monotonic_stack::next_greater_indices;
+///
+/// let a = vec![3, 1, 4, 1, 5, 9, 2, 6];
+/// assert_eq!(
+///     next_greater_indices(&a),
+///     vec![Some(2), Some(2), Some(4), Some(4), Some(5), None, Some(7), None]
+/// );
+/// ```
+pub fn next_greater_indices<T: Ord>(a: &[T]) -> Vec<Option<usize>> {
+    next_indices(a.len(), |j, i| a[j] > a[i])
+}
+
+// 各 i について j < i かつ ok(j, i) を満たす最大の j
+fn previous_indices<F>(n: usize, ok: F) -> Vec<Option<usize>>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut result = vec![None; n];
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, r) in result.iter_mut().enumerate() {
+        while let Some(&j) = stack.last() {
+            if ok(j, i) {
+                break;
+            }
+            stack.pop();
+        }
+        *r = stack.last().copied();
+        stack.push(i);
+    }
+    result
+}
+
+// 各 i について j > i かつ ok(j, i) を満たす最小の j
+fn next_indices<F>(n: usize, ok: F) -> Vec<Option<usize>>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let mut result = vec![None; n];
+    let mut stack: Vec<usize> = Vec::new();
+    for i in (0..n).rev() {
+        while let Some(&j) = stack.last() {
+            if ok(j, i) {
+                break;
+            }
+            stack.pop();
+        }
+        result[i] = stack.last().copied();
+        stack.push(i);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_random() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 20);
+            let a = (0..n).map(|_| rng.gen_range(0, 5)).collect::<Vec<u32>>();
+            let prev = |f: fn(u32, u32) -> bool| {
+                (0..n)
+                    .map(|i| (0..i).rev().find(|&j| f(a[j], a[i])))
+                    .collect::<Vec<_>>()
+            };
+            let next = |f: fn(u32, u32) -> bool| {
+                (0..n)
+                    .map(|i| ((i + 1)..n).find(|&j| f(a[j], a[i])))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(previous_smaller_indices(&a), prev(|x, y| x < y));
+            assert_eq!(next_smaller_indices(&a), next(|x, y| x < y));
+            assert_eq!(previous_greater_indices(&a), prev(|x, y| x > y));
+            assert_eq!(next_greater_indices(&a), next(|x, y| x > y));
+        }
+    }
+}
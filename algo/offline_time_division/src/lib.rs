@@ -0,0 +1,265 @@
+use std::ops::Range;
+
+/// 時刻に関する分割統治で、更新の追加と削除を含むクエリをオフラインで処理します。
+///
+/// 時刻は `0` 以上 `t` 未満で、`i` 番目の更新は時刻の区間 `intervals[i]` の間だけ有効です。各時刻 `s` について、その時刻に有効な更新だけを `state` に適用した状態で `answer(state, s)` を呼びます。
+///
+/// - `apply(state, i)`: `i` 番目の更新を `state` に適用する
+/// - `rollback(state, i)`: 最後に適用した `i` 番目の更新を取り消す
+/// - `answer(state, s)`: 時刻 `s` のクエリに答える
+///
+/// 時刻の区間を長さ `t` のセグメント木の O(log `t`) 個のノードに分けて深さ優先で走査するので、`apply` と `rollback` はそれぞれ O(`m` log `t`) 回呼ばれます (`m = intervals.len()`)。`rollback` は常に適用した順と逆順に呼ばれるので、経路圧縮をしない Union Find のように直前の操作しか取り消せないデータ構造と組み合わせられます。`answer` は時刻の昇順に呼ばれます。
+///
+/// # Panics
+///
+/// `intervals` の区間が `0..t` に含まれない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use offline_time_division::offline_time_division;
+///
+/// // 時刻 0..3 に値 1、時刻 1..4 に値 10、時刻 2..3 に値 100 を足す
+/// let intervals = vec![0..3, 1..4, 2..3];
+/// let values = vec![1, 10, 100];
+/// let mut sums = Vec::new();
+/// offline_time_division(
+///     &mut 0,
+///     4,
+///     &intervals,
+///     |sum, i| *sum += values[i],
+///     |sum, i| *sum -= values[i],
+///     |sum, _| sums.push(*sum),
+/// );
+/// assert_eq!(sums, vec![1, 11, 111, 10]);
+/// ```
+pub fn offline_time_division<S, A, R, Q>(
+    state: &mut S,
+    t: usize,
+    intervals: &[Range<usize>],
+    mut apply: A,
+    mut rollback: R,
+    mut answer: Q,
+) where
+    A: FnMut(&mut S, usize),
+    R: FnMut(&mut S, usize),
+    Q: FnMut(&mut S, usize),
+{
+    if t == 0 {
+        return;
+    }
+    let size = t.next_power_of_two();
+    let mut nodes = vec![Vec::new(); size * 2];
+    for (i, range) in intervals.iter().enumerate() {
+        assert!(range.start <= range.end && range.end <= t);
+        let (mut l, mut r) = (range.start + size, range.end + size);
+        while l < r {
+            if l % 2 == 1 {
+                nodes[l].push(i);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                nodes[r].push(i);
+            }
+            l /= 2;
+            r /= 2;
+        }
+    }
+    dfs(
+        1,
+        0..size,
+        t,
+        &nodes,
+        state,
+        &mut apply,
+        &mut rollback,
+        &mut answer,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dfs<S, A, R, Q>(
+    k: usize,
+    range: Range<usize>,
+    t: usize,
+    nodes: &[Vec<usize>],
+    state: &mut S,
+    apply: &mut A,
+    rollback: &mut R,
+    answer: &mut Q,
+) where
+    A: FnMut(&mut S, usize),
+    R: FnMut(&mut S, usize),
+    Q: FnMut(&mut S, usize),
+{
+    // ノード k は時刻の区間 range を表す
+    if range.start >= t {
+        return;
+    }
+    for &i in &nodes[k] {
+        apply(state, i);
+    }
+    if range.len() == 1 {
+        answer(state, range.start);
+    } else {
+        let mid = (range.start + range.end) / 2;
+        dfs(
+            k * 2,
+            range.start..mid,
+            t,
+            nodes,
+            state,
+            apply,
+            rollback,
+            answer,
+        );
+        dfs(
+            k * 2 + 1,
+            mid..range.end,
+            t,
+            nodes,
+            state,
+            apply,
+            rollback,
+            answer,
+        );
+    }
+    for &i in nodes[k].iter().rev() {
+        rollback(state, i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fenwick_tree::FenwickTree;
+    use rand::prelude::*;
+
+    // 経路圧縮をしない、直前の unite を取り消せる Union Find
+    struct RollbackUnionFind {
+        par: Vec<usize>,
+        size: Vec<usize>,
+        history: Vec<Option<(usize, usize)>>,
+    }
+
+    impl RollbackUnionFind {
+        fn new(n: usize) -> Self {
+            Self {
+                par: (0..n).collect(),
+                size: vec![1; n],
+                history: Vec::new(),
+            }
+        }
+
+        fn find(&self, mut i: usize) -> usize {
+            while self.par[i] != i {
+                i = self.par[i];
+            }
+            i
+        }
+
+        fn unite(&mut self, i: usize, j: usize) {
+            let (mut i, mut j) = (self.find(i), self.find(j));
+            if i == j {
+                self.history.push(None);
+                return;
+            }
+            if self.size[i] < self.size[j] {
+                std::mem::swap(&mut i, &mut j);
+            }
+            self.par[j] = i;
+            self.size[i] += self.size[j];
+            self.history.push(Some((i, j)));
+        }
+
+        fn undo(&mut self) {
+            if let Some((i, j)) = self.history.pop().unwrap() {
+                self.par[j] = j;
+                self.size[i] -= self.size[j];
+            }
+        }
+    }
+
+    fn random_intervals(rng: &mut ThreadRng, t: usize, m: usize) -> Vec<Range<usize>> {
+        (0..m)
+            .map(|_| {
+                let l = rng.gen_range(0, t + 1);
+                let r = rng.gen_range(l, t + 1);
+                l..r
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fenwick_tree() {
+        let mut rng = thread_rng();
+        for t in 0..20 {
+            let m = rng.gen_range(0, 20);
+            let n = 5;
+            let intervals = random_intervals(&mut rng, t, m);
+            let updates = (0..m)
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(-10, 10)))
+                .collect::<Vec<(usize, i64)>>();
+            let mut actual = Vec::new();
+            offline_time_division(
+                &mut FenwickTree::new(n, 0),
+                t,
+                &intervals,
+                |ft, i| ft.add(updates[i].0, updates[i].1),
+                |ft, i| ft.add(updates[i].0, -updates[i].1),
+                |ft, s| actual.push((s, ft.sum(..2))),
+            );
+            let expected = (0..t)
+                .map(|s| {
+                    let sum = (0..m)
+                        .filter(|&i| intervals[i].contains(&s) && updates[i].0 < 2)
+                        .map(|i| updates[i].1)
+                        .sum::<i64>();
+                    (s, sum)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_dynamic_connectivity() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(1, 8);
+            let t = rng.gen_range(1, 30);
+            let m = rng.gen_range(0, 15);
+            let intervals = random_intervals(&mut rng, t, m);
+            let edges = (0..m)
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(0, n)))
+                .collect::<Vec<_>>();
+            let mut applied = 0;
+            let mut actual = Vec::new();
+            offline_time_division(
+                &mut RollbackUnionFind::new(n),
+                t,
+                &intervals,
+                |uf, i| {
+                    uf.unite(edges[i].0, edges[i].1);
+                    applied += 1;
+                },
+                |uf, _| uf.undo(),
+                |uf, _| actual.push((0..n).map(|v| uf.find(v)).collect::<Vec<_>>()),
+            );
+            assert!(applied <= m * 2 * (usize::BITS - t.leading_zeros()) as usize);
+            for (s, actual) in actual.iter().enumerate() {
+                let mut uf = RollbackUnionFind::new(n);
+                for i in 0..m {
+                    if intervals[i].contains(&s) {
+                        uf.unite(edges[i].0, edges[i].1);
+                    }
+                }
+                for u in 0..n {
+                    for v in 0..n {
+                        assert_eq!(actual[u] == actual[v], uf.find(u) == uf.find(v));
+                    }
+                }
+            }
+        }
+    }
+}
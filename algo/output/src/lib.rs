@@ -0,0 +1,137 @@
+//! 出力をまとめて書き出すための関数とマクロです。
+//!
+//! `println!` は呼ぶたびに標準出力をロックしてフラッシュするので、大量に出力すると遅くなります。
+//!
+//! # Examples
+//! ```
+//! use output::{outln, stdout, write_joined, yes_no};
+//!
+//! let mut out = stdout();
+//! outln!(out, "{}", 42);
+//! write_joined(&mut out, [1, 2, 3], " ");
+//! outln!(out, "{}", yes_no(true));
+//! // out が drop されるときにまとめて書き出される
+//! ```
+
+use std::fmt::Display;
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// ロックした標準出力を `BufWriter` で包んで返します。
+///
+/// drop されるときにフラッシュされます。インタラクティブな問題では、入力を読む前に `flush` してください。
+pub fn stdout() -> BufWriter<StdoutLock<'static>> {
+    BufWriter::new(io::stdout().lock())
+}
+
+/// `iter` の各要素を `sep` 区切りで書き、最後に改行を書きます。
+///
+/// [`join`](../join/index.html) と違い、途中で `String` を作りません。
+///
+/// # Panics
+///
+/// 書き込みに失敗した場合パニックです。
+///
+/// # Examples
+/// ```
+/// use output::write_joined;
+///
+/// let mut buf = Vec::new();
+/// write_joined(&mut buf, vec![1, 23, 456], " ");
+/// write_joined(&mut buf, ["a", "b"].iter(), "\n");
+/// write_joined(&mut buf, Vec::<u32>::new(), " ");
+/// assert_eq!(String::from_utf8(buf).unwrap(), "1 23 456\na\nb\n\n");
+/// ```
+pub fn write_joined<W, I>(w: &mut W, iter: I, sep: &str)
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Display,
+{
+    let mut iter = iter.into_iter();
+    if let Some(first) = iter.next() {
+        write!(w, "{}", first).unwrap();
+        for x in iter {
+            write!(w, "{}{}", sep, x).unwrap();
+        }
+    }
+    writeln!(w).unwrap();
+}
+
+/// `b` が `true` なら `"Yes"`、`false` なら `"No"` を返します。
+///
+/// # Examples
+/// ```
+/// use output::yes_no;
+///
+/// assert_eq!(yes_no(true), "Yes");
+/// assert_eq!(yes_no(false), "No");
+/// ```
+pub fn yes_no(b: bool) -> &'static str {
+    if b {
+        "Yes"
+    } else {
+        "No"
+    }
+}
+
+/// `write!` して、失敗したらパニックします。
+#[macro_export]
+macro_rules! out {
+    ($w:expr, $($arg:tt)*) => {
+        ::std::io::Write::write_fmt(&mut $w, ::std::format_args!($($arg)*)).unwrap()
+    };
+}
+
+/// `writeln!` して、失敗したらパニックします。
+///
+/// # Examples
+/// ```
+/// use output::{out, outln};
+///
+/// let mut buf = Vec::new();
+/// out!(buf, "{} ", 1);
+/// outln!(buf, "{}", 2);
+/// outln!(buf);
+/// assert_eq!(String::from_utf8(buf).unwrap(), "1 2\n\n");
+/// ```
+#[macro_export]
+macro_rules! outln {
+    ($w:expr) => {
+        ::std::io::Write::write_all(&mut $w, b"\n").unwrap()
+    };
+    ($w:expr, $($arg:tt)*) => {{
+        // writeln! と同じく $w は 1 回だけ評価する
+        let w = &mut $w;
+        $crate::out!(*w, $($arg)*);
+        $crate::outln!(*w);
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_joined() {
+        let mut buf = Vec::new();
+        write_joined(&mut buf, (1..=5).map(|x| x * x), ", ");
+        outln!(buf, "{}", yes_no(false));
+        assert_eq!(String::from_utf8(buf).unwrap(), "1, 4, 9, 16, 25\nNo\n");
+    }
+
+    #[test]
+    fn test_outln_evaluates_writer_once() {
+        let mut bufs = vec![Vec::new(), Vec::new()];
+        let mut count = 0;
+        outln!(
+            bufs[{
+                count += 1;
+                count - 1
+            }],
+            "{}",
+            1
+        );
+        assert_eq!(count, 1);
+        assert_eq!(bufs, vec![b"1\n".to_vec(), Vec::new()]);
+    }
+}
@@ -0,0 +1,12 @@
+// problem: https://judge.yosupo.jp/problem/partition_function
+use join::Join;
+use partition_numbers::partition_numbers;
+use proconio::input;
+
+fn main() {
+    input! {
+        n: usize,
+    }
+    let p = partition_numbers::<998244353>(n + 1);
+    println!("{}", p.iter().map(|x| x.val()).join(" "));
+}
@@ -0,0 +1,71 @@
+use mod_int::ModInt;
+
+/// 分割数 `p(0), p(1), ..., p(n - 1)` を五角数定理を使って O(`n` sqrt(`n`)) 時間で求めます。
+///
+/// `p(i)` は `i` を正整数の和で表す方法の数です。和の順番は区別しません。
+///
+/// [参考](https://en.wikipedia.org/wiki/Pentagonal_number_theorem)
+///
+/// # Examples
+///
+/// ```
+/// use partition_numbers::partition_numbers;
+///
+/// let p = partition_numbers::<1_000_000_007>(8);
+/// assert_eq!(p.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![1, 1, 2, 3, 5, 7, 11, 15]);
+/// ```
+pub fn partition_numbers<const M: i64>(n: usize) -> Vec<ModInt<M>> {
+    let mut p = vec![ModInt::new(0); n];
+    if n == 0 {
+        return p;
+    }
+    p[0] = ModInt::new(1);
+    for i in 1..n {
+        // p(i) = Σ_{k >= 1} (-1)^(k + 1) * (p(i - k(3k - 1)/2) + p(i - k(3k + 1)/2))
+        for k in 1.. {
+            let g1 = k * (3 * k - 1) / 2;
+            if g1 > i {
+                break;
+            }
+            let g2 = k * (3 * k + 1) / 2;
+            let mut term = p[i - g1];
+            if g2 <= i {
+                term += p[i - g2];
+            }
+            if k % 2 == 1 {
+                p[i] += term;
+            } else {
+                p[i] -= term;
+            }
+        }
+    }
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::partition_numbers;
+
+    #[test]
+    fn test() {
+        let n = 100;
+        let m = 1_000_000_007;
+        // dp[j]: 使う整数を 1..=i に限ったときの j の分割数
+        let mut dp = vec![0; n];
+        dp[0] = 1;
+        for i in 1..n {
+            for j in i..n {
+                dp[j] = (dp[j] + dp[j - i]) % m;
+            }
+        }
+        let p = partition_numbers::<1_000_000_007>(n);
+        for i in 0..n {
+            assert_eq!(p[i].val(), dp[i]);
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(partition_numbers::<1_000_000_007>(0).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use std::ops::Index;
+
+/// `0, 1, ..., n - 1` の順列です。`i` を `p[i]` に移す写像と考えます。
+///
+/// # Examples
+/// ```
+/// use permutation::Permutation;
+///
+/// let p = Permutation::new(vec![1, 2, 0, 4, 3]);
+/// assert_eq!(p[0], 1);
+/// assert_eq!(p.cycles(), vec![vec![0, 1, 2], vec![3, 4]]);
+/// // 2 つの巡回の長さの最小公倍数 6 回で元に戻る
+/// assert_eq!(p.pow(6), Permutation::identity(5));
+/// assert_eq!(p.pow(1_000_000_000_000_000_000), p.pow(4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    /// 列 `p` から順列を作ります。
+    ///
+    /// # Panics
+    ///
+    /// `p` が `0, 1, ..., p.len() - 1` の並べ替えでない場合パニックです。
+    pub fn new(p: Vec<usize>) -> Self {
+        let n = p.len();
+        let mut seen = vec![false; n];
+        for &x in &p {
+            assert!(x < n && !seen[x], "not a permutation");
+            seen[x] = true;
+        }
+        Self(p)
+    }
+
+    /// 長さ `n` の恒等置換を返します。
+    pub fn identity(n: usize) -> Self {
+        Self((0..n).collect())
+    }
+
+    /// 長さを返します。
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// 長さが `0` かどうかを返します。
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 順列を列として返します。
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// 合成 `self ∘ other`、つまり `other` で移してから `self` で移す順列を返します。`i` は `self[other[i]]` に移ります。
+    ///
+    /// # Panics
+    ///
+    /// 長さが異なる場合パニックです。
+    ///
+    /// # Examples
+    /// ```
+    /// use permutation::Permutation;
+    ///
+    /// let p = Permutation::new(vec![1, 2, 0]);
+    /// let q = Permutation::new(vec![0, 2, 1]);
+    /// assert_eq!(p.compose(&q), Permutation::new(vec![1, 0, 2]));
+    /// assert_eq!(q.compose(&p), Permutation::new(vec![2, 1, 0]));
+    /// ```
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len());
+        Self(other.0.iter().map(|&i| self.0[i]).collect())
+    }
+
+    /// 逆置換を返します。
+    ///
+    /// # Examples
+    /// ```
+    /// use permutation::Permutation;
+    ///
+    /// let p = Permutation::new(vec![2, 0, 1]);
+    /// assert_eq!(p.inverse(), Permutation::new(vec![1, 2, 0]));
+    /// assert_eq!(p.compose(&p.inverse()), Permutation::identity(3));
+    /// ```
+    pub fn inverse(&self) -> Self {
+        let mut inv = vec![0; self.len()];
+        for (i, &p) in self.0.iter().enumerate() {
+            inv[p] = i;
+        }
+        Self(inv)
+    }
+
+    /// `self` を `k` 回合成した順列を返します。
+    ///
+    /// 巡回置換分解して各巡回の中で `k` 個先を求めるので、`k` によらず O(`n`) 時間です。
+    pub fn pow(&self, k: u64) -> Self {
+        let mut result = vec![0; self.len()];
+        for cycle in self.cycles() {
+            let l = cycle.len();
+            let shift = (k % l as u64) as usize;
+            for (j, &x) in cycle.iter().enumerate() {
+                result[x] = cycle[(j + shift) % l];
+            }
+        }
+        Self(result)
+    }
+
+    /// 巡回置換分解を返します。
+    ///
+    /// 各巡回は最小の要素から始まり `x, p[x], p[p[x]], ...` の順に並びます。巡回は最小の要素の昇順に並び、長さ 1 の巡回 (不動点) も含みます。
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let mut seen = vec![false; n];
+        let mut result = Vec::new();
+        for start in 0..n {
+            if seen[start] {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut x = start;
+            while !seen[x] {
+                seen[x] = true;
+                cycle.push(x);
+                x = self.0[x];
+            }
+            result.push(cycle);
+        }
+        result
+    }
+}
+
+impl Index<usize> for Permutation {
+    type Output = usize;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    fn random_permutation(rng: &mut ThreadRng, n: usize) -> Permutation {
+        let mut p = (0..n).collect::<Vec<_>>();
+        p.shuffle(rng);
+        Permutation::new(p)
+    }
+
+    #[test]
+    fn test_pow() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 10);
+            let p = random_permutation(&mut rng, n);
+            let mut expected = Permutation::identity(n);
+            for k in 0..100 {
+                assert_eq!(p.pow(k), expected);
+                expected = p.compose(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 10);
+            let p = random_permutation(&mut rng, n);
+            let q = random_permutation(&mut rng, n);
+            assert_eq!(p.inverse().compose(&p), Permutation::identity(n));
+            assert_eq!(p.compose(&q).inverse(), q.inverse().compose(&p.inverse()));
+        }
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 10);
+            let p = random_permutation(&mut rng, n);
+            let cycles = p.cycles();
+            assert_eq!(cycles.iter().map(|c| c.len()).sum::<usize>(), n);
+            for c in &cycles {
+                assert_eq!(c[0], *c.iter().min().unwrap());
+                for j in 0..c.len() {
+                    assert_eq!(p[c[j]], c[(j + 1) % c.len()]);
+                }
+            }
+            assert!(cycles.windows(2).all(|w| w[0][0] < w[1][0]));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_not_permutation() {
+        Permutation::new(vec![0, 2, 2]);
+    }
+}
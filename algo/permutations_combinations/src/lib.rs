@@ -0,0 +1,286 @@
+use next_permutation::NextPermutation;
+
+/// `0, 1, ..., n - 1` から `k` 個を選んで並べる順列を辞書順に列挙するイテレータです。
+///
+/// [`permutations`] で作ります。
+pub struct Permutations {
+    k: usize,
+    // 先頭 k 個が現在の順列
+    indices: Vec<usize>,
+    done: bool,
+}
+
+/// `0, 1, ..., n - 1` から `k` 個を選んで並べる順列を辞書順に列挙するイテレータを返します。
+///
+/// 順列の個数は `n! / (n - k)!` 個です。ひとつ進めるのに O(`n`) 時間かかります。`k > n` のときは何も返しません。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::permutations;
+///
+/// let perms = permutations(3, 2).collect::<Vec<_>>();
+/// assert_eq!(
+///     perms,
+///     vec![
+///         vec![0, 1],
+///         vec![0, 2],
+///         vec![1, 0],
+///         vec![1, 2],
+///         vec![2, 0],
+///         vec![2, 1],
+///     ]
+/// );
+/// assert_eq!(permutations(4, 4).count(), 24);
+/// assert_eq!(permutations(3, 0).collect::<Vec<_>>(), vec![vec![]]);
+/// assert_eq!(permutations(2, 3).count(), 0);
+/// ```
+pub fn permutations(n: usize, k: usize) -> Permutations {
+    Permutations {
+        k,
+        indices: (0..n).collect(),
+        done: k > n,
+    }
+}
+
+impl Iterator for Permutations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.indices[..self.k].to_vec();
+        // 後ろ n - k 個を降順にすると、全体の次の順列で先頭 k 個が次の順列になる
+        self.indices[self.k..].reverse();
+        self.done = !self.indices.next_permutation();
+        Some(result)
+    }
+}
+
+/// `0, 1, ..., n - 1` から `k` 個を選ぶ組合せを、昇順に並べた列として辞書順に列挙するイテレータです。
+///
+/// [`combinations`] で作ります。
+pub struct Combinations {
+    n: usize,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+/// `0, 1, ..., n - 1` から `k` 個を選ぶ組合せを、昇順に並べた列として辞書順に列挙するイテレータを返します。
+///
+/// 組合せの個数は `n! / (k! (n - k)!)` 個です。ひとつ進めるのに O(`k`) 時間かかります。`k > n` のときは何も返しません。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::combinations;
+///
+/// let combs = combinations(4, 2).collect::<Vec<_>>();
+/// assert_eq!(
+///     combs,
+///     vec![
+///         vec![0, 1],
+///         vec![0, 2],
+///         vec![0, 3],
+///         vec![1, 2],
+///         vec![1, 3],
+///         vec![2, 3],
+///     ]
+/// );
+/// assert_eq!(combinations(5, 0).collect::<Vec<_>>(), vec![vec![]]);
+/// assert_eq!(combinations(2, 3).count(), 0);
+/// ```
+pub fn combinations(n: usize, k: usize) -> Combinations {
+    Combinations {
+        n,
+        indices: (0..k).collect(),
+        done: k > n,
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.indices.clone();
+        let k = self.indices.len();
+        // 増やせる最も右の位置
+        match (0..k).rev().find(|&i| self.indices[i] < self.n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in (i + 1)..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(result)
+    }
+}
+
+/// `a` から `k` 個を選んで並べる順列を、要素を複製して列挙します。順番は [`permutations`] と同じです。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::permutations_of;
+///
+/// let perms = permutations_of(&['a', 'b', 'c'], 2)
+///     .map(|p| p.into_iter().collect::<String>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(perms, vec!["ab", "ac", "ba", "bc", "ca", "cb"]);
+/// ```
+pub fn permutations_of<T: Clone>(a: &[T], k: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+    permutations(a.len(), k).map(move |p| p.into_iter().map(|i| a[i].clone()).collect())
+}
+
+/// `a` から `k` 個を選ぶ組合せを、要素を複製して列挙します。順番は [`combinations`] と同じです。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::combinations_of;
+///
+/// let sums = combinations_of(&[1, 10, 100], 2)
+///     .map(|c| c.into_iter().sum::<i32>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(sums, vec![11, 101, 110]);
+/// ```
+pub fn combinations_of<T: Clone>(a: &[T], k: usize) -> impl Iterator<Item = Vec<T>> + '_ {
+    combinations(a.len(), k).map(move |c| c.into_iter().map(|i| a[i].clone()).collect())
+}
+
+/// `0, 1, ..., n - 1` の順列のうち、辞書順で `k` 番目 (0-indexed) のものを返します。
+///
+/// 階乗進法で O(`n^2`) 時間です。[`permutation_index`] の逆です。
+///
+/// # Panics
+///
+/// `n > 20` の場合 (`n!` が `u64` に収まらない場合)、または `k >= n!` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::kth_permutation;
+///
+/// assert_eq!(kth_permutation(3, 0), vec![0, 1, 2]);
+/// assert_eq!(kth_permutation(3, 3), vec![1, 2, 0]);
+/// assert_eq!(kth_permutation(3, 5), vec![2, 1, 0]);
+/// ```
+pub fn kth_permutation(n: usize, k: u64) -> Vec<usize> {
+    assert!(n <= 20);
+    let fact = factorials(n);
+    assert!(k < fact[n]);
+    let mut rest = (0..n).collect::<Vec<_>>();
+    let mut k = k;
+    let mut result = Vec::with_capacity(n);
+    for i in (0..n).rev() {
+        let d = (k / fact[i]) as usize;
+        k %= fact[i];
+        result.push(rest.remove(d));
+    }
+    result
+}
+
+/// 順列 `perm` が `0, 1, ..., n - 1` の順列の中で辞書順で何番目 (0-indexed) かを返します。
+///
+/// O(`n^2`) 時間です。[`kth_permutation`] の逆です。
+///
+/// # Panics
+///
+/// `perm` が `0, 1, ..., n - 1` の順列でない場合、または `n > 20` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use permutations_combinations::permutation_index;
+///
+/// assert_eq!(permutation_index(&[0, 1, 2]), 0);
+/// assert_eq!(permutation_index(&[1, 2, 0]), 3);
+/// assert_eq!(permutation_index(&[2, 1, 0]), 5);
+/// ```
+pub fn permutation_index(perm: &[usize]) -> u64 {
+    let n = perm.len();
+    assert!(n <= 20);
+    let fact = factorials(n);
+    let mut used = vec![false; n];
+    let mut result = 0;
+    for (i, &p) in perm.iter().enumerate() {
+        assert!(p < n && !used[p], "not a permutation");
+        // p より小さくまだ使っていない値の個数
+        let d = used[..p].iter().filter(|&&u| !u).count() as u64;
+        result += d * fact[n - 1 - i];
+        used[p] = true;
+    }
+    result
+}
+
+fn factorials(n: usize) -> Vec<u64> {
+    let mut fact = vec![1; n + 1];
+    for i in 1..=n {
+        fact[i] = fact[i - 1] * i as u64;
+    }
+    fact
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0..n の列で長さ k、値が重複しないもの (permutations) または昇順のもの (combinations) を全部作る
+    fn brute_force(n: usize, k: usize, increasing: bool) -> Vec<Vec<usize>> {
+        let mut result = vec![vec![]];
+        for _ in 0..k {
+            let mut next = Vec::new();
+            for p in result {
+                for i in 0..n {
+                    if p.contains(&i) || (increasing && p.last().map_or(false, |&j| j > i)) {
+                        continue;
+                    }
+                    let mut q = p.clone();
+                    q.push(i);
+                    next.push(q);
+                }
+            }
+            result = next;
+        }
+        result
+    }
+
+    #[test]
+    fn test_permutations() {
+        for n in 0..=6 {
+            for k in 0..=7 {
+                let actual = permutations(n, k).collect::<Vec<_>>();
+                assert_eq!(actual, brute_force(n, k, false), "n = {}, k = {}", n, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_kth_permutation() {
+        for n in 0..=6 {
+            for (k, p) in permutations(n, n).enumerate() {
+                assert_eq!(kth_permutation(n, k as u64), p);
+                assert_eq!(permutation_index(&p), k as u64);
+            }
+        }
+        let last = (0..20).rev().collect::<Vec<_>>();
+        assert_eq!(permutation_index(&last), 2_432_902_008_176_639_999);
+        assert_eq!(kth_permutation(20, 2_432_902_008_176_639_999), last);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kth_permutation_out_of_range() {
+        kth_permutation(3, 6);
+    }
+
+    #[test]
+    fn test_combinations() {
+        for n in 0..=8 {
+            for k in 0..=9 {
+                let actual = combinations(n, k).collect::<Vec<_>>();
+                assert_eq!(actual, brute_force(n, k, true), "n = {}, k = {}", n, k);
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+// problem: https://judge.yosupo.jp/problem/multipoint_evaluation
+use join::Join;
+use mod_int::ModInt998244353;
+use polynomial::multipoint_evaluation;
+use proconio::input;
+
+fn main() {
+    input! {
+        n: usize,
+        m: usize,
+        c: [i64; n],
+        p: [i64; m],
+    }
+    let c = c.into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+    let p = p.into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+    let ans = multipoint_evaluation(&c, &p);
+    println!("{}", ans.iter().map(|x| x.val()).join(" "));
+}
@@ -0,0 +1,16 @@
+// problem: https://judge.yosupo.jp/problem/polynomial_taylor_shift
+use join::Join;
+use mod_int::ModInt998244353;
+use polynomial::taylor_shift;
+use proconio::input;
+
+fn main() {
+    input! {
+        n: usize,
+        c: i64,
+        a: [i64; n],
+    }
+    let a = a.into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+    let b = taylor_shift(&a, ModInt998244353::new(c));
+    println!("{}", b.iter().map(|x| x.val()).join(" "));
+}
@@ -0,0 +1,441 @@
+//! mod 998244353 の多項式の演算です。多項式は係数の列 `f[0] + f[1] x + f[2] x^2 + ...` で表します。
+
+use mod_int::ModInt998244353;
+
+type Mint = ModInt998244353;
+
+// 998244353 の原始根
+const PRIMITIVE_ROOT: i64 = 3;
+
+/// 畳み込み `c[k] = Σ_{i+j=k} a[i] * b[j]` を NTT で O((`n` + `m`) log (`n` + `m`)) 時間で求めます。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::convolution;
+///
+/// let a = vec![1, 2, 3].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let b = vec![4, 5].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let c = convolution(&a, &b);
+/// assert_eq!(c.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![4, 13, 22, 15]);
+/// ```
+pub fn convolution(a: &[Mint], b: &[Mint]) -> Vec<Mint> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+    if a.len().min(b.len()) <= 32 {
+        let mut c = vec![Mint::new(0); len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x * y;
+            }
+        }
+        return c;
+    }
+    let n = len.next_power_of_two();
+    let mut fa = a.to_vec();
+    fa.resize(n, Mint::new(0));
+    let mut fb = b.to_vec();
+    fb.resize(n, Mint::new(0));
+    ntt(&mut fa, false);
+    ntt(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb) {
+        *x *= y;
+    }
+    ntt(&mut fa, true);
+    fa.truncate(len);
+    fa
+}
+
+fn ntt(a: &mut [Mint], invert: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let mut w = Mint::new(PRIMITIVE_ROOT).pow(((Mint::modulo() - 1) / len as i64) as u32);
+        if invert {
+            w = w.inv();
+        }
+        for start in (0..n).step_by(len) {
+            let mut wk = Mint::new(1);
+            for k in start..(start + len / 2) {
+                let u = a[k];
+                let v = a[k + len / 2] * wk;
+                a[k] = u + v;
+                a[k + len / 2] = u - v;
+                wk *= w;
+            }
+        }
+        len <<= 1;
+    }
+    if invert {
+        let n_inv = Mint::new(n as i64).inv();
+        for x in a.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+}
+
+/// `f g = 1 (mod x^n)` を満たす `g` を O(`n` log `n`) 時間で求めます。
+///
+/// # Panics
+///
+/// `f[0]` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::inverse;
+///
+/// // 1 / (1 - x) = 1 + x + x^2 + ...
+/// let f = vec![ModInt998244353::new(1), ModInt998244353::new(-1)];
+/// let g = inverse(&f, 4);
+/// assert_eq!(g.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![1, 1, 1, 1]);
+/// ```
+pub fn inverse(f: &[Mint], n: usize) -> Vec<Mint> {
+    assert!(!f.is_empty() && f[0].val() != 0);
+    let mut g = vec![f[0].inv()];
+    let mut len = 1;
+    while len < n {
+        len *= 2;
+        // g <- g (2 - f g)
+        let mut h = convolution(&f[..f.len().min(len)], &g);
+        h.truncate(len);
+        for x in h.iter_mut() {
+            *x = -*x;
+        }
+        h[0] += 2;
+        g = convolution(&g, &h);
+        g.truncate(len);
+    }
+    g.truncate(n);
+    g
+}
+
+/// `f = q g + r`, `deg r < deg g` を満たす商 `q` と余り `r` を O(`n` log `n`) 時間で求めます。
+///
+/// `r` の長さは `g.len() - 1` です。
+///
+/// # Panics
+///
+/// `g` の最後の要素が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::div_rem;
+///
+/// // x^3 + 2x + 3 = (x^2 + x + 3)(x - 1) + 6
+/// let f = vec![3, 2, 0, 1].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let g = vec![-1, 1].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let (q, r) = div_rem(&f, &g);
+/// assert_eq!(q.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![3, 1, 1]);
+/// assert_eq!(r.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![6]);
+/// ```
+pub fn div_rem(f: &[Mint], g: &[Mint]) -> (Vec<Mint>, Vec<Mint>) {
+    assert!(!g.is_empty() && g[g.len() - 1].val() != 0);
+    let m = g.len();
+    if f.len() < m {
+        let mut r = f.to_vec();
+        r.resize(m - 1, Mint::new(0));
+        return (Vec::new(), r);
+    }
+    let k = f.len() - m + 1;
+    let f_rev = f.iter().rev().take(k).copied().collect::<Vec<_>>();
+    let g_rev = g.iter().rev().copied().collect::<Vec<_>>();
+    let mut q = convolution(&f_rev, &inverse(&g_rev, k));
+    q.truncate(k);
+    q.reverse();
+    let qg = convolution(&q, g);
+    let r = (0..(m - 1)).map(|i| f[i] - qg[i]).collect();
+    (q, r)
+}
+
+/// `f(x + c)` の係数を O(`n` log `n`) 時間で求めます。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::taylor_shift;
+///
+/// // (x + 1)^2 = x^2 + 2x + 1
+/// let f = vec![0, 0, 1].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let g = taylor_shift(&f, ModInt998244353::new(1));
+/// assert_eq!(g.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![1, 2, 1]);
+/// ```
+pub fn taylor_shift(f: &[Mint], c: Mint) -> Vec<Mint> {
+    let n = f.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut fact = vec![Mint::new(1); n];
+    for i in 1..n {
+        fact[i] = fact[i - 1] * i;
+    }
+    let mut fact_inv = vec![fact[n - 1].inv(); n];
+    for i in (1..n).rev() {
+        fact_inv[i - 1] = fact_inv[i] * i;
+    }
+    // a[i] = f[n - 1 - i] * (n - 1 - i)!
+    let a = (0..n)
+        .map(|i| f[n - 1 - i] * fact[n - 1 - i])
+        .collect::<Vec<_>>();
+    // b[j] = c^j / j!
+    let mut b = Vec::with_capacity(n);
+    let mut pow = Mint::new(1);
+    for &x in &fact_inv {
+        b.push(pow * x);
+        pow *= c;
+    }
+    let ab = convolution(&a, &b);
+    (0..n).map(|k| ab[n - 1 - k] * fact_inv[k]).collect()
+}
+
+/// 全ての `i` について `f(xs[i])` を O(`n` log^2 `n`) 時間で求めます。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::multipoint_evaluation;
+///
+/// // x^2 + 1
+/// let f = vec![1, 0, 1].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let xs = vec![0, 1, 2, 3].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let ys = multipoint_evaluation(&f, &xs);
+/// assert_eq!(ys.iter().map(|y| y.val()).collect::<Vec<_>>(), vec![1, 2, 5, 10]);
+/// ```
+pub fn multipoint_evaluation(f: &[Mint], xs: &[Mint]) -> Vec<Mint> {
+    let m = xs.len();
+    if m == 0 {
+        return Vec::new();
+    }
+    let tree = SubproductTree::new(xs);
+    let (_, r) = div_rem(f, &tree.nodes[1]);
+    let mut result = vec![Mint::new(0); m];
+    tree.evaluate(&r, 1, 0, m, &mut result);
+    result
+}
+
+/// `g(xs[i]) = ys[i]` を満たす次数 `n - 1` 以下の多項式 `g` を O(`n` log^2 `n`) 時間で求めます。
+///
+/// # Panics
+///
+/// `xs` と `ys` の長さが異なる場合パニックです。
+///
+/// `xs` に重複がある場合の結果は未定義です。
+///
+/// # Examples
+/// ```
+/// use mod_int::ModInt998244353;
+/// use polynomial::interpolation;
+///
+/// let xs = vec![0, 1, 2].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// let ys = vec![1, 2, 5].into_iter().map(ModInt998244353::new).collect::<Vec<_>>();
+/// // x^2 + 1
+/// let g = interpolation(&xs, &ys);
+/// assert_eq!(g.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![1, 0, 1]);
+/// ```
+pub fn interpolation(xs: &[Mint], ys: &[Mint]) -> Vec<Mint> {
+    assert_eq!(xs.len(), ys.len());
+    let m = xs.len();
+    if m == 0 {
+        return Vec::new();
+    }
+    let tree = SubproductTree::new(xs);
+    // P(x) = Π (x - xs[i]) として ys[i] / P'(xs[i]) を重みにする
+    let p = &tree.nodes[1];
+    let dp = (1..p.len()).map(|i| p[i] * i).collect::<Vec<_>>();
+    let mut d = vec![Mint::new(0); m];
+    tree.evaluate(&div_rem(&dp, p).1, 1, 0, m, &mut d);
+    let w = ys.iter().zip(d).map(|(&y, d)| y / d).collect::<Vec<_>>();
+    let mut g = tree.combine(&w, 1, 0, m);
+    g.resize(m, Mint::new(0));
+    g
+}
+
+struct SubproductTree<'a> {
+    xs: &'a [Mint],
+    // nodes[i]: 区間 [l, r) の (x - xs[j]) の積
+    nodes: Vec<Vec<Mint>>,
+}
+
+impl<'a> SubproductTree<'a> {
+    fn new(xs: &'a [Mint]) -> Self {
+        let mut tree = Self {
+            xs,
+            nodes: vec![Vec::new(); xs.len() * 4],
+        };
+        tree.build(1, 0, xs.len());
+        tree
+    }
+
+    fn build(&mut self, node: usize, l: usize, r: usize) {
+        if r - l == 1 {
+            self.nodes[node] = vec![-self.xs[l], Mint::new(1)];
+            return;
+        }
+        let mid = (l + r) / 2;
+        self.build(node * 2, l, mid);
+        self.build(node * 2 + 1, mid, r);
+        self.nodes[node] = convolution(&self.nodes[node * 2], &self.nodes[node * 2 + 1]);
+    }
+
+    // f を nodes[node] で割った余りから各点の値を求める
+    fn evaluate(&self, f: &[Mint], node: usize, l: usize, r: usize, result: &mut [Mint]) {
+        if r - l <= 32 {
+            for (y, &x) in result[l..r].iter_mut().zip(&self.xs[l..r]) {
+                *y = f.iter().rev().fold(Mint::new(0), |acc, &c| acc * x + c);
+            }
+            return;
+        }
+        let mid = (l + r) / 2;
+        self.evaluate(
+            &div_rem(f, &self.nodes[node * 2]).1,
+            node * 2,
+            l,
+            mid,
+            result,
+        );
+        self.evaluate(
+            &div_rem(f, &self.nodes[node * 2 + 1]).1,
+            node * 2 + 1,
+            mid,
+            r,
+            result,
+        );
+    }
+
+    // Σ w[i] Π_{j≠i} (x - xs[j])
+    fn combine(&self, w: &[Mint], node: usize, l: usize, r: usize) -> Vec<Mint> {
+        if r - l == 1 {
+            return vec![w[l]];
+        }
+        let mid = (l + r) / 2;
+        let left = self.combine(w, node * 2, l, mid);
+        let right = self.combine(w, node * 2 + 1, mid, r);
+        let a = convolution(&left, &self.nodes[node * 2 + 1]);
+        let b = convolution(&right, &self.nodes[node * 2]);
+        let mut result = vec![Mint::new(0); a.len().max(b.len())];
+        for (i, x) in a.into_iter().enumerate() {
+            result[i] += x;
+        }
+        for (i, x) in b.into_iter().enumerate() {
+            result[i] += x;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    fn random_poly(rng: &mut ThreadRng, n: usize) -> Vec<Mint> {
+        (0..n)
+            .map(|_| Mint::new(rng.gen_range(0, Mint::modulo())))
+            .collect()
+    }
+
+    fn eval(f: &[Mint], x: Mint) -> Mint {
+        f.iter().rev().fold(Mint::new(0), |acc, &c| acc * x + c)
+    }
+
+    #[test]
+    fn test_convolution() {
+        let mut rng = thread_rng();
+        for n in 1..=100 {
+            let a = random_poly(&mut rng, n);
+            let m = rng.gen_range(1, 100);
+            let b = random_poly(&mut rng, m);
+            let mut expected = vec![Mint::new(0); a.len() + b.len() - 1];
+            for i in 0..a.len() {
+                for j in 0..b.len() {
+                    expected[i + j] += a[i] * b[j];
+                }
+            }
+            let actual = convolution(&a, &b);
+            assert_eq!(
+                actual.iter().map(|x| x.val()).collect::<Vec<_>>(),
+                expected.iter().map(|x| x.val()).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let mut rng = thread_rng();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 200);
+            let m = rng.gen_range(1, 100);
+            let f = random_poly(&mut rng, n);
+            let mut g = random_poly(&mut rng, m);
+            *g.last_mut().unwrap() = Mint::new(1);
+            let (q, r) = div_rem(&f, &g);
+            assert_eq!(r.len(), g.len() - 1);
+            let mut qg = convolution(&q, &g);
+            qg.resize(f.len().max(r.len()), Mint::new(0));
+            for (i, x) in r.iter().enumerate() {
+                qg[i] += *x;
+            }
+            let mut f = f;
+            f.resize(qg.len(), Mint::new(0));
+            assert_eq!(
+                qg.iter().map(|x| x.val()).collect::<Vec<_>>(),
+                f.iter().map(|x| x.val()).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_taylor_shift() {
+        let mut rng = thread_rng();
+        for n in 0..50 {
+            let f = random_poly(&mut rng, n);
+            let c = Mint::new(rng.gen_range(0, Mint::modulo()));
+            let g = taylor_shift(&f, c);
+            for _ in 0..5 {
+                let x = Mint::new(rng.gen_range(0, Mint::modulo()));
+                assert_eq!(eval(&g, x).val(), eval(&f, x + c).val());
+            }
+        }
+    }
+
+    #[test]
+    fn test_multipoint_evaluation_and_interpolation() {
+        let mut rng = thread_rng();
+        for m in [1, 2, 10, 33, 100, 300] {
+            let f = random_poly(&mut rng, m);
+            let mut xs = (0..m)
+                .map(|_| rng.gen_range(0, Mint::modulo()))
+                .collect::<Vec<_>>();
+            xs.sort();
+            xs.dedup();
+            let xs = xs.into_iter().map(Mint::new).collect::<Vec<_>>();
+            let ys = multipoint_evaluation(&f, &xs);
+            for (&x, &y) in xs.iter().zip(&ys) {
+                assert_eq!(y.val(), eval(&f, x).val());
+            }
+            if xs.len() == m {
+                let g = interpolation(&xs, &ys);
+                assert_eq!(
+                    g.iter().map(|x| x.val()).collect::<Vec<_>>(),
+                    f.iter().map(|x| x.val()).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+}
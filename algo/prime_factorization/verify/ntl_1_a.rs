@@ -0,0 +1,18 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=NTL_1_A
+
+use proconio::input;
+
+use prime_factorization::PrimeFactorization;
+
+fn main() {
+    input! {
+        n: u64,
+    }
+    let mut factors = Vec::new();
+    for (p, e) in n.prime_factorization() {
+        for _ in 0..e {
+            factors.push(p.to_string());
+        }
+    }
+    println!("{}: {}", n, factors.join(" "));
+}
@@ -0,0 +1,89 @@
+/// This `struct` is created by the [`quotient_blocks`] function.
+/// See its documentation for more.
+///
+/// [`quotient_blocks`]: fn.quotient_blocks.html
+pub struct QuotientBlocks {
+    n: u64,
+    l: Option<u64>,
+}
+
+/// `floor(n / i)` の値が等しい `i` の極大な区間 `[l, r]` を `l` の昇順に yield するイテレータを作ります。
+///
+/// `1 <= i <= n` の範囲で `floor(n / i)` がとる値は O(sqrt(`n`)) 通りしかないので、区間の個数も O(sqrt(`n`)) です。
+///
+/// 各要素は `(l, r, floor(n / l))` です。
+///
+/// # Examples
+/// ```
+/// use quotient_blocks::quotient_blocks;
+///
+/// // i        : 1  2 3 4 5 6 7 8 9 10
+/// // 10 / i   : 10 5 3 2 2 1 1 1 1 1
+/// let blocks = quotient_blocks(10).collect::<Vec<_>>();
+/// assert_eq!(
+///     blocks,
+///     vec![(1, 1, 10), (2, 2, 5), (3, 3, 3), (4, 5, 2), (6, 10, 1)]
+/// );
+///
+/// // Σ_{i=1}^{n} floor(n / i)
+/// let sum = quotient_blocks(10)
+///     .map(|(l, r, q)| (r - l + 1) * q)
+///     .sum::<u64>();
+/// assert_eq!(sum, 27);
+/// ```
+pub fn quotient_blocks(n: u64) -> QuotientBlocks {
+    QuotientBlocks { n, l: Some(1) }
+}
+
+impl Iterator for QuotientBlocks {
+    type Item = (u64, u64, u64);
+    fn next(&mut self) -> Option<Self::Item> {
+        let l = self.l.filter(|&l| l <= self.n)?;
+        let q = self.n / l;
+        let r = self.n / q;
+        self.l = r.checked_add(1);
+        Some((l, r, q))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{quotient_blocks, QuotientBlocks};
+
+    #[test]
+    fn test() {
+        for n in 0..=300 {
+            let mut expected = Vec::new();
+            for i in 1..=n {
+                match expected.last_mut() {
+                    Some((_, r, q)) if *q == n / i => *r = i,
+                    _ => expected.push((i, i, n / i)),
+                }
+            }
+            assert_eq!(quotient_blocks(n).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_large() {
+        let n = 1_000_000_000_000;
+        let blocks = quotient_blocks(n).collect::<Vec<_>>();
+        assert_eq!(blocks.first(), Some(&(1, 1, n)));
+        assert_eq!(blocks.last(), Some(&(n / 2 + 1, n, 1)));
+        for w in blocks.windows(2) {
+            assert_eq!(w[0].1 + 1, w[1].0);
+            assert!(w[0].2 > w[1].2);
+        }
+        assert!(blocks.len() <= 2_000_000);
+    }
+
+    #[test]
+    fn test_max() {
+        let n = std::u64::MAX;
+        let mut blocks = quotient_blocks(n);
+        assert_eq!(blocks.next(), Some((1, 1, n)));
+        let mut blocks = QuotientBlocks { n, l: Some(n) };
+        assert_eq!(blocks.next(), Some((n, n, 1)));
+        assert_eq!(blocks.next(), None);
+    }
+}
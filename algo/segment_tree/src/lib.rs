@@ -1,20 +1,85 @@
 use std::fmt;
 use std::ops::{Bound, Index, RangeBounds};
 
-/// __注意⚠__ この実装は遅いので time limit の厳しい問題には代わりに ACL のセグメントツリーを使うこと。
+/// セグメントツリーに載せるモノイドです。
 ///
-/// セグメントツリーです。
+/// 演算を持たない型 (ZST) に実装すると、クロージャを持つ場合と違って演算が直接呼び出されます。
+///
+/// # Examples
+/// ```
+/// use segment_tree::{Monoid, SegmentTree};
+///
+/// struct Max;
+/// impl Monoid for Max {
+///     type S = i64;
+///     fn e(&self) -> i64 {
+///         i64::MIN
+///     }
+///     fn op(&self, a: &i64, b: &i64) -> i64 {
+///         *a.max(b)
+///     }
+/// }
+///
+/// let mut seg = SegmentTree::with_monoid(5, Max);
+/// seg.set(1, 3);
+/// seg.set(3, 1);
+/// assert_eq!(seg.fold(..), 3);
+/// assert_eq!(seg.fold(2..), 1);
+/// assert_eq!(seg.fold(4..), i64::MIN);
+/// ```
+pub trait Monoid {
+    type S: Clone;
+    /// 単位元を返します。
+    fn e(&self) -> Self::S;
+    /// 二項演算の結果を返します。結合的でなければいけません。
+    fn op(&self, a: &Self::S, b: &Self::S) -> Self::S;
+}
+
+/// 単位元とクロージャで作るモノイドです。[`SegmentTree::new`] で使います。
 #[derive(Clone)]
-pub struct SegmentTree<T, F> {
-    original_n: usize,
-    n: usize,
-    dat: Vec<T>,
+pub struct FnMonoid<T, F> {
     e: T,
     multiply: F,
 }
 
-// https://hcpc-hokudai.github.io/archive/structure_segtree_001.pdf
-impl<T, F> SegmentTree<T, F>
+impl<T, F> Monoid for FnMonoid<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    type S = T;
+
+    fn e(&self) -> T {
+        self.e.clone()
+    }
+
+    fn op(&self, a: &T, b: &T) -> T {
+        (self.multiply)(a, b)
+    }
+}
+
+/// セグメントツリーです。
+///
+/// # Examples
+/// ```
+/// use segment_tree::SegmentTree;
+///
+/// let mut seg = SegmentTree::new(4, String::new(), |a, b| format!("{a}{b}"));
+/// seg.set(0, "a".to_string());
+/// seg.set(2, "c".to_string());
+/// seg.set(3, "d".to_string());
+/// assert_eq!(seg.fold(..), "acd");
+/// assert_eq!(seg.fold(1..3), "c");
+/// ```
+#[derive(Clone)]
+pub struct SegmentTree<M: Monoid> {
+    n: usize,
+    // dat[n..2n] が列、dat[0] は使わない
+    dat: Vec<M::S>,
+    monoid: M,
+}
+
+impl<T, F> SegmentTree<FnMonoid<T, F>>
 where
     T: Clone,
     F: Fn(&T, &T) -> T,
@@ -23,44 +88,73 @@ where
     ///
     /// `multiply` は fold に使う二項演算です。
     pub fn new(n: usize, e: T, multiply: F) -> Self {
-        let original_n = n;
-        let n = n.next_power_of_two();
+        Self::with_monoid(n, FnMonoid { e, multiply })
+    }
+}
+
+// https://codeforces.com/blog/entry/18051
+impl<M: Monoid> SegmentTree<M> {
+    /// 長さ `n` の列をモノイド `monoid` の単位元で初期化します。
+    pub fn with_monoid(n: usize, monoid: M) -> Self {
         Self {
-            original_n,
             n,
-            dat: vec![e.clone(); n * 2], // dat[0] is unused
-            e,
-            multiply,
+            dat: vec![monoid.e(); n * 2],
+            monoid,
         }
     }
 
+    /// 列の長さを返します。
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// 列が空かどうかを返します。
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// 列の要素を前から順に返すイテレータを返します。
+    pub fn iter(&self) -> std::slice::Iter<'_, M::S> {
+        self.as_slice().iter()
+    }
+
+    /// 列をスライスとして返します。
+    pub fn as_slice(&self) -> &[M::S] {
+        &self.dat[self.n..]
+    }
+
+    /// 列を `Vec` にコピーして返します。
+    pub fn to_vec(&self) -> Vec<M::S> {
+        self.as_slice().to_vec()
+    }
+
     /// 列の `i` 番目の要素を取得します。
-    pub fn get(&self, i: usize) -> &T {
-        assert!(i < self.original_n);
+    pub fn get(&self, i: usize) -> &M::S {
+        assert!(i < self.n);
         &self.dat[i + self.n]
     }
 
     /// 列の `i` 番目の要素を `x` で更新します。
-    pub fn set(&mut self, i: usize, x: T) {
+    pub fn set(&mut self, i: usize, x: M::S) {
         self.update(i, |_| x);
     }
 
     /// 列の `i` 番目の要素を `f` で更新します。
     pub fn update<U>(&mut self, i: usize, f: U)
     where
-        U: FnOnce(&T) -> T,
+        U: FnOnce(&M::S) -> M::S,
     {
-        assert!(i < self.original_n);
+        assert!(i < self.n);
         let mut k = i + self.n;
         self.dat[k] = f(&self.dat[k]);
         while k > 1 {
             k >>= 1;
-            self.dat[k] = (self.multiply)(&self.dat[k << 1], &self.dat[k << 1 | 1]);
+            self.dat[k] = self.monoid.op(&self.dat[k << 1], &self.dat[k << 1 | 1]);
         }
     }
 
     /// `range` が `l..r` として、`multiply(l番目の要素, multiply(..., multiply(r-2番目の要素, r-1番目の要素)))` の値を返します。
-    pub fn fold(&self, range: impl RangeBounds<usize>) -> T {
+    pub fn fold(&self, range: impl RangeBounds<usize>) -> M::S {
         let start = match range.start_bound() {
             Bound::Included(&start) => start,
             Bound::Excluded(&start) => start + 1,
@@ -69,60 +163,77 @@ where
         let end = match range.end_bound() {
             Bound::Included(&end) => end + 1,
             Bound::Excluded(&end) => end,
-            Bound::Unbounded => self.original_n,
+            Bound::Unbounded => self.n,
         };
-        assert!(start <= end && end <= self.original_n);
+        assert!(start <= end && end <= self.n);
         self._fold(start, end)
     }
 
-    fn _fold(&self, mut l: usize, mut r: usize) -> T {
-        let mut acc_l = self.e.clone();
-        let mut acc_r = self.e.clone();
+    fn _fold(&self, mut l: usize, mut r: usize) -> M::S {
+        // 単位元を作らずに済むように、まだ何も足していないときは None にする
+        let mut acc_l: Option<M::S> = None;
+        let mut acc_r: Option<M::S> = None;
         l += self.n;
         r += self.n;
         while l < r {
             if l & 1 == 1 {
                 // 右の子だったらいま足しておかないといけない
                 // 左の子だったら祖先のどれかで足されるのでよい
-                acc_l = (self.multiply)(&acc_l, &self.dat[l]);
+                acc_l = Some(match acc_l {
+                    Some(acc) => self.monoid.op(&acc, &self.dat[l]),
+                    None => self.dat[l].clone(),
+                });
                 l += 1;
             }
             if r & 1 == 1 {
                 // r が exclusive であることに注意する
                 r -= 1;
-                acc_r = (self.multiply)(&self.dat[r], &acc_r);
+                acc_r = Some(match acc_r {
+                    Some(acc) => self.monoid.op(&self.dat[r], &acc),
+                    None => self.dat[r].clone(),
+                });
             }
             l >>= 1;
             r >>= 1;
         }
-        (self.multiply)(&acc_l, &acc_r)
+        match (acc_l, acc_r) {
+            (Some(acc_l), Some(acc_r)) => self.monoid.op(&acc_l, &acc_r),
+            (Some(acc), None) | (None, Some(acc)) => acc,
+            (None, None) => self.monoid.e(),
+        }
     }
 }
 
-impl<T, F> Index<usize> for SegmentTree<T, F>
-where
-    T: Clone,
-    F: Fn(&T, &T) -> T,
-{
-    type Output = T;
+impl<M: Monoid> Index<usize> for SegmentTree<M> {
+    type Output = M::S;
 
     fn index(&self, index: usize) -> &Self::Output {
         self.get(index)
     }
 }
 
-impl<T, F> fmt::Debug for SegmentTree<T, F>
+impl<'a, M: Monoid> IntoIterator for &'a SegmentTree<M> {
+    type Item = &'a M::S;
+    type IntoIter = std::slice::Iter<'a, M::S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<M> fmt::Debug for SegmentTree<M>
 where
-    T: fmt::Debug,
+    M: Monoid,
+    M::S: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", &self.dat[self.n..])
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::SegmentTree;
+    use crate::{Monoid, SegmentTree};
 
     #[test]
     fn test() {
@@ -154,4 +265,66 @@ mod tests {
         seg.set(0, 42);
         assert_eq!(seg[0], 42);
     }
+
+    struct Concat;
+    impl Monoid for Concat {
+        type S = String;
+        fn e(&self) -> String {
+            String::new()
+        }
+        fn op(&self, a: &String, b: &String) -> String {
+            format!("{a}{b}")
+        }
+    }
+
+    #[test]
+    fn non_power_of_two_length() {
+        // 長さが 2 べきでなくても、可換でない演算の順序が保たれる
+        let s = "abcdefghijklmnopq";
+        for n in 0..=s.len() {
+            let mut seg = SegmentTree::with_monoid(n, Concat);
+            for (i, c) in s[..n].chars().enumerate() {
+                seg.set(i, c.to_string());
+            }
+            for i in 0..=n {
+                for j in i..=n {
+                    assert_eq!(s[i..j], seg.fold(i..j), "n = {}", n);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn update_test() {
+        let mut seg = SegmentTree::new(5, 0, |a, b| a + b);
+        for i in 0..5 {
+            seg.update(i, |x| x + i);
+            seg.update(i, |x| x * 2);
+        }
+        // [0, 2, 4, 6, 8]
+        assert_eq!(seg.fold(..), 20);
+        assert_eq!(seg.fold(1..4), 12);
+        assert_eq!(format!("{:?}", seg), "[0, 2, 4, 6, 8]");
+    }
+
+    #[test]
+    fn sequence_accessors() {
+        let mut seg = SegmentTree::new(3, 0, |a, b| a + b);
+        seg.set(0, 1);
+        seg.set(2, 3);
+        assert_eq!(seg.len(), 3);
+        assert!(!seg.is_empty());
+        assert_eq!(seg.as_slice(), &[1, 0, 3]);
+        assert_eq!(seg.to_vec(), vec![1, 0, 3]);
+        assert_eq!(seg.iter().copied().collect::<Vec<_>>(), vec![1, 0, 3]);
+        assert_eq!((&seg).into_iter().max(), Some(&3));
+        // 内部の節点は表示しない
+        assert_eq!(format!("{:?}", seg), "[1, 0, 3]");
+
+        let seg = SegmentTree::with_monoid(0, Concat);
+        assert_eq!(seg.len(), 0);
+        assert!(seg.is_empty());
+        assert_eq!(seg.iter().next(), None);
+        assert_eq!(format!("{:?}", seg), "[]");
+    }
 }
@@ -0,0 +1,105 @@
+use floor_sqrt::floor_sqrt;
+
+/// `l` 以上 `r` 未満の素数を昇順に返します。
+///
+/// `sqrt(r)` 以下の素数で区間 `[l, r)` をふるうので、O(`sqrt(r)` + (`r` - `l`) log log `r`) 時間、O(`sqrt(r)` + `r` - `l`) 空間です。
+/// `r` が `10^12` 程度でも区間の長さが小さければ使えます。
+///
+/// # Examples
+/// ```
+/// use segmented_sieve::primes_in_range;
+///
+/// assert_eq!(primes_in_range(0, 20), vec![2, 3, 5, 7, 11, 13, 17, 19]);
+/// assert_eq!(
+///     primes_in_range(1_000_000_000_000, 1_000_000_000_100),
+///     vec![1_000_000_000_039, 1_000_000_000_061, 1_000_000_000_063, 1_000_000_000_091]
+/// );
+/// ```
+pub fn primes_in_range(l: u64, r: u64) -> Vec<u64> {
+    least_prime_factors_in_range(l, r)
+        .into_iter()
+        .zip(l..)
+        .filter(|&(p, k)| k >= 2 && p == k)
+        .map(|(_, k)| k)
+        .collect()
+}
+
+/// `l` 以上 `r` 未満の全ての `k` について「`k` を割る最小の素数」を計算します。`result[i]` が `l + i` に対応します。`k` が `2` 未満のときは `0` です。
+///
+/// [`primes_in_range`] と同じく O(`sqrt(r)` + (`r` - `l`) log log `r`) 時間です。
+///
+/// [`primes_in_range`]: fn.primes_in_range.html
+///
+/// # Examples
+/// ```
+/// use segmented_sieve::least_prime_factors_in_range;
+///
+/// assert_eq!(least_prime_factors_in_range(0, 10), vec![0, 0, 2, 3, 2, 5, 2, 7, 2, 3]);
+/// // 10^12 + 1 = 73 * 137 * 99990001
+/// assert_eq!(least_prime_factors_in_range(1_000_000_000_001, 1_000_000_000_002), vec![73]);
+/// ```
+pub fn least_prime_factors_in_range(l: u64, r: u64) -> Vec<u64> {
+    if l >= r {
+        return Vec::new();
+    }
+    let mut result = vec![0; (r - l) as usize];
+    let sqrt = floor_sqrt(r - 1) as usize;
+    let mut is_prime = vec![true; sqrt + 1];
+    for p in 2..=sqrt {
+        if !is_prime[p] {
+            continue;
+        }
+        for j in ((p * p)..=sqrt).step_by(p) {
+            is_prime[j] = false;
+        }
+        let p = p as u64;
+        // l 以上で最小の p^2 以上の p の倍数
+        let start = (p * p).max((l + p - 1) / p * p);
+        for k in (start..r).step_by(p as usize) {
+            let x = &mut result[(k - l) as usize];
+            if *x == 0 {
+                *x = p;
+            }
+        }
+    }
+    for (x, k) in result.iter_mut().zip(l..) {
+        if *x == 0 && k >= 2 {
+            // sqrt(r) 以下の素因数を持たない
+            *x = k;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use least_prime_factors::least_prime_factors;
+
+    #[test]
+    fn test_least_prime_factors_in_range() {
+        let n = 500;
+        let expected = least_prime_factors(n);
+        for l in 0..n {
+            for r in l..n {
+                let actual = least_prime_factors_in_range(l as u64, r as u64);
+                assert_eq!(actual.len(), r - l);
+                for k in l..r {
+                    assert_eq!(actual[k - l], expected[k] as u64, "k = {}", k);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_primes_in_range() {
+        // 10^12 以上 10^12 + 10^5 未満の素数
+        let l = 1_000_000_000_000;
+        let primes = primes_in_range(l, l + 100_000);
+        let is_prime = |k: u64| (2..).take_while(|&d| d * d <= k).all(|d| k % d != 0);
+        assert_eq!(primes.len(), 3614);
+        for k in (l..).take(100) {
+            assert_eq!(primes.binary_search(&k).is_ok(), is_prime(k));
+        }
+    }
+}
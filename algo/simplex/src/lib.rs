@@ -0,0 +1,271 @@
+const EPS: f64 = 1e-9;
+
+/// 線形計画問題の解です。
+#[derive(Debug, Clone, PartialEq)]
+pub enum LpSolution {
+    /// 最適値 `value` と、それを達成する `x` です。
+    Optimal { value: f64, x: Vec<f64> },
+    /// 制約を満たす `x` が存在しません。
+    Infeasible,
+    /// 目的関数をいくらでも大きくできます。
+    Unbounded,
+}
+
+/// 線形計画問題
+///
+/// maximize `c^T x` subject to `a x <= b`, `x >= 0`
+///
+/// を二段階単体法で解きます。
+///
+/// 変数・制約が数十個程度の小さい問題を想定しています。最悪計算量は指数時間ですが、実用上は速く動きます。巡回を避けるために Bland の規則を使っています。
+///
+/// # Panics
+///
+/// `a` が `b.len()` 行 `c.len()` 列の行列でない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use simplex::{simplex, LpSolution};
+///
+/// // maximize 3x + 2y
+/// // subject to
+/// //   x + y <= 4
+/// //   x + 3y <= 6
+/// //   x <= 3
+/// let a = vec![vec![1.0, 1.0], vec![1.0, 3.0], vec![1.0, 0.0]];
+/// let b = vec![4.0, 6.0, 3.0];
+/// let c = vec![3.0, 2.0];
+/// match simplex(&a, &b, &c) {
+///     LpSolution::Optimal { value, x } => {
+///         assert!((value - 11.0).abs() < 1e-9);
+///         assert!((x[0] - 3.0).abs() < 1e-9);
+///         assert!((x[1] - 1.0).abs() < 1e-9);
+///     }
+///     _ => unreachable!(),
+/// }
+///
+/// // x <= -1, x >= 0
+/// assert_eq!(simplex(&[vec![1.0]], &[-1.0], &[1.0]), LpSolution::Infeasible);
+/// // maximize x subject to -x <= 1
+/// assert_eq!(simplex(&[vec![-1.0]], &[1.0], &[1.0]), LpSolution::Unbounded);
+/// ```
+pub fn simplex(a: &[Vec<f64>], b: &[f64], c: &[f64]) -> LpSolution {
+    Tableau::new(a, b, c).solve()
+}
+
+struct Tableau {
+    m: usize,
+    n: usize,
+    // d[m]: 目的関数, d[m + 1]: 第一段階の目的関数
+    d: Vec<Vec<f64>>,
+    // 基底変数と非基底変数。-1 は第一段階で使う人為変数
+    basis: Vec<isize>,
+    non_basis: Vec<isize>,
+}
+
+impl Tableau {
+    fn new(a: &[Vec<f64>], b: &[f64], c: &[f64]) -> Self {
+        let m = b.len();
+        let n = c.len();
+        assert_eq!(a.len(), m);
+        let mut d = vec![vec![0.0; n + 2]; m + 2];
+        for i in 0..m {
+            assert_eq!(a[i].len(), n);
+            d[i][..n].copy_from_slice(&a[i]);
+            d[i][n] = -1.0;
+            d[i][n + 1] = b[i];
+        }
+        for (x, &c) in d[m].iter_mut().zip(c) {
+            *x = -c;
+        }
+        d[m + 1][n] = 1.0;
+        let mut non_basis = (0..n as isize).collect::<Vec<_>>();
+        non_basis.push(-1);
+        let basis = (0..m).map(|i| (n + i) as isize).collect();
+        Self {
+            m,
+            n,
+            d,
+            basis,
+            non_basis,
+        }
+    }
+
+    fn pivot(&mut self, r: usize, s: usize) {
+        let inv = 1.0 / self.d[r][s];
+        for i in 0..(self.m + 2) {
+            if i == r {
+                continue;
+            }
+            let f = self.d[i][s] * inv;
+            for j in 0..(self.n + 2) {
+                if j != s {
+                    self.d[i][j] -= self.d[r][j] * f;
+                }
+            }
+            self.d[i][s] = -f;
+        }
+        for j in 0..(self.n + 2) {
+            if j != s {
+                self.d[r][j] *= inv;
+            }
+        }
+        self.d[r][s] = inv;
+        std::mem::swap(&mut self.basis[r], &mut self.non_basis[s]);
+    }
+
+    // 有界なら true
+    fn run(&mut self, phase: usize) -> bool {
+        let x = if phase == 1 { self.m + 1 } else { self.m };
+        loop {
+            let mut s = None;
+            for j in 0..=self.n {
+                if phase == 2 && self.non_basis[j] == -1 {
+                    continue;
+                }
+                s = match s {
+                    Some(s)
+                        if (self.d[x][s], self.non_basis[s])
+                            <= (self.d[x][j], self.non_basis[j]) =>
+                    {
+                        Some(s)
+                    }
+                    _ => Some(j),
+                };
+            }
+            let s = s.unwrap();
+            if self.d[x][s] > -EPS {
+                return true;
+            }
+            let mut r: Option<usize> = None;
+            for i in 0..self.m {
+                if self.d[i][s] < EPS {
+                    continue;
+                }
+                r = match r {
+                    Some(r)
+                        if (self.d[r][self.n + 1] / self.d[r][s], self.basis[r])
+                            <= (self.d[i][self.n + 1] / self.d[i][s], self.basis[i]) =>
+                    {
+                        Some(r)
+                    }
+                    _ => Some(i),
+                };
+            }
+            let Some(r) = r else {
+                return false;
+            };
+            self.pivot(r, s);
+        }
+    }
+
+    fn solve(mut self) -> LpSolution {
+        let (m, n) = (self.m, self.n);
+        if let Some(r) = (0..m).min_by(|&i, &j| self.d[i][n + 1].total_cmp(&self.d[j][n + 1])) {
+            if self.d[r][n + 1] < -EPS {
+                // 第一段階: 実行可能解を探す
+                self.pivot(r, n);
+                if !self.run(1) || self.d[m + 1][n + 1] < -EPS {
+                    return LpSolution::Infeasible;
+                }
+                for i in 0..m {
+                    if self.basis[i] == -1 {
+                        let s = (0..=n)
+                            .min_by(|&j, &k| {
+                                (self.d[i][j], self.non_basis[j])
+                                    .partial_cmp(&(self.d[i][k], self.non_basis[k]))
+                                    .unwrap()
+                            })
+                            .unwrap();
+                        self.pivot(i, s);
+                    }
+                }
+            }
+        }
+        if !self.run(2) {
+            return LpSolution::Unbounded;
+        }
+        let mut x = vec![0.0; n];
+        for i in 0..m {
+            if (self.basis[i] as usize) < n {
+                x[self.basis[i] as usize] = self.d[i][n + 1];
+            }
+        }
+        LpSolution::Optimal {
+            value: self.d[m][n + 1],
+            x,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    // 2 変数の問題を、2 本の制約の交点を全て試して解く
+    fn brute_force(a: &[Vec<f64>], b: &[f64], c: &[f64]) -> Option<f64> {
+        let mut lines = a
+            .iter()
+            .zip(b)
+            .map(|(row, &b)| (row[0], row[1], b))
+            .collect::<Vec<_>>();
+        lines.push((-1.0, 0.0, 0.0));
+        lines.push((0.0, -1.0, 0.0));
+        let mut best: Option<f64> = None;
+        for i in 0..lines.len() {
+            for j in (i + 1)..lines.len() {
+                let (p, q, r) = lines[i];
+                let (s, t, u) = lines[j];
+                let det = p * t - q * s;
+                if det.abs() < EPS {
+                    continue;
+                }
+                let x = (r * t - q * u) / det;
+                let y = (p * u - r * s) / det;
+                if lines.iter().all(|&(p, q, r)| p * x + q * y <= r + 1e-7) {
+                    let value = c[0] * x + c[1] * y;
+                    best = Some(best.map_or(value, |b| b.max(value)));
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_random_2d() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let m = rng.gen_range(1, 6);
+            let mut a = (0..m)
+                .map(|_| {
+                    (0..2)
+                        .map(|_| rng.gen_range(-5, 6) as f64)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let mut b = (0..m)
+                .map(|_| rng.gen_range(-5, 11) as f64)
+                .collect::<Vec<_>>();
+            // 有界にする
+            a.push(vec![1.0, 1.0]);
+            b.push(20.0);
+            let c = (0..2)
+                .map(|_| rng.gen_range(-5, 6) as f64)
+                .collect::<Vec<_>>();
+            let expected = brute_force(&a, &b, &c);
+            match simplex(&a, &b, &c) {
+                LpSolution::Optimal { value, x } => {
+                    let expected = expected.unwrap();
+                    assert!((value - expected).abs() < 1e-6, "{} {}", value, expected);
+                    for i in 0..a.len() {
+                        assert!(a[i][0] * x[0] + a[i][1] * x[1] <= b[i] + 1e-6);
+                    }
+                    assert!(x.iter().all(|&x| x >= -1e-6));
+                }
+                LpSolution::Infeasible => assert_eq!(expected, None),
+                LpSolution::Unbounded => unreachable!(),
+            }
+        }
+    }
+}
@@ -11,11 +11,13 @@ use std::collections::VecDeque;
 /// - ...
 /// - `b[n - window_width]`: `min(a[n - window_width], ..., a[n - 2], a[n - 1])`
 ///
+/// `window_width` が `n` より大きい場合は、幅 `window_width` の区間が存在しないので空の `Vec` を返します ([`slice::windows`] と同じです)。
+///
 /// [実装の参考資料](https://qiita.com/kuuso1/items/318d42cd089a49eeb332)
 ///
 /// # Panics
 ///
-/// if `window_width` is zero or is greater than `a.len()`.
+/// if `window_width` is zero.
 ///
 /// # Examples
 ///
@@ -52,39 +54,184 @@ where
     sliding_window(a, window_width, false)
 }
 
+/// [`sliding_window_minimum`](fn.sliding_window_minimum.html) の、`window_width` が `0` または `a.len()` より大きい場合に `None` を返すバージョンです。
+///
+/// # Examples
+///
+/// ```
+/// use sliding_window::checked_sliding_window_minimum;
+///
+/// let a = vec![3, 1, 4, 1, 5];
+/// assert_eq!(checked_sliding_window_minimum(&a, 2), Some(vec![1, 1, 1, 1]));
+/// assert_eq!(checked_sliding_window_minimum(&a, 0), None);
+/// assert_eq!(checked_sliding_window_minimum(&a, 6), None);
+/// ```
+pub fn checked_sliding_window_minimum<T>(a: &[T], window_width: usize) -> Option<Vec<T>>
+where
+    T: Ord + Clone,
+{
+    if 0 < window_width && window_width <= a.len() {
+        Some(sliding_window(a, window_width, true))
+    } else {
+        None
+    }
+}
+
+/// [`checked_sliding_window_minimum`](fn.checked_sliding_window_minimum.html) の最大値バージョンです。
+pub fn checked_sliding_window_maximum<T>(a: &[T], window_width: usize) -> Option<Vec<T>>
+where
+    T: Ord + Clone,
+{
+    if 0 < window_width && window_width <= a.len() {
+        Some(sliding_window(a, window_width, false))
+    } else {
+        None
+    }
+}
+
+/// [`sliding_window_minimum`](fn.sliding_window_minimum.html) の、値の代わりに位置を返すバージョンです。
+///
+/// 最小値が複数あるときは、そのうち最も右の位置を返します。
+///
+/// # Panics
+///
+/// if `window_width` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use sliding_window::sliding_window_minimum_indices;
+///
+/// let a = vec![4, 7, 7, 8, 5, 7, 6, 9, 9, 2, 8, 3];
+/// assert_eq!(
+///     sliding_window_minimum_indices(&a, 6),
+///     vec![0, 4, 4, 4, 9, 9, 9]
+/// );
+/// ```
+pub fn sliding_window_minimum_indices<T>(a: &[T], window_width: usize) -> Vec<usize>
+where
+    T: Ord,
+{
+    SlidingWindow::minimum(a, window_width).collect()
+}
+
+/// [`sliding_window_minimum_indices`](fn.sliding_window_minimum_indices.html) の最大値バージョンです。
+pub fn sliding_window_maximum_indices<T>(a: &[T], window_width: usize) -> Vec<usize>
+where
+    T: Ord,
+{
+    SlidingWindow::maximum(a, window_width).collect()
+}
+
 fn sliding_window<T>(a: &[T], window_width: usize, choose_minimum: bool) -> Vec<T>
 where
     T: Ord + Clone,
 {
-    assert!(0 < window_width && window_width <= a.len());
-    let mut result = Vec::new();
-    let mut arg_min_max_candidates: VecDeque<usize> = VecDeque::new();
-    for (i, v) in a.iter().enumerate() {
-        while !arg_min_max_candidates.is_empty() {
-            let back = arg_min_max_candidates.back().unwrap();
-            if choose_minimum && a[*back].cmp(v) == Less {
-                break;
-            }
-            if !choose_minimum && a[*back].cmp(v) == Greater {
-                break;
-            }
-            arg_min_max_candidates.pop_back();
+    SlidingWindow::new(a, window_width, choose_minimum)
+        .map(|i| Clone::clone(&a[i]))
+        .collect()
+}
+
+/// 幅 `window_width` の区間の最小値 (または最大値) の位置を、左の区間から順に返すイテレータです。
+///
+/// 結果の `Vec` を作らずに 1 つずつ処理できます。
+///
+/// # Panics
+///
+/// if `window_width` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use sliding_window::SlidingWindow;
+///
+/// let a = vec![2, 2, 3, 6, 0, 6, 7, 9];
+/// let sum = SlidingWindow::maximum(&a, 3).map(|i| a[i]).sum::<i32>();
+/// assert_eq!(sum, 3 + 6 + 6 + 6 + 7 + 9);
+/// ```
+pub struct SlidingWindow<'a, T> {
+    a: &'a [T],
+    window_width: usize,
+    choose_minimum: bool,
+    next_index: usize,
+    arg_min_max_candidates: VecDeque<usize>,
+}
+
+impl<'a, T> SlidingWindow<'a, T>
+where
+    T: Ord,
+{
+    /// 最小値の位置を返すイテレータを作ります。
+    pub fn minimum(a: &'a [T], window_width: usize) -> Self {
+        Self::new(a, window_width, true)
+    }
+
+    /// 最大値の位置を返すイテレータを作ります。
+    pub fn maximum(a: &'a [T], window_width: usize) -> Self {
+        Self::new(a, window_width, false)
+    }
+
+    fn new(a: &'a [T], window_width: usize, choose_minimum: bool) -> Self {
+        assert!(0 < window_width);
+        Self {
+            a,
+            window_width,
+            choose_minimum,
+            next_index: 0,
+            arg_min_max_candidates: VecDeque::new(),
         }
-        arg_min_max_candidates.push_back(i);
-        if i >= window_width - 1 {
-            let arg_min_max = arg_min_max_candidates.front().unwrap();
-            result.push(Clone::clone(&a[*arg_min_max]));
-            if *arg_min_max == i - (window_width - 1) {
-                arg_min_max_candidates.pop_front();
+    }
+}
+
+impl<'a, T> Iterator for SlidingWindow<'a, T>
+where
+    T: Ord,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.a;
+        while self.next_index < a.len() {
+            let i = self.next_index;
+            let v = &a[i];
+            self.next_index += 1;
+            while !self.arg_min_max_candidates.is_empty() {
+                let back = self.arg_min_max_candidates.back().unwrap();
+                if self.choose_minimum && a[*back].cmp(v) == Less {
+                    break;
+                }
+                if !self.choose_minimum && a[*back].cmp(v) == Greater {
+                    break;
+                }
+                self.arg_min_max_candidates.pop_back();
+            }
+            self.arg_min_max_candidates.push_back(i);
+            if i >= self.window_width - 1 {
+                let arg_min_max = *self.arg_min_max_candidates.front().unwrap();
+                if arg_min_max == i - (self.window_width - 1) {
+                    self.arg_min_max_candidates.pop_front();
+                }
+                return Some(arg_min_max);
             }
         }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let rest = (self.a.len() + 1).saturating_sub(self.window_width.max(self.next_index + 1));
+        (rest, Some(rest))
     }
-    result
 }
 
+impl<'a, T> ExactSizeIterator for SlidingWindow<'a, T> where T: Ord {}
+
 #[cfg(test)]
 mod tests {
-    use crate::{sliding_window_maximum, sliding_window_minimum};
+    use crate::{
+        checked_sliding_window_maximum, checked_sliding_window_minimum, sliding_window_maximum,
+        sliding_window_maximum_indices, sliding_window_minimum, sliding_window_minimum_indices,
+        SlidingWindow,
+    };
 
     #[test]
     fn test_min() {
@@ -138,6 +285,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_indices() {
+        let a = vec![2, 2, 3, 6, 0, 6, 7, 9, 7, 7, 4, 9];
+        for w in 1..=a.len() {
+            let minimums = sliding_window_minimum_indices(&a, w);
+            let maximums = sliding_window_maximum_indices(&a, w);
+            assert_eq!(minimums.len(), a.len() - w + 1);
+            assert_eq!(maximums.len(), a.len() - w + 1);
+            for l in 0..=(a.len() - w) {
+                assert!(l <= minimums[l] && minimums[l] < l + w);
+                assert!(l <= maximums[l] && maximums[l] < l + w);
+                assert_eq!(a[minimums[l]], *a[l..(l + w)].iter().min().unwrap());
+                assert_eq!(a[maximums[l]], *a[l..(l + w)].iter().max().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_iter_len() {
+        let a = vec![2, 2, 3, 6, 0, 6, 7, 9, 7, 7, 4, 9];
+        for w in 1..=a.len() {
+            let mut iter = SlidingWindow::minimum(&a, w);
+            for rest in (0..=(a.len() - w + 1)).rev() {
+                assert_eq!(iter.len(), rest);
+                iter.next();
+            }
+            assert_eq!(iter.next(), None);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_empty_0() {
@@ -145,8 +322,19 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_empty_1() {
         assert_eq!(sliding_window_minimum::<u32>(&[], 1), vec![]);
     }
+
+    #[test]
+    fn test_too_wide() {
+        let a = vec![2, 2, 3, 6, 0];
+        assert_eq!(sliding_window_minimum(&a, 6), vec![]);
+        assert_eq!(sliding_window_maximum(&a, 100), vec![]);
+        assert_eq!(sliding_window_minimum_indices(&a, 6), vec![]);
+        assert_eq!(SlidingWindow::maximum(&a, 6).len(), 0);
+        assert_eq!(checked_sliding_window_minimum(&a, 6), None);
+        assert_eq!(checked_sliding_window_maximum(&a, 0), None);
+        assert_eq!(checked_sliding_window_maximum(&a, 5), Some(vec![6]));
+    }
 }
@@ -0,0 +1,79 @@
+// problem: https://judge.yosupo.jp/problem/stern_brocot_tree
+use proconio::{fastout, input};
+use stern_brocot::{ancestor, decode_path, encode_path, lca, subtree_range, Direction};
+
+#[fastout]
+fn main() {
+    input! {
+        t: usize,
+    }
+    for _ in 0..t {
+        input! {
+            query: String,
+        }
+        match query.as_str() {
+            "ENCODE_PATH" => {
+                input! {
+                    a: u64,
+                    b: u64,
+                }
+                let path = encode_path(a, b);
+                let mut ans = vec![path.len().to_string()];
+                for (dir, n) in path {
+                    ans.push(match dir {
+                        Direction::Left => "L".to_string(),
+                        Direction::Right => "R".to_string(),
+                    });
+                    ans.push(n.to_string());
+                }
+                println!("{}", ans.join(" "));
+            }
+            "DECODE_PATH" => {
+                input! {
+                    k: usize,
+                    path: [(char, u64); k],
+                }
+                let path = path
+                    .into_iter()
+                    .map(|(c, n)| match c {
+                        'L' => (Direction::Left, n),
+                        'R' => (Direction::Right, n),
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                let (a, b) = decode_path(&path);
+                println!("{} {}", a, b);
+            }
+            "LCA" => {
+                input! {
+                    a: u64,
+                    b: u64,
+                    c: u64,
+                    d: u64,
+                }
+                let (f, g) = lca(a, b, c, d);
+                println!("{} {}", f, g);
+            }
+            "ANCESTOR" => {
+                input! {
+                    k: u64,
+                    a: u64,
+                    b: u64,
+                }
+                match ancestor(a, b, k) {
+                    Some((f, g)) => println!("{} {}", f, g),
+                    None => println!("-1"),
+                }
+            }
+            "RANGE" => {
+                input! {
+                    a: u64,
+                    b: u64,
+                }
+                let ((f, g), (h, k)) = subtree_range(a, b);
+                println!("{} {} {} {}", f, g, h, k);
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use std::cmp::Ordering;
+
+/// Stern–Brocot 木で子へ進む向きです。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+/// 既約分数 `a/b` へ根 `1/1` から進む経路を `(向き, 回数)` の列として返します。
+///
+/// 経路の長さを `k` として O(`k`) 時間です。`k` は O(log max(`a`, `b`)) です。
+///
+/// # Panics
+///
+/// `a` または `b` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::{encode_path, Direction};
+///
+/// // 1/1 -> 1/2 -> 2/3 -> 3/4 -> 5/7
+/// assert_eq!(
+///     encode_path(5, 7),
+///     vec![(Direction::Left, 1), (Direction::Right, 2), (Direction::Left, 1)]
+/// );
+/// assert_eq!(encode_path(1, 1), vec![]);
+/// ```
+pub fn encode_path(a: u64, b: u64) -> Vec<(Direction, u64)> {
+    assert!(a >= 1 && b >= 1);
+    let (mut a, mut b) = (a, b);
+    let mut path = Vec::new();
+    while a != b {
+        if a > b {
+            let k = (a - 1) / b;
+            path.push((Direction::Right, k));
+            a -= k * b;
+        } else {
+            let k = (b - 1) / a;
+            path.push((Direction::Left, k));
+            b -= k * a;
+        }
+    }
+    assert_eq!(a, 1, "a/b must be irreducible");
+    path
+}
+
+/// 根 `1/1` から `path` の通りに進んだ先の分数を返します。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::{decode_path, Direction};
+///
+/// assert_eq!(
+///     decode_path(&[(Direction::Left, 1), (Direction::Right, 2), (Direction::Left, 1)]),
+///     (5, 7)
+/// );
+/// assert_eq!(decode_path(&[]), (1, 1));
+/// ```
+pub fn decode_path(path: &[(Direction, u64)]) -> (u64, u64) {
+    let ((p, q), (r, s)) = bounds(path);
+    (p + r, q + s)
+}
+
+/// `a/b` と `c/d` の最小共通祖先を返します。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::lca;
+///
+/// assert_eq!(lca(5, 7, 3, 4), (3, 4));
+/// assert_eq!(lca(1, 3, 3, 1), (1, 1));
+/// ```
+pub fn lca(a: u64, b: u64, c: u64, d: u64) -> (u64, u64) {
+    let p1 = encode_path(a, b);
+    let p2 = encode_path(c, d);
+    let mut path = Vec::new();
+    for (&(dir1, k1), &(dir2, k2)) in p1.iter().zip(&p2) {
+        if dir1 != dir2 {
+            break;
+        }
+        path.push((dir1, k1.min(k2)));
+        if k1 != k2 {
+            break;
+        }
+    }
+    decode_path(&path)
+}
+
+/// `a/b` の深さ `k` の祖先を返します。根 `1/1` の深さは `0` です。`a/b` の深さが `k` 未満の場合は `None` を返します。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::ancestor;
+///
+/// assert_eq!(ancestor(5, 7, 0), Some((1, 1)));
+/// assert_eq!(ancestor(5, 7, 2), Some((2, 3)));
+/// assert_eq!(ancestor(5, 7, 4), Some((5, 7)));
+/// assert_eq!(ancestor(5, 7, 5), None);
+/// ```
+pub fn ancestor(a: u64, b: u64, k: u64) -> Option<(u64, u64)> {
+    let mut rest = k;
+    let mut path = Vec::new();
+    for (dir, cnt) in encode_path(a, b) {
+        if rest == 0 {
+            break;
+        }
+        path.push((dir, cnt.min(rest)));
+        rest -= cnt.min(rest);
+    }
+    if rest > 0 {
+        return None;
+    }
+    Some(decode_path(&path))
+}
+
+/// `a/b` を根とする部分木に含まれる分数全体が成す開区間 `(p/q, r/s)` を `((p, q), (r, s))` として返します。
+///
+/// 右端が無限大のときは `(r, s) = (1, 0)` です。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::subtree_range;
+///
+/// assert_eq!(subtree_range(5, 7), ((2, 3), (3, 4)));
+/// assert_eq!(subtree_range(1, 1), ((0, 1), (1, 0)));
+/// ```
+pub fn subtree_range(a: u64, b: u64) -> ((u64, u64), (u64, u64)) {
+    bounds(&encode_path(a, b))
+}
+
+fn bounds(path: &[(Direction, u64)]) -> ((u64, u64), (u64, u64)) {
+    let (mut lower, mut upper) = ((0, 1), (1, 0));
+    for &(dir, k) in path {
+        match dir {
+            Direction::Left => {
+                upper = (upper.0 + lower.0 * k, upper.1 + lower.1 * k);
+            }
+            Direction::Right => {
+                lower = (lower.0 + upper.0 * k, lower.1 + upper.1 * k);
+            }
+        }
+    }
+    (lower, upper)
+}
+
+/// `p/q` の連分数展開 `[a_0; a_1, ..., a_k]` を返します。
+///
+/// # Panics
+///
+/// `q` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::continued_fraction;
+///
+/// // 415/93 = 4 + 1/(2 + 1/(6 + 1/7))
+/// assert_eq!(continued_fraction(415, 93), vec![4, 2, 6, 7]);
+/// assert_eq!(continued_fraction(0, 5), vec![0]);
+/// ```
+pub fn continued_fraction(p: u64, q: u64) -> Vec<u64> {
+    assert!(q >= 1);
+    let (mut p, mut q) = (p, q);
+    let mut result = Vec::new();
+    while q != 0 {
+        result.push(p / q);
+        (p, q) = (q, p % q);
+    }
+    result
+}
+
+/// 分母が `n` 以下の分数のうち `p/q` 以下で最大のものと `p/q` 以上で最小のものを `((a, b), (c, d))` として返します。
+///
+/// `p/q` の既約分数の分母が `n` 以下であれば `(a, b) = (c, d)` で、`a/b` は `p/q` を約分したものです。
+///
+/// Stern–Brocot 木を同じ向きにまとめて降りるので O(log max(`p`, `q`)) 時間です。
+///
+/// # Panics
+///
+/// `q` または `n` が `0` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use stern_brocot::best_rational_approximation;
+///
+/// // π ≒ 3.14159265
+/// assert_eq!(
+///     best_rational_approximation(314159265, 100000000, 10),
+///     ((25, 8), (22, 7)) // 3.125, 3.142857
+/// );
+/// assert_eq!(
+///     best_rational_approximation(314159265, 100000000, 200),
+///     ((333, 106), (355, 113))
+/// );
+/// assert_eq!(best_rational_approximation(6, 4, 2), ((3, 2), (3, 2)));
+/// ```
+pub fn best_rational_approximation(p: u64, q: u64, n: u64) -> ((u64, u64), (u64, u64)) {
+    assert!(q >= 1);
+    assert!(n >= 1);
+    if p == 0 {
+        return ((0, 1), (0, 1));
+    }
+    let (p, q) = (u128::from(p), u128::from(q));
+    let n = u128::from(n);
+    let (mut lower, mut upper): ((u128, u128), (u128, u128)) = ((0, 1), (1, 0));
+    loop {
+        let m = (lower.0 + upper.0, lower.1 + upper.1);
+        if m.1 > n {
+            break;
+        }
+        // lower < p/q < upper を保つ
+        // lower_gap = p/q - lower, upper_gap = upper - p/q (を q * 分母 倍したもの)
+        let lower_gap = p * lower.1 - lower.0 * q;
+        let upper_gap = upper.0 * q - p * upper.1;
+        match (m.0 * q).cmp(&(p * m.1)) {
+            Ordering::Equal => {
+                lower = m;
+                upper = m;
+                break;
+            }
+            Ordering::Less => {
+                // lower + k * upper <= p/q
+                let mut k = lower_gap / upper_gap;
+                if upper.1 > 0 {
+                    k = k.min((n - lower.1) / upper.1);
+                }
+                lower = (lower.0 + upper.0 * k, lower.1 + upper.1 * k);
+                if lower_gap == upper_gap * k {
+                    upper = lower;
+                    break;
+                }
+            }
+            Ordering::Greater => {
+                // upper + k * lower >= p/q
+                let k = (upper_gap / lower_gap).min((n - upper.1) / lower.1);
+                upper = (upper.0 + lower.0 * k, upper.1 + lower.1 * k);
+                if upper_gap == lower_gap * k {
+                    lower = upper;
+                    break;
+                }
+            }
+        }
+    }
+    (
+        (lower.0 as u64, lower.1 as u64),
+        (upper.0 as u64, upper.1 as u64),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        for a in 1..=50 {
+            for b in 1..=50 {
+                if gcd(a, b) != 1 {
+                    continue;
+                }
+                let path = encode_path(a, b);
+                assert_eq!(decode_path(&path), (a, b));
+                let ((p, q), (r, s)) = subtree_range(a, b);
+                // p/q < a/b < r/s
+                assert!(p * b < a * q);
+                assert!(a * s < r * b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lca() {
+        let depth = |a: u64, b: u64| encode_path(a, b).iter().map(|&(_, k)| k).sum::<u64>();
+        for a in 1..=20 {
+            for b in 1..=20 {
+                if gcd(a, b) != 1 {
+                    continue;
+                }
+                for c in 1..=20 {
+                    for d in 1..=20 {
+                        if gcd(c, d) != 1 {
+                            continue;
+                        }
+                        // 深さが最大の共通祖先
+                        let max_depth = depth(a, b).min(depth(c, d));
+                        let expected = (0..=max_depth)
+                            .rev()
+                            .find_map(|k| {
+                                let x = ancestor(a, b, k).unwrap();
+                                (Some(x) == ancestor(c, d, k)).then_some(x)
+                            })
+                            .unwrap();
+                        assert_eq!(lca(a, b, c, d), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_best_rational_approximation() {
+        for p in 0..=30 {
+            for q in 1..=30 {
+                for n in 1..=30 {
+                    let (lo, hi) = best_rational_approximation(p, q, n);
+                    // 分母 n 以下の分数を全探索
+                    let mut expected_lo = (0, 1);
+                    let mut expected_hi = (1, 0);
+                    for b in 1..=n {
+                        // p/q 以下で最大の a/b
+                        let a = p * b / q;
+                        if a * expected_lo.1 > expected_lo.0 * b {
+                            expected_lo = (a, b);
+                        }
+                        // p/q 以上で最小の a/b
+                        let a = (p * b + q - 1) / q;
+                        if a * expected_hi.1 < expected_hi.0 * b {
+                            expected_hi = (a, b);
+                        }
+                    }
+                    let g = gcd(expected_lo.0, expected_lo.1);
+                    assert_eq!(lo, (expected_lo.0 / g, expected_lo.1 / g));
+                    let g = gcd(expected_hi.0, expected_hi.1);
+                    assert_eq!(hi, (expected_hi.0 / g, expected_hi.1 / g));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use factorials::ModIntFactorial;
+use mod_int::ModInt;
+
+/// 0 以上 `n` 未満の全ての `i`, `j` について (符号なし) 第一種スターリング数 `c(i, j)` を O(`n^2`) 時間で求めます。
+///
+/// `c(i, j)` は `i` 要素の置換のうち巡回置換 `j` 個に分解されるものの個数です。
+///
+/// # Examples
+///
+/// ```
+/// use stirling_numbers::stirling_first_table;
+///
+/// let c = stirling_first_table::<1_000_000_007>(5);
+/// assert_eq!(c[4].iter().map(|x| x.val()).collect::<Vec<_>>(), vec![0, 6, 11, 6, 1]);
+/// ```
+pub fn stirling_first_table<const M: i64>(n: usize) -> Vec<Vec<ModInt<M>>> {
+    let mut c = vec![vec![ModInt::new(0); n]; n];
+    if n == 0 {
+        return c;
+    }
+    c[0][0] = ModInt::new(1);
+    for i in 1..n {
+        for j in 1..=i {
+            // c(i, j) = c(i - 1, j - 1) + (i - 1) * c(i - 1, j)
+            c[i][j] = c[i - 1][j - 1] + c[i - 1][j] * (i - 1);
+        }
+    }
+    c
+}
+
+/// 0 以上 `n` 未満の全ての `i`, `j` について第二種スターリング数 `S(i, j)` を O(`n^2`) 時間で求めます。
+///
+/// `S(i, j)` は `i` 要素の集合を `j` 個の空でない部分集合に分割する方法の数です。
+///
+/// # Examples
+///
+/// ```
+/// use stirling_numbers::stirling_second_table;
+///
+/// let s = stirling_second_table::<1_000_000_007>(5);
+/// assert_eq!(s[4].iter().map(|x| x.val()).collect::<Vec<_>>(), vec![0, 1, 7, 6, 1]);
+/// ```
+pub fn stirling_second_table<const M: i64>(n: usize) -> Vec<Vec<ModInt<M>>> {
+    let mut s = vec![vec![ModInt::new(0); n]; n];
+    if n == 0 {
+        return s;
+    }
+    s[0][0] = ModInt::new(1);
+    for i in 1..n {
+        for j in 1..=i {
+            // S(i, j) = S(i - 1, j - 1) + j * S(i - 1, j)
+            s[i][j] = s[i - 1][j - 1] + s[i - 1][j] * j;
+        }
+    }
+    s
+}
+
+/// 第一種スターリング数 `c(n, 0), c(n, 1), ..., c(n, n)` を `x(x + 1)...(x + n - 1)` の係数として O(`n^2`) 時間で求めます。
+///
+/// # Examples
+///
+/// ```
+/// use stirling_numbers::stirling_first_row;
+///
+/// let c = stirling_first_row::<1_000_000_007>(4);
+/// assert_eq!(c.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![0, 6, 11, 6, 1]);
+/// ```
+pub fn stirling_first_row<const M: i64>(n: usize) -> Vec<ModInt<M>> {
+    let mut coef = vec![ModInt::new(0); n + 1];
+    coef[0] = ModInt::new(1);
+    for i in 0..n {
+        // (x + i) を掛ける
+        for j in (0..=(i + 1)).rev() {
+            let shifted = if j >= 1 { coef[j - 1] } else { ModInt::new(0) };
+            coef[j] = shifted + coef[j] * i;
+        }
+    }
+    coef
+}
+
+/// 第二種スターリング数 `S(n, 0), S(n, 1), ..., S(n, n)` を O(`n^2`) 時間で求めます。
+///
+/// `S(n, k) = Σ_{i=0}^{k} (-1)^(k-i) * i^n / (i! * (k-i)!)` を畳み込みとして計算します。`f` は `n + 1` 個以上の階乗を前計算したものを渡してください。
+///
+/// # Examples
+///
+/// ```
+/// use factorials::ModIntFactorial;
+/// use stirling_numbers::stirling_second_row;
+///
+/// let f = ModIntFactorial::<1_000_000_007>::new(10);
+/// let s = stirling_second_row(4, &f);
+/// assert_eq!(s.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![0, 1, 7, 6, 1]);
+/// ```
+pub fn stirling_second_row<const M: i64>(n: usize, f: &ModIntFactorial<M>) -> Vec<ModInt<M>> {
+    let a = (0..=n)
+        .map(|i| ModInt::new(i as i64).pow(n as u32) * f.inversion(i))
+        .collect::<Vec<_>>();
+    let b = (0..=n)
+        .map(|j| {
+            if j % 2 == 0 {
+                f.inversion(j)
+            } else {
+                ModInt::new(0) - f.inversion(j)
+            }
+        })
+        .collect::<Vec<_>>();
+    (0..=n)
+        .map(|k| (0..=k).fold(ModInt::new(0), |acc, i| acc + a[i] * b[k - i]))
+        .collect()
+}
+
+/// 第二種スターリング数 `S(n, k)` を O(`k` log `n`) 時間で求めます。`f` は `k + 1` 個以上の階乗を前計算したものを渡してください。
+///
+/// # Examples
+///
+/// ```
+/// use factorials::ModIntFactorial;
+/// use stirling_numbers::stirling_second;
+///
+/// let f = ModIntFactorial::<1_000_000_007>::new(10);
+/// assert_eq!(stirling_second(4, 2, &f).val(), 7);
+/// assert_eq!(stirling_second(1_000_000_000, 1, &f).val(), 1);
+/// assert_eq!(stirling_second(3, 5, &f).val(), 0);
+/// ```
+pub fn stirling_second<const M: i64>(n: u64, k: usize, f: &ModIntFactorial<M>) -> ModInt<M> {
+    if n < k as u64 {
+        return ModInt::new(0);
+    }
+    let mut sum = ModInt::new(0);
+    for i in 0..=k {
+        // (-1)^(k-i) * C(k, i) * i^n
+        let term = f.binomial(k, i) * pow(ModInt::new(i as i64), n);
+        if (k - i) % 2 == 0 {
+            sum += term;
+        } else {
+            sum -= term;
+        }
+    }
+    sum * f.inversion(k)
+}
+
+/// ベル数 `B(0), B(1), ..., B(n - 1)` をベル三角形を使って O(`n^2`) 時間で求めます。
+///
+/// `B(i)` は `i` 要素の集合を空でない部分集合に分割する方法の数です。
+///
+/// # Examples
+///
+/// ```
+/// use stirling_numbers::bell_numbers;
+///
+/// let b = bell_numbers::<1_000_000_007>(7);
+/// assert_eq!(b.iter().map(|x| x.val()).collect::<Vec<_>>(), vec![1, 1, 2, 5, 15, 52, 203]);
+/// ```
+pub fn bell_numbers<const M: i64>(n: usize) -> Vec<ModInt<M>> {
+    let mut result = Vec::with_capacity(n);
+    if n == 0 {
+        return result;
+    }
+    let mut row = vec![ModInt::new(1)];
+    result.push(ModInt::new(1));
+    for _ in 1..n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(*row.last().unwrap());
+        for &x in &row {
+            next.push(*next.last().unwrap() + x);
+        }
+        result.push(next[0]);
+        row = next;
+    }
+    result
+}
+
+fn pow<const M: i64>(x: ModInt<M>, exp: u64) -> ModInt<M> {
+    let mut result = ModInt::new(1);
+    let mut base = x;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: i64 = 1_000_000_007;
+
+    #[test]
+    fn test_first() {
+        let table = stirling_first_table::<P>(20);
+        for (n, expected) in table.iter().enumerate() {
+            let row = stirling_first_row::<P>(n);
+            for (e, x) in expected.iter().zip(&row) {
+                assert_eq!(e.val(), x.val());
+            }
+            // Σ_k c(n, k) = n!
+            let sum = row.iter().fold(0, |acc, x| (acc + x.val()) % P);
+            let fac = (1..=n as i64).fold(1, |acc, i| acc * i % P);
+            assert_eq!(sum, fac);
+        }
+    }
+
+    #[test]
+    fn test_second() {
+        let f = ModIntFactorial::<P>::new(30);
+        let table = stirling_second_table::<P>(20);
+        for (n, expected) in table.iter().enumerate() {
+            let row = stirling_second_row(n, &f);
+            for (k, (e, x)) in expected.iter().zip(&row).enumerate() {
+                assert_eq!(e.val(), x.val());
+                assert_eq!(e.val(), stirling_second(n as u64, k, &f).val());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bell() {
+        let table = stirling_second_table::<P>(20);
+        let bell = bell_numbers::<P>(20);
+        for n in 0..20 {
+            let sum = table[n].iter().fold(0, |acc, x| (acc + x.val()) % P);
+            assert_eq!(bell[n].val(), sum);
+        }
+    }
+}
@@ -1,4 +1,6 @@
 // problem: https://judge.yosupo.jp/problem/scc
+// tags: graph
+// checker_cpp: https://raw.githubusercontent.com/yosupo06/library-checker-problems/master/graph/scc/checker.cpp
 // judge_program_rs: ./judge_scc.rs
 
 use join::Join;
@@ -0,0 +1,111 @@
+use bitset::BitSet;
+
+/// `weights` からいくつか (0 個でもよい) を選んだ和として `0, 1, ..., cap` のそれぞれが作れるかを求めます。
+///
+/// 返り値は長さ `cap + 1` の [`BitSet`] で、`s` ビット目が和 `s` を作れるかどうかです。ビット並列化した DP で O(`n * cap / 64`) 時間です。
+///
+/// # Examples
+/// ```
+/// use subset_sum::subset_sum_possible;
+///
+/// let dp = subset_sum_possible(&[3, 5, 9], 10);
+/// assert_eq!(dp.ones().collect::<Vec<_>>(), vec![0, 3, 5, 8, 9]);
+/// ```
+pub fn subset_sum_possible(weights: &[usize], cap: usize) -> BitSet {
+    let mut dp = BitSet::new(cap + 1);
+    dp.set(0, true);
+    for &w in weights {
+        if w <= cap {
+            let shifted = &dp << w;
+            dp |= &shifted;
+        }
+    }
+    dp
+}
+
+/// 重さ `w` の品物が `c` 個ずつある (`items` の要素が `(w, c)`) とき、いくつか選んだ重さの和として `0, 1, ..., cap` のそれぞれが作れるかを求めます。
+///
+/// 個数を `1, 2, 4, ...` に二進分解するので、O(`cap / 64 * Σ log c`) 時間です。
+///
+/// # Examples
+/// ```
+/// use subset_sum::bounded_subset_sum_possible;
+///
+/// // 重さ 3 が 2 個、重さ 4 が 1 個
+/// let dp = bounded_subset_sum_possible(&[(3, 2), (4, 1)], 20);
+/// assert_eq!(dp.ones().collect::<Vec<_>>(), vec![0, 3, 4, 6, 7, 10]);
+/// ```
+pub fn bounded_subset_sum_possible(items: &[(usize, usize)], cap: usize) -> BitSet {
+    let mut weights = Vec::new();
+    for &(w, c) in items {
+        // 重さ 0 の品物は和を変えない
+        if w == 0 {
+            continue;
+        }
+        // cap / w 個より多くは選べない
+        let mut c = c.min(cap / w);
+        let mut k = 1;
+        while c > 0 {
+            let t = k.min(c);
+            // 重さ w * t の品物 1 個とみなす
+            weights.push(w * t);
+            c -= t;
+            k *= 2;
+        }
+    }
+    subset_sum_possible(&weights, cap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_subset_sum_possible() {
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(0, 10);
+            let weights = (0..n).map(|_| rng.gen_range(0, 100)).collect::<Vec<_>>();
+            let cap = rng.gen_range(0, 300);
+            let dp = subset_sum_possible(&weights, cap);
+            assert_eq!(dp.len(), cap + 1);
+            let mut expected = vec![false; cap + 1];
+            for bits in 0..(1 << n) {
+                let s = (0..n)
+                    .filter(|&i| bits >> i & 1 == 1)
+                    .map(|i| weights[i])
+                    .sum::<usize>();
+                if s <= cap {
+                    expected[s] = true;
+                }
+            }
+            assert_eq!(dp, expected.into_iter().collect::<BitSet>());
+        }
+    }
+
+    #[test]
+    fn test_bounded_subset_sum_possible() {
+        let mut rng = thread_rng();
+        for _ in 0..200 {
+            let n = rng.gen_range(0, 4);
+            let items = (0..n)
+                .map(|_| (rng.gen_range(0, 30), rng.gen_range(0, 8)))
+                .collect::<Vec<_>>();
+            let cap = rng.gen_range(0, 200);
+            let dp = bounded_subset_sum_possible(&items, cap);
+            // 品物を 1 個ずつに分ける
+            let weights = items
+                .iter()
+                .flat_map(|&(w, c)| std::iter::repeat(w).take(c))
+                .collect::<Vec<_>>();
+            assert_eq!(dp, subset_sum_possible(&weights, cap));
+        }
+    }
+
+    #[test]
+    fn test_bounded_subset_sum_possible_huge_count() {
+        let dp = bounded_subset_sum_possible(&[(3, usize::MAX), (0, usize::MAX)], 10);
+        assert_eq!(dp.ones().collect::<Vec<_>>(), vec![0, 3, 6, 9]);
+    }
+}
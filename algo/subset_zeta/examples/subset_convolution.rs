@@ -0,0 +1,18 @@
+// problem: https://judge.yosupo.jp/problem/subset_convolution
+use join::Join;
+use mod_int::ModInt998244353;
+use proconio::{fastout, input};
+use subset_zeta::subset_convolution;
+
+#[fastout]
+fn main() {
+    input! {
+        n: usize,
+        a: [u32; 1 << n],
+        b: [u32; 1 << n],
+    }
+    let a = a.into_iter().map(ModInt998244353::from).collect::<Vec<_>>();
+    let b = b.into_iter().map(ModInt998244353::from).collect::<Vec<_>>();
+    let c = subset_convolution(&a, &b, ModInt998244353::new(0));
+    println!("{}", c.iter().join(" "));
+}
@@ -0,0 +1,228 @@
+use std::ops::{AddAssign, Mul, SubAssign};
+
+/// 部分集合についてのゼータ変換です。全ての `s` について `a[s]` を `Σ_{t ⊆ s} a[t]` で置き換えます。
+///
+/// `a.len()` を `2^n` として O(`n 2^n`) 時間です。
+///
+/// # Panics
+///
+/// `a.len()` が 2 べきでない場合パニックです。
+///
+/// # Examples
+/// ```
+/// use subset_zeta::subset_zeta;
+///
+/// let mut a = vec![1, 2, 3, 4];
+/// subset_zeta(&mut a);
+/// // a[0b11] = a[0b00] + a[0b01] + a[0b10] + a[0b11]
+/// assert_eq!(a, vec![1, 1 + 2, 1 + 3, 1 + 2 + 3 + 4]);
+/// ```
+pub fn subset_zeta<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    assert!(a.len().is_power_of_two());
+    let n = a.len();
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit != 0 {
+                let x = a[s ^ bit];
+                a[s] += x;
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// [`subset_zeta`] の逆変換です。
+///
+/// # Examples
+/// ```
+/// use subset_zeta::subset_mobius;
+///
+/// let mut a = vec![1, 3, 4, 10];
+/// subset_mobius(&mut a);
+/// assert_eq!(a, vec![1, 2, 3, 4]);
+/// ```
+///
+/// [`subset_zeta`]: fn.subset_zeta.html
+pub fn subset_mobius<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    assert!(a.len().is_power_of_two());
+    let n = a.len();
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit != 0 {
+                let x = a[s ^ bit];
+                a[s] -= x;
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// 上位集合についてのゼータ変換です。全ての `s` について `a[s]` を `Σ_{s ⊆ t} a[t]` で置き換えます。
+///
+/// # Examples
+/// ```
+/// use subset_zeta::superset_zeta;
+///
+/// let mut a = vec![1, 2, 3, 4];
+/// superset_zeta(&mut a);
+/// assert_eq!(a, vec![1 + 2 + 3 + 4, 2 + 4, 3 + 4, 4]);
+/// ```
+pub fn superset_zeta<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    assert!(a.len().is_power_of_two());
+    let n = a.len();
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit == 0 {
+                let x = a[s | bit];
+                a[s] += x;
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// [`superset_zeta`] の逆変換です。
+///
+/// [`superset_zeta`]: fn.superset_zeta.html
+pub fn superset_mobius<T>(a: &mut [T])
+where
+    T: Copy + AddAssign + SubAssign,
+{
+    assert!(a.len().is_power_of_two());
+    let n = a.len();
+    let mut bit = 1;
+    while bit < n {
+        for s in 0..n {
+            if s & bit == 0 {
+                let x = a[s | bit];
+                a[s] -= x;
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+/// 部分集合畳み込み `c[s] = Σ_{t ⊆ s} a[t] * b[s \ t]` を O(`n^2 2^n`) 時間で求めます。`zero` は加法の単位元です。
+///
+/// # Panics
+///
+/// `a.len()` が 2 べきでない場合、または `a.len()` と `b.len()` が異なる場合パニックです。
+///
+/// # Examples
+/// ```
+/// use subset_zeta::subset_convolution;
+///
+/// let a = vec![1, 2, 3, 4];
+/// let b = vec![5, 6, 7, 8];
+/// let c = subset_convolution(&a, &b, 0);
+/// assert_eq!(
+///     c,
+///     vec![
+///         1 * 5,
+///         1 * 6 + 2 * 5,
+///         1 * 7 + 3 * 5,
+///         1 * 8 + 2 * 7 + 3 * 6 + 4 * 5,
+///     ]
+/// );
+/// ```
+pub fn subset_convolution<T>(a: &[T], b: &[T], zero: T) -> Vec<T>
+where
+    T: Copy + AddAssign + SubAssign + Mul<Output = T>,
+{
+    assert!(a.len().is_power_of_two());
+    assert_eq!(a.len(), b.len());
+    let len = a.len();
+    let n = len.trailing_zeros() as usize;
+    let ranked = |a: &[T]| {
+        let mut f = vec![vec![zero; len]; n + 1];
+        for (s, &x) in a.iter().enumerate() {
+            f[s.count_ones() as usize][s] = x;
+        }
+        for f in &mut f {
+            subset_zeta(f);
+        }
+        f
+    };
+    let fa = ranked(a);
+    let fb = ranked(b);
+    let mut h = vec![vec![zero; len]; n + 1];
+    for k in 0..=n {
+        for i in 0..=k {
+            for s in 0..len {
+                h[k][s] += fa[i][s] * fb[k - i][s];
+            }
+        }
+        subset_mobius(&mut h[k]);
+    }
+    (0..len).map(|s| h[s.count_ones() as usize][s]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(len: usize, seed: i64) -> Vec<i64> {
+        (0..len as i64).map(|i| (i * 31 + seed) % 11 - 5).collect()
+    }
+
+    #[test]
+    fn test_subset() {
+        for n in 0..=6 {
+            let len = 1 << n;
+            let a = sequence(len, 3);
+            let mut b = a.clone();
+            subset_zeta(&mut b);
+            for (s, &x) in b.iter().enumerate() {
+                let expected = (0..len).filter(|&t| t & s == t).map(|t| a[t]).sum::<i64>();
+                assert_eq!(x, expected);
+            }
+            subset_mobius(&mut b);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_superset() {
+        for n in 0..=6 {
+            let len = 1 << n;
+            let a = sequence(len, 7);
+            let mut b = a.clone();
+            superset_zeta(&mut b);
+            for (s, &x) in b.iter().enumerate() {
+                let expected = (0..len).filter(|&t| t & s == s).map(|t| a[t]).sum::<i64>();
+                assert_eq!(x, expected);
+            }
+            superset_mobius(&mut b);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_subset_convolution() {
+        for n in 0..=6 {
+            let len = 1 << n;
+            let a = sequence(len, 1);
+            let b = sequence(len, 2);
+            let c = subset_convolution(&a, &b, 0);
+            for s in 0..len {
+                let expected = (0..len)
+                    .filter(|&t| t & s == t)
+                    .map(|t| a[t] * b[s ^ t])
+                    .sum::<i64>();
+                assert_eq!(c[s], expected);
+            }
+        }
+    }
+}
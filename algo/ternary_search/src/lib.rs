@@ -0,0 +1,132 @@
+/// `lo` 以上 `hi` 以下の整数 `x` のうち `f(x)` を最小にするものを返します。
+///
+/// `f` は `[lo, hi]` で単峰 (ある `m` まで狭義単調減少し、`m` から狭義単調増加する) である必要があります。`f` を O(log(`hi` - `lo`)) 回呼びます。
+///
+/// # Panics
+///
+/// `lo > hi` の場合パニックです。
+///
+/// # Examples
+/// ```
+/// use ternary_search::ternary_search_int;
+///
+/// assert_eq!(ternary_search_int(-100, 100, |x| (x - 7) * (x - 7)), 7);
+/// assert_eq!(ternary_search_int(0, 10, |x| x), 0);
+/// assert_eq!(ternary_search_int(0, 10, |x| -x), 10);
+/// ```
+pub fn ternary_search_int<T, F>(lo: i64, hi: i64, mut f: F) -> i64
+where
+    T: PartialOrd,
+    F: FnMut(i64) -> T,
+{
+    assert!(lo <= hi);
+    let (mut lo, mut hi) = (lo, hi);
+    // 最小値を取る x は [lo, hi] にある
+    // hi - lo は i64 に収まらないことがあるので u64 で数える
+    let width = |lo: i64, hi: i64| hi.wrapping_sub(lo) as u64;
+    while width(lo, hi) > 2 {
+        let third = (width(lo, hi) / 3) as i64;
+        let m1 = lo.wrapping_add(third);
+        let m2 = hi.wrapping_sub(third);
+        if f(m1) < f(m2) {
+            hi = m2 - 1;
+        } else {
+            lo = m1 + 1;
+        }
+    }
+    let mut best = lo;
+    let mut best_value = f(lo);
+    for x in (lo..=hi).skip(1) {
+        let value = f(x);
+        if value < best_value {
+            best = x;
+            best_value = value;
+        }
+    }
+    best
+}
+
+/// 区間 `[lo, hi]` で単峰な関数 `f` を最小にする `x` を黄金分割探索で求めます。
+///
+/// 反復ごとに区間の長さが約 0.618 倍になり、`f` は反復ごとに 1 回だけ呼ばれます。`iters` 回反復します。
+///
+/// # Examples
+/// ```
+/// use ternary_search::golden_section;
+///
+/// let x = golden_section(-10.0, 10.0, |x| (x - 1.5) * (x - 1.5), 100);
+/// assert!((x - 1.5).abs() < 1e-9);
+/// ```
+pub fn golden_section<F>(lo: f64, hi: f64, mut f: F, iters: usize) -> f64
+where
+    F: FnMut(f64) -> f64,
+{
+    // 1 / φ
+    let r = (5_f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (lo, hi);
+    let mut m1 = hi - (hi - lo) * r;
+    let mut m2 = lo + (hi - lo) * r;
+    let mut f1 = f(m1);
+    let mut f2 = f(m2);
+    for _ in 0..iters {
+        if f1 < f2 {
+            hi = m2;
+            m2 = m1;
+            f2 = f1;
+            m1 = hi - (hi - lo) * r;
+            f1 = f(m1);
+        } else {
+            lo = m1;
+            m1 = m2;
+            f1 = f2;
+            m2 = lo + (hi - lo) * r;
+            f2 = f(m2);
+        }
+    }
+    (lo + hi) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ternary_search_int() {
+        for lo in -10..=10 {
+            for hi in lo..=10 {
+                for m in lo..=hi {
+                    // 狭義単峰
+                    let f = |x: i64| (x - m).abs() * 3 + if x > m { 1 } else { 0 };
+                    assert_eq!(ternary_search_int(lo, hi, f), m);
+                }
+            }
+        }
+        assert_eq!(
+            ternary_search_int(i64::MIN / 2, i64::MAX / 2, |x| (x as i128 - 12345).abs()),
+            12345
+        );
+        assert_eq!(ternary_search_int(i64::MAX, i64::MAX, |x| x), i64::MAX);
+        for m in [i64::MIN, -1, 0, i64::MAX] {
+            assert_eq!(
+                ternary_search_int(i64::MIN, i64::MAX, |x| (x as i128 - m as i128).abs()),
+                m
+            );
+        }
+    }
+
+    #[test]
+    fn test_golden_section() {
+        let mut count = 0;
+        let x = golden_section(
+            0.0,
+            std::f64::consts::PI * 2.0,
+            |x| {
+                count += 1;
+                x.sin()
+            },
+            100,
+        );
+        assert!((x - std::f64::consts::PI * 1.5).abs() < 1e-6);
+        assert_eq!(count, 102);
+    }
+}
@@ -0,0 +1,34 @@
+// problem: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_3_A
+
+use proconio::input;
+
+use two_pointers::two_pointers;
+
+fn main() {
+    input! {
+        n: usize,
+        s: u64,
+        a: [u64; n],
+    }
+    // 和が s 未満である極大な区間
+    let ends = two_pointers(
+        &mut 0,
+        n,
+        |sum, r| {
+            if *sum + a[r] < s {
+                *sum += a[r];
+                true
+            } else {
+                false
+            }
+        },
+        |sum, l| *sum -= a[l],
+    );
+    // [l, ends[l]] の和が s 以上
+    let ans = (0..n)
+        .filter(|&l| ends[l] < n)
+        .map(|l| ends[l] + 1 - l)
+        .min()
+        .unwrap_or(0);
+    println!("{}", ans);
+}
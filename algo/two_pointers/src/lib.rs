@@ -0,0 +1,125 @@
+/// しゃくとり法です。各 `l` について、区間 `[l, r)` が条件を満たす最大の `r` を求めます。
+///
+/// 条件は単調である、つまり区間 `[l, r)` が条件を満たすならそれに含まれる区間も条件を満たすとします。区間の状態を `state` で管理し、次の 2 つの関数で更新します。
+///
+/// - `try_extend(state, r)`: 区間に `r` を加えても条件を満たすなら、`r` を加えて `true` を返す。満たさないなら `state` を変えずに `false` を返す
+/// - `shrink(state, l)`: 区間から `l` を取り除く
+///
+/// 返り値の `ends` は長さ `n` で、`ends[l]` が `[l, r)` が条件を満たす最大の `r` です。空の区間は常に条件を満たすとみなすので `ends[l] >= l` です。
+///
+/// `try_extend` と `shrink` はそれぞれ O(`n`) 回呼ばれます。
+///
+/// # Examples
+/// ```
+/// use two_pointers::two_pointers;
+///
+/// // 和が 10 以下の区間
+/// let a = vec![3, 4, 5, 1, 2, 9, 11];
+/// let ends = two_pointers(
+///     &mut 0,
+///     a.len(),
+///     |sum, r| {
+///         if *sum + a[r] <= 10 {
+///             *sum += a[r];
+///             true
+///         } else {
+///             false
+///         }
+///     },
+///     |sum, l| *sum -= a[l],
+/// );
+/// assert_eq!(ends, vec![2, 4, 5, 5, 5, 6, 6]);
+/// ```
+pub fn two_pointers<S, E, F>(
+    state: &mut S,
+    n: usize,
+    mut try_extend: E,
+    mut shrink: F,
+) -> Vec<usize>
+where
+    E: FnMut(&mut S, usize) -> bool,
+    F: FnMut(&mut S, usize),
+{
+    let mut ends = Vec::with_capacity(n);
+    let mut r = 0;
+    for l in 0..n {
+        if r < l {
+            // [l - 1, r) が空だった
+            r = l;
+        }
+        while r < n && try_extend(state, r) {
+            r += 1;
+        }
+        ends.push(r);
+        if r > l {
+            shrink(state, l);
+        }
+    }
+    ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_sum() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 20);
+            let a = (0..n).map(|_| rng.gen_range(0, 10)).collect::<Vec<u32>>();
+            let k = rng.gen_range(0, 30);
+            let ends = two_pointers(
+                &mut 0,
+                n,
+                |sum, r| {
+                    if *sum + a[r] <= k {
+                        *sum += a[r];
+                        true
+                    } else {
+                        false
+                    }
+                },
+                |sum, l| *sum -= a[l],
+            );
+            for l in 0..n {
+                let expected = (l..=n)
+                    .take_while(|&r| a[l..r].iter().sum::<u32>() <= k)
+                    .last()
+                    .unwrap();
+                assert_eq!(ends[l], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_distinct() {
+        let mut rng = thread_rng();
+        for _ in 0..1000 {
+            let n = rng.gen_range(0, 20);
+            let a = (0..n).map(|_| rng.gen_range(0, 5)).collect::<Vec<usize>>();
+            // 値がすべて異なる区間
+            let ends = two_pointers(
+                &mut vec![false; 5],
+                n,
+                |seen, r| {
+                    if seen[a[r]] {
+                        false
+                    } else {
+                        seen[a[r]] = true;
+                        true
+                    }
+                },
+                |seen, l| seen[a[l]] = false,
+            );
+            for l in 0..n {
+                let expected = (l..=n)
+                    .take_while(|&r| (l..r).all(|i| !a[l..i].contains(&a[i])))
+                    .last()
+                    .unwrap();
+                assert_eq!(ends[l], expected);
+            }
+        }
+    }
+}
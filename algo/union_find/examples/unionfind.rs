@@ -1,4 +1,5 @@
 // problem: https://judge.yosupo.jp/problem/unionfind
+// tags: graph
 use join::Join;
 use proconio::{fastout, input};
 use union_find::UnionFind;
@@ -0,0 +1,157 @@
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 外部クレートに依存しない擬似乱数生成器です。[xoshiro256**](https://prng.di.unimi.it/) を使っています。
+///
+/// # Examples
+/// ```
+/// use xorshift::Xorshift;
+///
+/// let mut rng = Xorshift::new(42);
+/// let x = rng.gen_range(0..10_usize);
+/// assert!(x < 10);
+/// let y = rng.gen_range(-5..5_i64);
+/// assert!(-5 <= y && y < 5);
+///
+/// let mut a = vec![1, 2, 3, 4, 5];
+/// rng.shuffle(&mut a);
+/// a.sort();
+/// assert_eq!(a, vec![1, 2, 3, 4, 5]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Xorshift {
+    s: [u64; 4],
+}
+
+impl Xorshift {
+    /// シード `seed` で初期化します。同じシードからは同じ列が生成されます。
+    pub fn new(seed: u64) -> Self {
+        // splitmix64 で内部状態を埋める
+        let mut x = seed;
+        let mut next = || {
+            x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = x;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        Self {
+            s: [next(), next(), next(), next()],
+        }
+    }
+
+    /// 現在時刻をシードにして初期化します。
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        Self::new(nanos as u64)
+    }
+
+    /// `0` 以上 `2^64` 未満の一様な乱数を返します。
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// `range` に含まれる一様な乱数を返します。
+    ///
+    /// # Panics
+    ///
+    /// `range` が空の場合パニックです。
+    pub fn gen_range<T: RangeInt>(&mut self, range: Range<T>) -> T {
+        assert!(range.start < range.end);
+        let width = range.end.to_u64().wrapping_sub(range.start.to_u64());
+        // [0, width) に写す
+        let x = (u128::from(self.next_u64()) * u128::from(width)) >> 64;
+        T::from_u64(range.start.to_u64().wrapping_add(x as u64))
+    }
+
+    /// `a` を一様ランダムに並べ替えます (Fisher–Yates)。
+    pub fn shuffle<T>(&mut self, a: &mut [T]) {
+        for i in (1..a.len()).rev() {
+            let j = self.gen_range(0..(i + 1));
+            a.swap(i, j);
+        }
+    }
+}
+
+/// [`Xorshift::gen_range`] で使える整数型です。
+pub trait RangeInt: Copy + PartialOrd {
+    fn to_u64(self) -> u64;
+    fn from_u64(x: u64) -> Self;
+}
+
+macro_rules! impl_range_int {
+    ($($t:ty),*) => {
+        $(
+            impl RangeInt for $t {
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+                fn from_u64(x: u64) -> Self {
+                    x as Self
+                }
+            }
+        )*
+    };
+}
+
+impl_range_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::Xorshift;
+
+    #[test]
+    fn test_same_seed() {
+        let mut a = Xorshift::new(1);
+        let mut b = Xorshift::new(1);
+        let mut c = Xorshift::new(2);
+        let xs = (0..10).map(|_| a.next_u64()).collect::<Vec<_>>();
+        let ys = (0..10).map(|_| b.next_u64()).collect::<Vec<_>>();
+        let zs = (0..10).map(|_| c.next_u64()).collect::<Vec<_>>();
+        assert_eq!(xs, ys);
+        assert_ne!(xs, zs);
+    }
+
+    #[test]
+    fn test_gen_range() {
+        let mut rng = Xorshift::new(12345);
+        let mut count = [0; 10];
+        for _ in 0..100_000 {
+            count[rng.gen_range(0..10)] += 1;
+        }
+        for c in count {
+            assert!((9_000..11_000).contains(&c), "{:?}", count);
+        }
+        for _ in 0..1000 {
+            let x = rng.gen_range(-3..3_i8);
+            assert!((-3..3).contains(&x));
+            let y = rng.gen_range(i64::MIN..i64::MAX);
+            assert!(y < i64::MAX);
+        }
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = Xorshift::new(0);
+        // 3 要素の並べ替え 6 通りがどれも出る
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..1000 {
+            let mut a = [0, 1, 2];
+            rng.shuffle(&mut a);
+            seen.insert(a);
+        }
+        assert_eq!(seen.len(), 6);
+    }
+}
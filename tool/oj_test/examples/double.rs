@@ -1,3 +1,5 @@
+// generator_rs: ./double_generator.rs
+// naive_rs: ./double_naive.rs
 use std::io;
 use std::str::FromStr;
 
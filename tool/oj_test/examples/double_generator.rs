@@ -0,0 +1,14 @@
+use std::env;
+
+// シード値から -1000 以上 1000 以下の整数をひとつ作る
+fn main() {
+    let seed = env::args()
+        .nth(1)
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(0);
+    let x = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407)
+        >> 33;
+    println!("{}", (x % 2001) as i64 - 1000);
+}
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::report::SolverSummary;
+
+// これより短い実行時間の差は計測の揺れとみなす (秒)
+const MIN_REGRESSION: f64 = 0.05;
+
+/// テストケースごとの実行時間の基準値です。解答のパスと問題の URL → テストケースの名前 → 実行時間 (秒) です。
+///
+/// 実行時間はマシンによって違うので、CI は master で `--update-baseline` を付けて更新した基準値を GitHub Actions のキャッシュに保存し、プルリクエストではそれと比べます。
+/// 手元では `oj_test --baseline FILE --update-baseline` で作った基準値と比べてください。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Baseline {
+    solvers: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+/// 基準値より遅くなったテストケースです。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub solver: String,
+    pub testcase: String,
+    pub baseline: f64,
+    pub elapsed: f64,
+}
+
+impl Baseline {
+    /// [`Baseline::to_json`] で書いた JSON を読みます。
+    pub fn parse(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
+    /// 差分が読みやすいように、解答ごとに 1 行の JSON にします。
+    pub fn to_json(&self) -> String {
+        let mut result = "{\n".to_string();
+        for (i, (solver, testcases)) in self.solvers.iter().enumerate() {
+            let separator = if i + 1 < self.solvers.len() { "," } else { "" };
+            writeln!(
+                result,
+                "  {}: {}{}",
+                serde_json::to_string(solver).unwrap(),
+                serde_json::to_string(testcases).unwrap(),
+                separator
+            )
+            .unwrap();
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    /// AC だった解答の実行時間で基準値を置き換えます。実行しなかった解答の基準値はそのまま残します。
+    pub fn update(&mut self, summaries: &[SolverSummary]) {
+        for s in summaries.iter().filter(|s| s.is_accepted()) {
+            let report = s.result.as_ref().unwrap();
+            self.solvers.insert(
+                solver_key(s),
+                report
+                    .testcases
+                    .iter()
+                    // ミリ秒より細かい値は揺れるだけなので丸める
+                    .map(|t| (t.name.clone(), (t.elapsed * 1000.0).round() / 1000.0))
+                    .collect(),
+            );
+        }
+    }
+
+    /// 基準値の `threshold` 倍より遅くなったテストケースを返します。
+    pub fn regressions(&self, summaries: &[SolverSummary], threshold: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for s in summaries {
+            let (Some(baseline), Ok(report)) = (self.solvers.get(&solver_key(s)), &s.result) else {
+                continue;
+            };
+            for t in &report.testcases {
+                let Some(&base) = baseline.get(&t.name) else {
+                    continue;
+                };
+                if t.elapsed > base * threshold && t.elapsed - base > MIN_REGRESSION {
+                    regressions.push(Regression {
+                        solver: solver_key(s),
+                        testcase: t.name.clone(),
+                        baseline: base,
+                        elapsed: t.elapsed,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+}
+
+// ひとつの解答で複数の問題を検証することがあるので、問題の URL も含める
+// OS によらないように / で区切る
+fn solver_key(summary: &SolverSummary) -> String {
+    let path = summary
+        .solver_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{} {}", path, summary.problem_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Baseline, Regression};
+    use crate::report::{SolverSummary, TestReport, TestcaseResult, Verdict};
+
+    fn summary(path: &str, verdict: Verdict, elapsed: &[f64]) -> SolverSummary {
+        SolverSummary {
+            solver_path: PathBuf::from(path),
+            problem_url: "https://example.com".to_string(),
+            result: Ok(TestReport {
+                testcases: elapsed
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &elapsed)| TestcaseResult {
+                        name: format!("{}", i + 1),
+                        verdict,
+                        elapsed,
+                    })
+                    .collect(),
+            }),
+        }
+    }
+
+    #[test]
+    fn update_and_parse_test() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[
+            summary("algo/a/examples/a.rs", Verdict::Accepted, &[0.5, 0.12345]),
+            summary("algo/b/examples/b.rs", Verdict::WrongAnswer, &[0.1]),
+        ]);
+        let json = baseline.to_json();
+        assert_eq!(
+            json,
+            "{\n  \"algo/a/examples/a.rs https://example.com\": {\"1\":0.5,\"2\":0.123}\n}\n"
+        );
+        assert_eq!(Baseline::parse(&json).unwrap(), baseline);
+
+        // 実行しなかった解答の基準値は残す
+        baseline.update(&[summary("algo/c/examples/c.rs", Verdict::Accepted, &[1.0])]);
+        let baseline = Baseline::parse(&baseline.to_json()).unwrap();
+        assert_eq!(baseline.solvers.len(), 2);
+    }
+
+    #[test]
+    fn regressions_test() {
+        let mut baseline = Baseline::default();
+        baseline.update(&[summary(
+            "algo/a/examples/a.rs",
+            Verdict::Accepted,
+            &[0.5, 0.01, 0.5],
+        )]);
+        let regressions = baseline.regressions(
+            &[
+                summary("algo/a/examples/a.rs", Verdict::Accepted, &[1.0, 0.03, 0.6]),
+                summary("algo/b/examples/b.rs", Verdict::Accepted, &[10.0]),
+            ],
+            1.5,
+        );
+        // 2 は 3 倍遅いが、差が小さいので無視する
+        assert_eq!(
+            regressions,
+            vec![Regression {
+                solver: "algo/a/examples/a.rs https://example.com".to_string(),
+                testcase: "1".to_string(),
+                baseline: 0.5,
+                elapsed: 1.0,
+            }]
+        );
+    }
+}
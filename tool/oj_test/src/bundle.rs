@@ -0,0 +1,645 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use glob::glob;
+
+/// ワークスペースのライブラリクレートです。
+#[derive(Debug, Clone)]
+pub(crate) struct LibraryCrate {
+    dir: PathBuf,
+    pub(crate) dependencies: Vec<String>,
+}
+
+/// 解答 `solver_path` が使っているワークスペースのクレートを `mod クレート名 { ... }` の形で展開して、提出できるひとつのファイルにします。
+///
+/// ドキュメントコメントと `#[cfg(test)]` の付いた要素は取り除きます。
+pub fn bundle(solver_path: &Path, workspace_root: &Path) -> Result<String> {
+    let solver = fs::read_to_string(solver_path)
+        .with_context(|| format!("failed to read {:?}", solver_path))?;
+    let crates = library_crates(workspace_root)?;
+
+    // 解答から直接使われているクレートと、その依存先を全て集める
+    let mut used = BTreeSet::new();
+    let mut stack = identifiers_before_path_separator(&solver)
+        .into_iter()
+        .filter(|name| crates.contains_key(name))
+        .collect::<Vec<_>>();
+    while let Some(name) = stack.pop() {
+        if used.insert(name.clone()) {
+            stack.extend(crates[&name].dependencies.iter().cloned());
+        }
+    }
+
+    let mut result = solver;
+    if !result.ends_with('\n') {
+        result.push('\n');
+    }
+    for name in &used {
+        let library = &crates[name];
+        result.push('\n');
+        result.push_str(&bundle_crate(name, library)?);
+    }
+    Ok(result)
+}
+
+pub(crate) fn bundle_crate(name: &str, library: &LibraryCrate) -> Result<String> {
+    let src = library.dir.join("src");
+    let body = expand_module(&src.join("lib.rs"), &src, name, &library.dependencies)?;
+    let mut result = format!("#[allow(dead_code, unused_imports)]\npub mod {} {{\n", name);
+    for line in body.lines() {
+        if line.trim() == "#[macro_export]" {
+            continue;
+        }
+        if line.is_empty() {
+            result.push('\n');
+        } else {
+            result.push_str("    ");
+            result.push_str(line);
+            result.push('\n');
+        }
+    }
+    // #[macro_export] を外したマクロをパスで使えるようにする
+    for macro_name in exported_macros(&body) {
+        result.push_str(&format!("    pub(crate) use {};\n", macro_name));
+    }
+    result.push_str("}\n");
+    Ok(result)
+}
+
+// file を読んで、mod foo; を mod foo { ... } に展開する
+// dir は file の子モジュールのファイルを置くディレクトリ
+fn expand_module(
+    file: &Path,
+    dir: &Path,
+    crate_name: &str,
+    dependencies: &[String],
+) -> Result<String> {
+    let source = fs::read_to_string(file).with_context(|| format!("failed to read {:?}", file))?;
+    let source = rewrite_crate_paths(&strip_tests(&strip_doc_comments(&source)), crate_name);
+    // 依存先のクレートはバンドルしたファイルのルートにあるので、このモジュールで使うものだけ持ってくる
+    let used = identifiers_before_path_separator(&source);
+    let mut result = String::new();
+    for dependency in dependencies.iter().filter(|d| used.contains(*d)) {
+        result.push_str(&format!("use crate::{};\n", dependency));
+    }
+    for line in source.lines() {
+        if let Some((visibility, module)) = parse_mod_declaration(line) {
+            let candidates = [
+                dir.join(format!("{}.rs", module)),
+                dir.join(module).join("mod.rs"),
+            ];
+            let Some(child) = candidates.iter().find(|p| p.exists()) else {
+                bail!("module {} not found in {:?}", module, dir);
+            };
+            let body = expand_module(child, &dir.join(module), crate_name, dependencies)?;
+            result.push_str(&format!("{}mod {} {{\n", visibility, module));
+            for l in body.lines() {
+                if !l.is_empty() {
+                    result.push_str("    ");
+                }
+                result.push_str(l);
+                result.push('\n');
+            }
+            result.push_str("}\n");
+        } else {
+            result.push_str(line);
+            result.push('\n');
+        }
+    }
+    Ok(result)
+}
+
+// "pub mod foo;" -> Some(("pub ", "foo"))
+fn parse_mod_declaration(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    let rest = line.strip_suffix(';')?;
+    let position = rest.find("mod ")?;
+    let (visibility, module) = (&rest[..position], rest[(position + 4)..].trim());
+    let valid_visibility = visibility.is_empty()
+        || visibility == "pub "
+        || (visibility.starts_with("pub(") && visibility.ends_with(") "));
+    let valid_module = !module.is_empty()
+        && module
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid_visibility && valid_module {
+        Some((visibility, module))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn library_crates(workspace_root: &Path) -> Result<BTreeMap<String, LibraryCrate>> {
+    let pattern = workspace_root.join("algo").join("*").join("Cargo.toml");
+    let mut crates = BTreeMap::new();
+    for entry in glob(pattern.to_str().context("invalid workspace path")?)? {
+        let manifest_path = entry?;
+        let manifest = fs::read_to_string(&manifest_path)?;
+        let (name, dependencies) = parse_manifest(&manifest)
+            .with_context(|| format!("failed to parse {:?}", manifest_path))?;
+        crates.insert(
+            name,
+            LibraryCrate {
+                dir: manifest_path.parent().unwrap().to_path_buf(),
+                dependencies,
+            },
+        );
+    }
+    Ok(crates)
+}
+
+// Cargo.toml からクレート名と、path で指定された依存先を読む
+fn parse_manifest(manifest: &str) -> Option<(String, Vec<String>)> {
+    let mut section = "";
+    let mut name = None;
+    let mut dependencies = Vec::new();
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if section == "[package]" && key == "name" {
+            name = Some(value.trim_matches('"').replace('-', "_"));
+        } else if section == "[dependencies]" && value.contains("path") {
+            dependencies.push(key.replace('-', "_"));
+        }
+    }
+    name.map(|name| (name, dependencies))
+}
+
+// foo::bar の foo のような識別子を全て返す
+pub(crate) fn identifiers_before_path_separator(source: &str) -> BTreeSet<String> {
+    let source = strip_comments_and_literals(source);
+    let bytes = source.as_bytes();
+    let mut result = BTreeSet::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if is_identifier_start(bytes[i]) && (i == 0 || !is_identifier_char(bytes[i - 1])) {
+            let start = i;
+            while i < bytes.len() && is_identifier_char(bytes[i]) {
+                i += 1;
+            }
+            let rest = source[i..].trim_start();
+            let preceded_by_separator = source[..start].trim_end().ends_with("::");
+            if rest.starts_with("::") && !preceded_by_separator {
+                result.insert(source[start..i].to_string());
+            }
+        } else {
+            i += 1;
+        }
+    }
+    result
+}
+
+fn is_identifier_start(c: u8) -> bool {
+    c.is_ascii_alphabetic() || c == b'_'
+}
+
+fn is_identifier_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+// crate:: と $crate:: を crate::crate_name:: に書き換える
+// コメントと文字列・文字リテラルの中は書き換えない
+fn rewrite_crate_paths(source: &str, crate_name: &str) -> String {
+    const CRATE: &str = "crate::";
+    let masked = strip_comments_and_literals(source);
+    let mut result = String::new();
+    let mut last = 0;
+    for (position, _) in masked.match_indices(CRATE) {
+        let preceded_by_identifier = masked[..position]
+            .bytes()
+            .last()
+            .map_or(false, is_identifier_char);
+        if !preceded_by_identifier {
+            let end = position + CRATE.len();
+            result.push_str(&source[last..end]);
+            result.push_str(crate_name);
+            result.push_str("::");
+            last = end;
+        }
+    }
+    result.push_str(&source[last..]);
+    result
+}
+
+// 文字列リテラルの中の行は残す
+fn strip_doc_comments(source: &str) -> String {
+    let masked = strip_literals(source);
+    let mut result = String::new();
+    for (line, masked_line) in source.lines().zip(masked.lines()) {
+        let trimmed = masked_line.trim_start();
+        if trimmed.starts_with("///") || trimmed.starts_with("//!") {
+            continue;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+// #[cfg(test)] の付いた要素を取り除く
+fn strip_tests(source: &str) -> String {
+    const ATTRIBUTE: &str = "#[cfg(test)]";
+    let masked = strip_comments_and_literals(source);
+    let mut result = String::new();
+    let mut last = 0;
+    let mut search_from = 0;
+    while let Some(offset) = masked[search_from..].find(ATTRIBUTE) {
+        let start = search_from + offset;
+        // 要素の終わり (対応する } か、括弧の外の ;) を探す
+        let mut depth = 0;
+        let mut end = masked.len();
+        for (i, c) in masked[(start + ATTRIBUTE.len())..].char_indices() {
+            let i = start + ATTRIBUTE.len() + i;
+            match c {
+                '{' | '(' | '[' => depth += 1,
+                '}' | ')' | ']' => {
+                    depth -= 1;
+                    if depth == 0 && c == '}' {
+                        end = i + 1;
+                        break;
+                    }
+                }
+                ';' if depth == 0 => {
+                    end = i + 1;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        result.push_str(source[last..start].trim_end_matches([' ', '\t']));
+        last = end;
+        // 行末の改行も消す
+        if source[last..].starts_with('\n') {
+            last += 1;
+        }
+        search_from = last;
+    }
+    result.push_str(&source[last..]);
+    result
+}
+
+// コメントと文字列・文字リテラルの中身を空白に置き換える (バイト位置は変えない)
+fn strip_comments_and_literals(source: &str) -> String {
+    mask_source(source, true)
+}
+
+// 文字列・文字リテラルの中身を空白に置き換える (バイト位置は変えない)
+fn strip_literals(source: &str) -> String {
+    mask_source(source, false)
+}
+
+fn mask_source(source: &str, comments: bool) -> String {
+    let bytes = source.as_bytes();
+    let mut result = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"//") {
+            let end = bytes[i..]
+                .iter()
+                .position(|&c| c == b'\n')
+                .map_or(bytes.len(), |p| i + p);
+            if comments {
+                mask(&mut result, i, end);
+            }
+            i = end;
+        } else if bytes[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            let mut j = i;
+            while j < bytes.len() {
+                if bytes[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if bytes[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            if comments {
+                mask(&mut result, i, j);
+            }
+            i = j;
+        } else if bytes[i] == b'r'
+            && (i == 0 || !is_identifier_char(bytes[i - 1]))
+            && matches!(bytes.get(i + 1), Some(b'"' | b'#'))
+        {
+            // raw string
+            let hashes = bytes[(i + 1)..].iter().take_while(|&&c| c == b'#').count();
+            if bytes.get(i + 1 + hashes) != Some(&b'"') {
+                i += 1;
+                continue;
+            }
+            let mut terminator = vec![b'"'];
+            terminator.extend(std::iter::repeat(b'#').take(hashes));
+            let body = i + 2 + hashes;
+            let end = (body..bytes.len())
+                .find(|&j| bytes[j..].starts_with(&terminator))
+                .map_or(bytes.len(), |j| j + terminator.len());
+            mask(
+                &mut result,
+                body,
+                end.saturating_sub(terminator.len()).max(body),
+            );
+            i = end;
+        } else if bytes[i] == b'"' {
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != b'"' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            mask(&mut result, i + 1, j.min(bytes.len()));
+            i = j + 1;
+        } else if bytes[i] == b'\'' {
+            // 文字リテラルかライフタイム
+            let end = if bytes.get(i + 1) == Some(&b'\\') {
+                (i + 2..bytes.len()).find(|&j| bytes[j] == b'\'')
+            } else {
+                let len = source[(i + 1)..].chars().next().map_or(0, char::len_utf8);
+                (bytes.get(i + 1 + len) == Some(&b'\'')).then_some(i + 1 + len)
+            };
+            match end {
+                Some(end) => {
+                    mask(&mut result, i + 1, end);
+                    i = end + 1;
+                }
+                None => i += 1,
+            }
+        } else {
+            i += 1;
+        }
+    }
+    // 置き換えたのは ASCII の部分と、マルチバイト文字を丸ごとなので UTF-8 のまま
+    String::from_utf8(result).unwrap()
+}
+
+fn mask(bytes: &mut [u8], from: usize, to: usize) {
+    for c in &mut bytes[from..to] {
+        if *c != b'\n' {
+            *c = b' ';
+        }
+    }
+}
+
+// #[macro_export] の付いたマクロの名前を返す
+fn exported_macros(source: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut exported = false;
+    for line in source.lines() {
+        let line = line.trim();
+        if line == "#[macro_export]" {
+            exported = true;
+        } else if let Some(rest) = line.strip_prefix("macro_rules!") {
+            if exported {
+                let name = rest
+                    .trim()
+                    .trim_end_matches('{')
+                    .trim_end_matches('(')
+                    .trim();
+                result.push(name.to_string());
+            }
+            exported = false;
+        } else if !line.starts_with("#[") {
+            exported = false;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_tests_test() {
+        let source = r##"pub fn f() -> &'static str {
+    "#[cfg(test)] { "
+}
+
+#[cfg(test)]
+mod tests {
+    // }
+    #[test]
+    fn g() {
+        let _ = '}';
+    }
+}
+#[cfg(test)]
+use std::collections::HashMap;
+fn h() {}
+"##;
+        assert_eq!(
+            strip_tests(source),
+            r##"pub fn f() -> &'static str {
+    "#[cfg(test)] { "
+}
+
+fn h() {}
+"##
+        );
+    }
+
+    #[test]
+    fn strip_doc_comments_test() {
+        let source = "/// doc\n//! inner\nfn f() {\n    /// doc\n    // comment\n}\n";
+        assert_eq!(strip_doc_comments(source), "fn f() {\n    // comment\n}\n");
+        let source = "const S: &str = r#\"\n/// not a doc\n\"#;\n/// doc\nconst T: &str = \"\n//! not a doc\";\n";
+        assert_eq!(
+            strip_doc_comments(source),
+            "const S: &str = r#\"\n/// not a doc\n\"#;\nconst T: &str = \"\n//! not a doc\";\n"
+        );
+    }
+
+    #[test]
+    fn identifiers_test() {
+        let source = r#"use proconio::input;
+use ext_gcd::ext_gcd;
+// use commented::out;
+fn main() {
+    let s = "literal::path";
+    let x = std::cmp::max(1, 2);
+    let y = <Vec<u8> as segment_tree :: Foo>::bar();
+}
+"#;
+        assert_eq!(
+            identifiers_before_path_separator(source)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["ext_gcd", "proconio", "segment_tree", "std"]
+        );
+    }
+
+    #[test]
+    fn rewrite_crate_paths_test() {
+        assert_eq!(
+            rewrite_crate_paths(
+                "use crate::point::Point;\n$crate::out!(w);\npub(crate) fn f() {}\nmy_crate::x",
+                "geometry"
+            ),
+            "use crate::geometry::point::Point;\n$crate::geometry::out!(w);\npub(crate) fn f() {}\nmy_crate::x"
+        );
+        assert_eq!(
+            rewrite_crate_paths(
+                "// crate::a\nlet s = \"crate::b\";\nlet r = r\"crate::c\";\ncrate::d();",
+                "geometry"
+            ),
+            "// crate::a\nlet s = \"crate::b\";\nlet r = r\"crate::c\";\ncrate::geometry::d();"
+        );
+    }
+
+    #[test]
+    fn parse_mod_declaration_test() {
+        assert_eq!(parse_mod_declaration("mod point;"), Some(("", "point")));
+        assert_eq!(
+            parse_mod_declaration("pub mod float;"),
+            Some(("pub ", "float"))
+        );
+        assert_eq!(
+            parse_mod_declaration("    pub(crate) mod x;"),
+            Some(("pub(crate) ", "x"))
+        );
+        assert_eq!(parse_mod_declaration("mod tests {"), None);
+        assert_eq!(parse_mod_declaration("let mod x;"), None);
+    }
+
+    #[test]
+    fn parse_manifest_test() {
+        let manifest = r#"[package]
+name = "arbitrary-mod-binomial"
+version = "0.1.0"
+
+[dependencies]
+ext_gcd = { path = "../ext_gcd" }
+mod_int = { path = "../mod_int" }
+
+[dev-dependencies]
+pascal_triangle = { path = "../pascal_triangle" }
+rand = "0.7"
+"#;
+        assert_eq!(
+            parse_manifest(manifest),
+            Some((
+                "arbitrary_mod_binomial".to_string(),
+                vec!["ext_gcd".to_string(), "mod_int".to_string()]
+            ))
+        );
+    }
+
+    #[test]
+    fn exported_macros_test() {
+        let source = "#[macro_export]\nmacro_rules! out {\n}\nmacro_rules! private {\n}\n#[macro_export]\n#[doc(hidden)]\nmacro_rules! outln {\n}\n";
+        assert_eq!(exported_macros(source), vec!["out", "outln"]);
+    }
+
+    #[test]
+    fn bundle_workspace_example() {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+        let solver = workspace_root
+            .join("algo")
+            .join("inversion_count")
+            .join("examples")
+            .join("alds_1_5_d.rs");
+        let bundled = bundle(&solver, &workspace_root).unwrap();
+        assert!(bundled.starts_with("// problem: "));
+        assert!(bundled.contains("pub mod inversion_count {"));
+        assert!(bundled.contains("pub mod fenwick_tree {"));
+        assert!(bundled.contains("pub mod zarts {"));
+        assert!(!bundled.contains("pub mod proconio {"));
+        assert!(!bundled.contains("#[cfg(test)]"));
+        assert!(!bundled.contains("///"));
+    }
+
+    // バンドルしたファイルを rustc でコンパイルできるか確かめる
+    fn compile(name: &str, source: &str, args: &[&str]) {
+        let dir = std::env::temp_dir()
+            .join("oj_test_bundle_test")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(format!("{}.rs", name));
+        fs::write(&file, source).unwrap();
+        let output = std::process::Command::new("rustc")
+            .arg("--edition")
+            .arg("2021")
+            .arg("--out-dir")
+            .arg(&dir)
+            .args(args)
+            .arg(&file)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}\n{}",
+            source,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn compile_bundled_example() {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+        let solver = workspace_root
+            .join("algo")
+            .join("inversion_count")
+            .join("examples")
+            .join("alds_1_5_d.rs");
+        let bundled = bundle(&solver, &workspace_root).unwrap();
+        // 解答は proconio を使うので、dev-dependencies としてビルドされたものを渡す
+        let deps = std::env::current_exe()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let proconio = glob(deps.join("libproconio-*.rlib").to_str().unwrap())
+            .unwrap()
+            .next()
+            .expect("proconio is not built")
+            .unwrap();
+        compile(
+            "alds_1_5_d",
+            &bundled,
+            &[
+                "--extern",
+                &format!("proconio={}", proconio.display()),
+                "-L",
+                &format!("dependency={}", deps.display()),
+            ],
+        );
+    }
+
+    #[test]
+    fn compile_bundled_submodules() {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+        let dir = std::env::temp_dir()
+            .join("oj_test_bundle_submodules")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        let solver = dir.join("main.rs");
+        fs::write(
+            &solver,
+            r#"use geometry::{segments_intersect, Point};
+use inversion_count::inversion_count;
+
+fn main() {
+    let p = |x, y| Point::new(x, y);
+    println!("{}", segments_intersect(p(0, 0), p(2, 2), p(0, 2), p(2, 0)));
+    println!("{}", inversion_count(&[3, 1, 2]));
+}
+"#,
+        )
+        .unwrap();
+        let bundled = bundle(&solver, &workspace_root).unwrap();
+        assert!(bundled.contains("pub mod geometry {"));
+        compile("submodules", &bundled, &[]);
+    }
+}
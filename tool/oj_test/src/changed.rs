@@ -0,0 +1,264 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{ensure, Result};
+use log::info;
+use serde::Deserialize;
+
+/// ワークスペースのパッケージです。
+#[derive(Debug, Clone, PartialEq)]
+struct Package {
+    name: String,
+    dir: PathBuf,
+    /// ワークスペース内の依存先 (dev-dependencies も含む)
+    dependencies: Vec<String>,
+}
+
+/// `git_ref` から分岐した後の変更の影響を受けるパッケージのディレクトリを返します。
+///
+/// 変更されたファイルを含むパッケージと、それに (dev-dependencies も含めて) 推移的に依存するパッケージが対象です。
+/// どのパッケージにも含まれないファイル (ワークスペースの `Cargo.toml` など) や `oj_test` 自身が変更されたときは、全てを対象にするために `None` を返します。
+pub fn affected_package_dirs(git_ref: &str) -> Result<Option<Vec<PathBuf>>> {
+    let changed_files = changed_files(Path::new("."), git_ref)?;
+    info!("{} file(s) changed since {}", changed_files.len(), git_ref);
+    affected_package_dirs_by_files(&changed_files)
+}
+
+// git_ref と HEAD の分岐点から HEAD までに変更されたファイルを絶対パスで返す
+// git_ref 側で分岐後に変更されたファイルは含めない
+fn changed_files(repository: &Path, git_ref: &str) -> Result<Vec<PathBuf>> {
+    let toplevel = command_output(
+        Command::new("git")
+            .arg("-C")
+            .arg(repository)
+            .arg("rev-parse")
+            .arg("--show-toplevel"),
+    )?;
+    let toplevel = Path::new(toplevel.trim());
+    let changed_files = command_output(
+        Command::new("git")
+            .arg("-C")
+            .arg(repository)
+            .arg("diff")
+            .arg("--name-only")
+            .arg(format!("{}...HEAD", git_ref)),
+    )?
+    .lines()
+    .map(|l| toplevel.join(l))
+    .collect();
+    Ok(changed_files)
+}
+
+/// `changed_files` (絶対パス) の影響を受けるパッケージのディレクトリを返します。対象は [`affected_package_dirs`] と同じです。
+pub fn affected_package_dirs_by_files(changed_files: &[PathBuf]) -> Result<Option<Vec<PathBuf>>> {
+    let packages = parse_metadata(&command_output(
+        Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+            .arg("metadata")
+            .arg("--format-version")
+            .arg("1")
+            .arg("--no-deps"),
+    )?)?;
+    Ok(affected_packages(&packages, changed_files).map(|names| {
+        packages
+            .iter()
+            .filter(|p| names.contains(&p.name))
+            .map(|p| p.dir.clone())
+            .collect()
+    }))
+}
+
+fn command_output(command: &mut Command) -> Result<String> {
+    info!("execute {:?}", command);
+    let output = command.output()?;
+    ensure!(
+        output.status.success(),
+        "failed: {:?}\n{}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+// cargo metadata --format-version 1 --no-deps の出力のうち使う部分
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<MetadataPackage>,
+}
+
+#[derive(Deserialize)]
+struct MetadataPackage {
+    name: String,
+    manifest_path: PathBuf,
+    #[serde(default)]
+    dependencies: Vec<MetadataDependency>,
+}
+
+#[derive(Deserialize)]
+struct MetadataDependency {
+    name: String,
+    path: Option<PathBuf>,
+}
+
+fn parse_metadata(metadata: &str) -> Result<Vec<Package>> {
+    let metadata: Metadata = serde_json::from_str(metadata)?;
+    let packages = metadata
+        .packages
+        .into_iter()
+        .map(|package| Package {
+            name: package.name,
+            dir: package.manifest_path.parent().unwrap().to_path_buf(),
+            // path が無い依存先は crates.io のクレート
+            dependencies: package
+                .dependencies
+                .into_iter()
+                .filter(|d| d.path.is_some())
+                .map(|d| d.name)
+                .collect(),
+        })
+        .collect();
+    Ok(packages)
+}
+
+fn affected_packages(packages: &[Package], changed_files: &[PathBuf]) -> Option<BTreeSet<String>> {
+    let mut affected = BTreeSet::new();
+    for file in changed_files {
+        // 入れ子になったパッケージもあるので、いちばん深いものを選ぶ
+        let package = packages
+            .iter()
+            .filter(|p| file.starts_with(&p.dir))
+            .max_by_key(|p| p.dir.components().count())?;
+        // 検証する側が変わったときは全ての解答を検証し直す
+        if package.name == "oj_test" {
+            return None;
+        }
+        affected.insert(package.name.clone());
+    }
+    loop {
+        let mut updated = false;
+        for p in packages {
+            if !affected.contains(&p.name) && p.dependencies.iter().any(|d| affected.contains(d)) {
+                affected.insert(p.name.clone());
+                updated = true;
+            }
+        }
+        if !updated {
+            break;
+        }
+    }
+    Some(affected)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn packages() -> Vec<Package> {
+        parse_metadata(
+            r#"{"packages": [
+                {"name": "fenwick_tree", "manifest_path": "/w/algo/fenwick_tree/Cargo.toml",
+                 "dependencies": [{"name": "proconio", "kind": "dev"}]},
+                {"name": "zarts", "manifest_path": "/w/algo/zarts/Cargo.toml", "dependencies": []},
+                {"name": "inversion_count", "manifest_path": "/w/algo/inversion_count/Cargo.toml",
+                 "dependencies": [
+                     {"name": "fenwick_tree", "kind": null, "path": "/w/algo/fenwick_tree"},
+                     {"name": "zarts", "kind": null, "path": "/w/algo/zarts"}]},
+                {"name": "offline_time_division", "manifest_path": "/w/algo/offline_time_division/Cargo.toml",
+                 "dependencies": [{"name": "fenwick_tree", "kind": "dev", "path": "/w/algo/fenwick_tree"}]},
+                {"name": "oj_test", "manifest_path": "/w/tool/oj_test/Cargo.toml", "dependencies": []}
+            ], "workspace_root": "/w"}"#
+                .replace('\n', " ")
+                .as_str(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_metadata_test() {
+        let packages = packages();
+        assert_eq!(packages.len(), 5);
+        assert_eq!(packages[0].dependencies, Vec::<String>::new());
+        assert_eq!(packages[2].dir, PathBuf::from("/w/algo/inversion_count"));
+        assert_eq!(packages[2].dependencies, vec!["fenwick_tree", "zarts"]);
+    }
+
+    #[test]
+    fn affected_packages_test() {
+        let packages = packages();
+        let affected = |files: &[&str]| {
+            affected_packages(
+                &packages,
+                &files.iter().map(PathBuf::from).collect::<Vec<_>>(),
+            )
+            .map(|s| s.into_iter().collect::<Vec<_>>())
+        };
+        assert_eq!(
+            affected(&["/w/algo/fenwick_tree/src/lib.rs"]),
+            Some(vec![
+                "fenwick_tree".to_string(),
+                "inversion_count".to_string(),
+                "offline_time_division".to_string()
+            ])
+        );
+        assert_eq!(
+            affected(&["/w/algo/zarts/examples/fish_0580.rs"]),
+            Some(vec!["inversion_count".to_string(), "zarts".to_string()])
+        );
+        assert_eq!(affected(&[]), Some(vec![]));
+        assert_eq!(affected(&["/w/Cargo.toml"]), None);
+        assert_eq!(affected(&["/w/tool/oj_test/src/report.rs"]), None);
+        assert_eq!(
+            affected(&["/w/algo/zarts/src/lib.rs", "/w/tool/oj_test/src/main.rs"]),
+            None
+        );
+        // 名前が前方一致するだけのディレクトリは別のパッケージ
+        assert_eq!(affected(&["/w/algo/zarts2/src/lib.rs"]), None);
+    }
+
+    #[test]
+    fn changed_files_test() {
+        let repository = std::env::temp_dir()
+            .join("oj_test_changed_files_test")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(&repository).unwrap();
+        let git = |args: &[&str]| {
+            command_output(
+                Command::new("git")
+                    .arg("-C")
+                    .arg(&repository)
+                    .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                    .args(["-c", "commit.gpgsign=false"])
+                    .args(args),
+            )
+            .unwrap();
+        };
+        let commit = |file: &str| {
+            fs::write(repository.join(file), file).unwrap();
+            git(&["add", file]);
+            git(&["commit", "-m", file]);
+        };
+        git(&["init", "-q", "-b", "base"]);
+        commit("a");
+        git(&["checkout", "-q", "-b", "topic"]);
+        commit("b");
+        git(&["checkout", "-q", "base"]);
+        // 分岐した後に base で変更されたファイルは含めない
+        commit("c");
+        git(&["checkout", "-q", "topic"]);
+        let toplevel = command_output(
+            Command::new("git")
+                .arg("-C")
+                .arg(&repository)
+                .arg("rev-parse")
+                .arg("--show-toplevel"),
+        )
+        .unwrap();
+        assert_eq!(
+            changed_files(&repository, "base").unwrap(),
+            vec![Path::new(toplevel.trim()).join("b")]
+        );
+        fs::remove_dir_all(&repository).unwrap();
+    }
+}
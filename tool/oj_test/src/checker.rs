@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{ensure, Context, Result};
+use log::info;
+
+use crate::download::{cache_root, fnv1a, retry_with_backoff, DOWNLOAD_ATTEMPTS};
+
+// Library Checker のチェッカーが使っている testlib.h
+const TESTLIB_URL: &str =
+    "https://raw.githubusercontent.com/yosupo06/library-checker-problems/master/common/testlib.h";
+
+pub(crate) fn checker_cache_root() -> PathBuf {
+    cache_root().join("checkers")
+}
+
+/// testlib 形式のチェッカーをコンパイルして、実行ファイルのパスを返します。
+///
+/// `source` は C++ のソースファイルのパスか、`https://` で始まる URL です。
+/// Library Checker の問題なら `https://raw.githubusercontent.com/yosupo06/library-checker-problems/master/graph/scc/checker.cpp` のように公式のチェッカーを指定できます。
+/// `testlib.h` はチェッカーと同じディレクトリにあればそれを、なければダウンロードしたものを使います。
+/// 解答に `judge_program_rs` も書いておくと、チェッカーを用意できないときはそちらで判定します。
+///
+/// ダウンロードしたファイルは URL ごとにキャッシュします。`refresh` が `true` のときは必ずダウンロードし直します。
+pub fn prepare_checker(source: &str, refresh: bool) -> Result<PathBuf> {
+    let dir = checker_cache_root().join(format!("{:016x}", fnv1a(source.as_bytes())));
+    fs::create_dir_all(&dir)?;
+    let (source_path, include_dir) = if is_url(source) {
+        let path = dir.join("checker.cpp");
+        if refresh || !path.exists() {
+            download_file(source, &path)?;
+        }
+        (path, None)
+    } else {
+        let path = PathBuf::from(source);
+        ensure!(path.exists(), "checker not found: {:?}", path);
+        let include_dir = path.parent().map(Path::to_path_buf);
+        (path, include_dir)
+    };
+    let testlib = dir.join("testlib.h");
+    if !include_dir
+        .as_ref()
+        .map_or(false, |d| d.join("testlib.h").exists())
+        && (refresh || !testlib.exists())
+    {
+        download_file(TESTLIB_URL, &testlib)?;
+    }
+
+    let binary = dir.join("checker");
+    if is_up_to_date(&binary, &source_path) {
+        info!("use compiled checker {:?}", binary);
+        return Ok(binary);
+    }
+    let mut command = Command::new(std::env::var("CXX").unwrap_or_else(|_| "g++".to_string()));
+    command.arg("-O2").arg("-std=c++17");
+    // チェッカーと同じディレクトリの testlib.h を優先する
+    if let Some(include_dir) = include_dir {
+        command.arg("-I").arg(include_dir);
+    }
+    command
+        .arg("-I")
+        .arg(&dir)
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source_path);
+    info!("execute {:?}", command);
+    let status = command.status().context("failed to execute C++ compiler")?;
+    ensure!(status.success(), "failed to compile checker: {}", source);
+    Ok(binary)
+}
+
+pub(crate) fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+fn is_up_to_date(binary: &Path, source: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified());
+    match (modified(binary), modified(source)) {
+        (Ok(binary), Ok(source)) => binary >= source,
+        _ => false,
+    }
+}
+
+fn download_file(url: &str, path: &Path) -> Result<()> {
+    // 途中まで書かれたファイルを残さないように、別の名前で保存してから置き換える
+    let partial = path.with_extension("partial");
+    retry_with_backoff(DOWNLOAD_ATTEMPTS, Duration::from_secs(2), || {
+        let mut command = Command::new("curl");
+        command
+            .arg("--fail")
+            .arg("--silent")
+            .arg("--show-error")
+            .arg("--location")
+            .arg("--output")
+            .arg(&partial)
+            .arg(url);
+        info!("execute {:?}", command);
+        let status = command.status().context("failed to execute curl")?;
+        ensure!(status.success(), "failed to download {}", url);
+        Ok(())
+    })?;
+    fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{is_up_to_date, is_url};
+
+    #[test]
+    fn is_url_test() {
+        assert!(is_url(
+            "https://raw.githubusercontent.com/yosupo06/library-checker-problems/master/graph/scc/checker.cpp"
+        ));
+        assert!(!is_url("./checker.cpp"));
+        assert!(!is_url("/tmp/https/checker.cpp"));
+    }
+
+    #[test]
+    fn is_up_to_date_test() {
+        let dir = env::temp_dir()
+            .join("oj_test_is_up_to_date_test")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("checker.cpp");
+        let binary = dir.join("checker");
+        fs::write(&source, "").unwrap();
+        assert!(!is_up_to_date(&binary, &source));
+        thread::sleep(Duration::from_millis(10));
+        fs::write(&binary, "").unwrap();
+        assert!(is_up_to_date(&binary, &source));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::bundle::{identifiers_before_path_separator, library_crates};
+use crate::ProblemSolver;
+
+/// ライブラリのクレートごとに、それを直接使っている検証用の解答 (`// problem:` を持つもの) を返します。
+///
+/// どの解答にも使われていないクレートは空の `Vec` になります。
+pub fn verification_coverage(
+    workspace_root: &Path,
+    solvers: &[ProblemSolver],
+) -> Result<BTreeMap<String, Vec<PathBuf>>> {
+    let mut coverage = library_crates(workspace_root)?
+        .into_keys()
+        .map(|name| (name, Vec::new()))
+        .collect::<BTreeMap<_, _>>();
+    for s in solvers.iter().filter(|s| s.problem_url().is_some()) {
+        let source = fs::read_to_string(s.solver_path())
+            .with_context(|| format!("failed to read {:?}", s.solver_path()))?;
+        for name in identifiers_before_path_separator(&source) {
+            if let Some(solvers) = coverage.get_mut(&name) {
+                solvers.push(s.solver_path().to_path_buf());
+            }
+        }
+    }
+    Ok(coverage)
+}
+
+/// 検証の状況を Markdown の表にします。
+pub fn coverage_markdown(coverage: &BTreeMap<String, Vec<PathBuf>>) -> String {
+    let covered = coverage.values().filter(|s| !s.is_empty()).count();
+    let mut result = format!(
+        "## Verification coverage\n\n{} / {} crates verified\n\n| crate | solvers |\n| --- | --- |\n",
+        covered,
+        coverage.len()
+    );
+    for (name, solvers) in coverage {
+        let solvers = if solvers.is_empty() {
+            "**none**".to_string()
+        } else {
+            solvers
+                .iter()
+                .map(|s| format!("`{}`", s.display()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        result.push_str(&format!("| {} | {} |\n", name, solvers));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{coverage_markdown, verification_coverage};
+    use crate::{workspace_root, ProblemSolver};
+
+    #[test]
+    fn verification_coverage_test() {
+        let root = workspace_root();
+        let solvers = [
+            ProblemSolver::new(&root.join("algo/union_find/examples/unionfind.rs")),
+            // problem の無い解答は数えない
+            ProblemSolver::new(&root.join("tool/oj_test/examples/double_naive.rs")),
+        ];
+        let coverage = verification_coverage(&root, &solvers).unwrap();
+        assert_eq!(coverage["union_find"], vec![solvers[0].solver_path()]);
+        assert_eq!(coverage["join"], vec![solvers[0].solver_path()]);
+        assert!(coverage["fenwick_tree"].is_empty());
+    }
+
+    #[test]
+    fn coverage_markdown_test() {
+        let coverage = [
+            (
+                "union_find".to_string(),
+                vec![PathBuf::from("algo/union_find/examples/unionfind.rs")],
+            ),
+            ("zarts".to_string(), vec![]),
+        ]
+        .into_iter()
+        .collect();
+        let markdown = coverage_markdown(&coverage);
+        assert!(markdown.contains("1 / 2 crates verified"));
+        assert!(markdown.contains("| union_find | `algo/union_find/examples/unionfind.rs` |"));
+        assert!(markdown.contains("| zarts | **none** |"));
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{ensure, Context, Result};
+use log::{info, warn};
+
+use crate::checker::checker_cache_root;
+use crate::library_checker;
+
+// ダウンロードが最後まで終わったディレクトリに置くファイル
+const COMPLETE_MARKER: &str = ".oj_test_complete";
+
+pub(crate) const DOWNLOAD_ATTEMPTS: usize = 4;
+
+/// テストケースのキャッシュを置くディレクトリを返します。環境変数 `OJ_TEST_CACHE_DIR` で変えられます。
+pub fn cache_root() -> PathBuf {
+    env::var_os("OJ_TEST_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::temp_dir().join("oj_test_cache"))
+}
+
+/// `oj --version` の出力を返します。
+pub fn oj_version() -> Result<String> {
+    let output = Command::new("oj")
+        .arg("--version")
+        .output()
+        .context("failed to execute oj")?;
+    ensure!(output.status.success(), "failed: oj --version");
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+/// 問題の URL、oj のバージョン、問題の改訂 (`// problem_revision:`) から決まる、テストケースのキャッシュのディレクトリを返します。
+///
+/// どれかが変わればディレクトリも変わるので、古いテストケースを使い続けることはありません。
+pub fn testcase_cache_dir(problem_url: &str, oj_version: &str, revision: Option<&str>) -> PathBuf {
+    let key = format!(
+        "{}\n{}\n{}",
+        problem_url,
+        oj_version,
+        revision.unwrap_or("")
+    );
+    cache_root().join(format!("{:016x}", fnv1a(key.as_bytes())))
+}
+
+// 実行ごとに変わらないハッシュ値が欲しいので std の Hasher は使わない
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325_u64;
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// `problem_url` のテストケースを `dir` にダウンロードして、`dir` を返します。
+///
+/// 前回のダウンロードが最後まで終わっていればそれを使います。途中で失敗したディレクトリは消してダウンロードし直します。
+/// `refresh` が `true` のときは必ずダウンロードし直します。失敗したときは間隔を倍にしながら何度か試します。
+/// それでも失敗した Library Checker の問題は、library-checker-problems の生成器でテストケースを作ります。
+pub fn download_online_judge_testcase(
+    problem_url: &str,
+    dir: &Path,
+    refresh: bool,
+) -> Result<PathBuf> {
+    if !refresh && is_complete(dir) {
+        info!("use cached testcases in {:?}", dir);
+        return Ok(dir.to_path_buf());
+    }
+    let downloaded = retry_with_backoff(DOWNLOAD_ATTEMPTS, Duration::from_secs(2), || {
+        download(problem_url, dir)
+    });
+    if let Err(err) = downloaded {
+        let Some(name) = library_checker::problem_name(problem_url) else {
+            return Err(err);
+        };
+        warn!(
+            "oj download failed: {:#}; generate testcases with library-checker-problems",
+            err
+        );
+        library_checker::generate_testcases(name, dir)
+            .with_context(|| format!("failed to generate testcases of {}", name))?;
+        ensure!(has_testcase(dir), "no testcases generated: {}", problem_url);
+    }
+    fs::write(dir.join(COMPLETE_MARKER), problem_url)?;
+    Ok(dir.to_path_buf())
+}
+
+/// キャッシュのディレクトリのうち `keep` に含まれないものを消して、消したディレクトリを返します。
+///
+/// コンパイルしたチェッカーと clone した library-checker-problems は消しません。
+pub fn gc_testcase_cache(keep: &BTreeSet<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut keep = keep.clone();
+    keep.insert(checker_cache_root());
+    keep.insert(library_checker::repository_dir());
+    remove_unused_dirs(&cache_root(), &keep)
+}
+
+fn remove_unused_dirs(root: &Path, keep: &BTreeSet<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
+    if !root.exists() {
+        return Ok(removed);
+    }
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        if path.is_dir() && !keep.contains(&path) {
+            fs::remove_dir_all(&path)?;
+            removed.push(path);
+        }
+    }
+    removed.sort();
+    Ok(removed)
+}
+
+fn download(problem_url: &str, dir: &Path) -> Result<()> {
+    // 途中まで書かれたファイルが残っていると oj download が失敗する
+    if dir.exists() {
+        fs::remove_dir_all(dir).with_context(|| format!("failed to remove {:?}", dir))?;
+    }
+    let mut oj_command = Command::new("oj");
+    oj_command
+        .arg("download")
+        .arg(problem_url)
+        .arg("--directory")
+        .arg(dir.as_os_str())
+        .arg("--system")
+        .arg("--silent");
+    info!("execute {:?}", oj_command);
+    let status = oj_command.status().context("failed to execute oj")?;
+    ensure!(status.success(), "failed: oj download");
+    ensure!(
+        has_testcase(dir),
+        "no testcases downloaded: {}",
+        problem_url
+    );
+    Ok(())
+}
+
+fn is_complete(dir: &Path) -> bool {
+    dir.join(COMPLETE_MARKER).exists() && has_testcase(dir)
+}
+
+fn has_testcase(dir: &Path) -> bool {
+    fs::read_dir(dir).map_or(false, |entries| {
+        entries
+            .filter_map(|e| e.ok())
+            .any(|e| e.path().extension().map_or(false, |ext| ext == "in"))
+    })
+}
+
+// 失敗したら delay, 2 * delay, 4 * delay, ... 待って、最大 attempts 回試す
+pub(crate) fn retry_with_backoff<T>(
+    attempts: usize,
+    delay: Duration,
+    mut f: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    assert!(attempts >= 1);
+    let mut delay = delay;
+    for attempt in 1.. {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < attempts => {
+                warn!(
+                    "attempt {}/{} failed: {:#}; retry in {:?}",
+                    attempt, attempts, err, delay
+                );
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    #[test]
+    fn retry_with_backoff_test() {
+        let mut calls = 0;
+        let result = retry_with_backoff(3, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                bail!("failed");
+            }
+            Ok(calls)
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<()> = retry_with_backoff(2, Duration::ZERO, || {
+            calls += 1;
+            bail!("failed {}", calls)
+        });
+        assert_eq!(result.unwrap_err().to_string(), "failed 2");
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn testcase_cache_dir_test() {
+        let url = "https://judge.yosupo.jp/problem/aplusb";
+        let dir = testcase_cache_dir(url, "online-judge-tools 11.5.1", None);
+        assert_eq!(
+            dir,
+            testcase_cache_dir(url, "online-judge-tools 11.5.1", None)
+        );
+        assert_eq!(dir.parent(), Some(cache_root().as_path()));
+        assert_ne!(
+            dir,
+            testcase_cache_dir(url, "online-judge-tools 11.5.2", None)
+        );
+        assert_ne!(
+            dir,
+            testcase_cache_dir(url, "online-judge-tools 11.5.1", Some("2"))
+        );
+        assert_ne!(
+            dir,
+            testcase_cache_dir(
+                "https://judge.yosupo.jp/problem/many_aplusb",
+                "online-judge-tools 11.5.1",
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn fnv1a_test() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn remove_unused_dirs_test() {
+        let root = env::temp_dir()
+            .join("oj_test_remove_unused_dirs_test")
+            .join(std::process::id().to_string());
+        for name in ["a", "b", "c"] {
+            fs::create_dir_all(root.join(name)).unwrap();
+        }
+        fs::write(root.join("file"), "").unwrap();
+        let keep = BTreeSet::from([root.join("b")]);
+        assert_eq!(
+            remove_unused_dirs(&root, &keep).unwrap(),
+            vec![root.join("a"), root.join("c")]
+        );
+        assert!(root.join("b").exists());
+        assert!(root.join("file").exists());
+        fs::remove_dir_all(&root).unwrap();
+        assert_eq!(
+            remove_unused_dirs(&root, &keep).unwrap(),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn is_complete_test() {
+        let dir = env::temp_dir()
+            .join("oj_test_is_complete_test")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(&dir).unwrap();
+        assert!(!is_complete(&dir));
+        fs::write(dir.join("sample-1.in"), "1\n").unwrap();
+        // 途中で失敗したダウンロード
+        assert!(!is_complete(&dir));
+        fs::write(dir.join(COMPLETE_MARKER), "").unwrap();
+        assert!(is_complete(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!is_complete(&dir));
+    }
+}
@@ -1,12 +1,40 @@
-use std::collections::HashMap;
 use std::env;
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 
-use anyhow::{ensure, Result};
-use log::info;
+use anyhow::{ensure, Context, Result};
+use log::{info, warn};
+
+mod benchmark;
+mod bundle;
+mod changed;
+mod checker;
+mod coverage;
+mod download;
+mod library_checker;
+mod report;
+mod scaffold;
+mod snippet;
+mod stress;
+mod watch;
+
+pub use benchmark::{Baseline, Regression};
+pub use bundle::bundle;
+pub use changed::affected_package_dirs;
+pub use checker::prepare_checker;
+pub use coverage::{coverage_markdown, verification_coverage};
+pub use download::{
+    cache_root, download_online_judge_testcase, gc_testcase_cache, oj_version, testcase_cache_dir,
+};
+pub use report::{
+    summary_json, summary_markdown, SolverSummary, TestReport, TestcaseResult, Verdict,
+};
+pub use scaffold::new_example;
+pub use snippet::snippets;
+pub use stress::{stress_test, StressFailure};
+pub use watch::{affected_solvers, FileWatcher};
 
 pub struct ProblemSolver {
     solver_path: PathBuf,
@@ -31,7 +59,31 @@ impl ProblemSolver {
         self.solver_path.as_path()
     }
 
-    pub fn run(&self, testcase_dir: &Path) -> Result<()> {
+    /// クレートの `tests/<解答の名前>/` に置いた手書きのテストケース (`.in` と `.out`) のディレクトリを返します。
+    pub fn local_testcase_dir(&self) -> Option<PathBuf> {
+        let crate_dir = self.solver_path.parent()?.parent()?;
+        let dir = crate_dir.join("tests").join(self.solver_path.file_stem()?);
+        dir.is_dir().then_some(dir)
+    }
+
+    /// ダウンロードしたテストケースに手書きのテストケースを合わせたディレクトリを返します。手書きのテストケースが無ければ `testcase_dir` のままです。
+    ///
+    /// 名前がぶつからないように、手書きのテストケースには `local-` を付けます。
+    pub fn with_local_testcases(&self, testcase_dir: &Path) -> Result<PathBuf> {
+        let Some(local_dir) = self.local_testcase_dir() else {
+            return Ok(testcase_dir.to_path_buf());
+        };
+        // ダウンロードしたディレクトリはキャッシュなので、別のディレクトリにまとめる
+        let merged_dir = env::temp_dir().join("oj_test_merged").join(format!(
+            "{}-{}",
+            self.solver_path.file_stem().unwrap().to_string_lossy(),
+            testcase_dir.file_name().unwrap().to_string_lossy()
+        ));
+        merge_testcases(testcase_dir, &local_dir, &merged_dir)?;
+        Ok(merged_dir)
+    }
+
+    pub fn run(&self, testcase_dir: &Path) -> Result<TestReport> {
         let mut oj_command = Command::new("oj");
         oj_command
             .arg("test")
@@ -46,56 +98,159 @@ impl ProblemSolver {
 
         // special judge
         // for problem which has multiple answers
-        if let Some(judge_program_path) = self.judge_program_path() {
+        // checker_cpp と judge_program_rs を両方指定したときは、チェッカーを用意できなければ (ダウンロードやコンパイルに失敗したら) 判定プログラムを使う
+        let checker = match self.checker().map(|c| prepare_checker(&c, false)) {
+            Some(Ok(checker)) => Some(checker),
+            Some(Err(err)) if self.judge_program_path().is_some() => {
+                warn!("{:#}, use judge_program_rs instead", err);
+                None
+            }
+            Some(Err(err)) => return Err(err),
+            None => None,
+        };
+        // testlib 形式のチェッカーも oj と同じく 入力 出力 想定解 の順に引数を受け取る
+        if let Some(checker) = checker {
+            oj_command.arg("--judge-command").arg(checker);
+        } else if let Some(judge_program_path) = self.judge_program_path() {
             oj_command
                 .arg("--judge-command")
                 .arg(example_binary_path(judge_program_path.as_path()));
         }
 
-        info!("execute {:?}", oj_command);
-        let status = oj_command.status()?;
-        ensure!(status.success(), "failed: oj test");
+        // 浮動小数点数の出力を誤差を許して比べる
+        if let Some(error) = self.error()? {
+            oj_command.arg("--error").arg(error.to_string());
+        }
 
-        Ok(())
+        // 参照解が遅すぎて CI が止まらないようにする
+        if let Some(timeout) = self.timeout()? {
+            oj_command.arg("--tle").arg(timeout.to_string());
+        }
+
+        oj_command
+            .arg("--json")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit());
+
+        info!("execute {:?}", oj_command);
+        let output = oj_command.output().context("failed to execute oj")?;
+        // 不正解のときも oj test は失敗するので、結果を読めたらそれを返す
+        match TestReport::parse_oj_json(&String::from_utf8_lossy(&output.stdout)) {
+            Ok(report) => Ok(report),
+            Err(err) => {
+                ensure!(output.status.success(), "failed: oj test");
+                Err(err)
+            }
+        }
     }
 
+    /// 最初に指定した問題の URL を返します。
     pub fn problem_url(&self) -> Option<&str> {
-        self.test_property.get("problem")
+        self.problem_urls().first().copied()
     }
 
-    fn judge_program_path(&self) -> Option<PathBuf> {
+    /// `// problem:` (何度書いてもよい) と `// problem2:`, `// problem3:`, ... で指定した問題の URL を、書いた順に返します。
+    pub fn problem_urls(&self) -> Vec<&str> {
+        let mut urls = Vec::new();
+        for (key, url) in &self.test_property.properties {
+            let is_problem = key
+                .strip_prefix("problem")
+                .map_or(false, |n| n.chars().all(|c| c.is_ascii_digit()));
+            if is_problem && !urls.contains(&url.as_str()) {
+                urls.push(url.as_str());
+            }
+        }
+        urls
+    }
+
+    /// `// problem_revision: 2` で指定した問題の改訂を返します。ジャッジのテストケースが更新されたときに変えると、キャッシュを使わずにダウンロードし直します。
+    pub fn problem_revision(&self) -> Option<&str> {
+        self.test_property.get("problem_revision")
+    }
+
+    /// `// tags: graph, shortest-path` で指定したタグを返します。
+    pub fn tags(&self) -> Vec<&str> {
+        self.test_property.get("tags").map_or(Vec::new(), |tags| {
+            tags.split(',')
+                .map(|t| t.trim())
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+    }
+
+    /// `// timeout: 5` で指定した、テストケースごとの制限時間 (秒) を返します。
+    pub fn timeout(&self) -> Result<Option<f64>> {
+        self.f64_property("timeout")
+    }
+
+    /// `// error: 1e-6` で指定した、出力の浮動小数点数に許す誤差 (絶対誤差または相対誤差) を返します。
+    pub fn error(&self) -> Result<Option<f64>> {
+        self.f64_property("error")
+    }
+
+    fn f64_property(&self, key: &str) -> Result<Option<f64>> {
         self.test_property
-            .get("judge_program_rs")
-            .map(|judge_program_rs| self.solver_path.parent().unwrap().join(judge_program_rs))
+            .get(key)
+            .map(|value| {
+                value
+                    .parse::<f64>()
+                    .with_context(|| format!("invalid {}: {}", key, value))
+            })
+            .transpose()
     }
-}
 
-pub fn download_online_judge_testcase(problem_url: &str, dir_suffix: &Path) -> Result<PathBuf> {
-    let dir = env::temp_dir().join(dir_suffix);
-    if dir.exists() {
-        fs::remove_dir_all(&dir).unwrap_or_else(|err| panic!("{}", err));
+    fn judge_program_path(&self) -> Option<PathBuf> {
+        self.relative_path_property("judge_program_rs")
+    }
+
+    // URL はそのまま、ファイルは解答のファイルからの相対パス
+    fn checker(&self) -> Option<String> {
+        let checker = self.test_property.get("checker_cpp")?;
+        if checker::is_url(checker) {
+            Some(checker.to_string())
+        } else {
+            self.relative_path_property("checker_cpp")
+                .map(|path| path.display().to_string())
+        }
+    }
+
+    /// `checker_cpp` に URL が指定されていれば、チェッカーをダウンロードし直してコンパイルします。
+    pub fn refresh_checker(&self) -> Result<()> {
+        if let Some(checker) = self.checker().filter(|c| checker::is_url(c)) {
+            prepare_checker(&checker, true)?;
+        }
+        Ok(())
+    }
+
+    fn generator_path(&self) -> Option<PathBuf> {
+        self.relative_path_property("generator_rs")
+    }
+
+    fn naive_path(&self) -> Option<PathBuf> {
+        self.relative_path_property("naive_rs")
+    }
+
+    /// ランダムテスト用のジェネレータと愚直解が指定されているかを返します。
+    pub fn has_stress_test(&self) -> bool {
+        self.generator_path().is_some() && self.naive_path().is_some()
+    }
+
+    // 解答のファイルからの相対パス
+    fn relative_path_property(&self, key: &str) -> Option<PathBuf> {
+        self.test_property
+            .get(key)
+            .map(|path| self.solver_path.parent().unwrap().join(path))
     }
-    let mut oj_command = Command::new("oj");
-    oj_command
-        .arg("download")
-        .arg(problem_url)
-        .arg("--directory")
-        .arg(dir.as_os_str())
-        .arg("--system")
-        .arg("--silent");
-    info!("execute {:?}", oj_command);
-    let status = oj_command.status()?;
-    ensure!(status.success(), "failed: oj download");
-    Ok(dir)
 }
 
 struct TestProperty {
-    properties: HashMap<String, String>,
+    // 同じキーを何度も書けるように、書いた順に持つ
+    properties: Vec<(String, String)>,
 }
 
 impl TestProperty {
     fn new(solver_source_code: &str) -> Self {
-        let mut properties = HashMap::new();
+        let mut properties = Vec::new();
         for l in solver_source_code.lines() {
             let v: Vec<&str> = l.splitn(2, ':').map(|t| t.trim()).collect();
             if v.len() != 2 {
@@ -105,7 +260,7 @@ impl TestProperty {
                 continue;
             }
             let key = v[0].trim_start_matches('/').trim();
-            properties.insert(key.to_string(), v[1].to_string());
+            properties.push((key.to_string(), v[1].to_string()));
         }
         Self { properties }
     }
@@ -115,15 +270,162 @@ impl TestProperty {
         Self::new(&source_code)
     }
 
+    // 同じキーが何度もあるときは最初の値
     fn get(&self, key: &str) -> Option<&str> {
-        self.properties.get(key).map(|s| s.as_str())
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn merge_testcases(downloaded_dir: &Path, local_dir: &Path, merged_dir: &Path) -> Result<()> {
+    if merged_dir.exists() {
+        fs::remove_dir_all(merged_dir)?;
+    }
+    fs::create_dir_all(merged_dir)?;
+    for (dir, prefix) in [(downloaded_dir, ""), (local_dir, "local-")] {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_testcase = path
+                .extension()
+                .map_or(false, |ext| ext == "in" || ext == "out");
+            if is_testcase {
+                let file_name = path.file_name().unwrap().to_string_lossy();
+                fs::copy(&path, merged_dir.join(format!("{}{}", prefix, file_name)))?;
+            }
+        }
     }
+    Ok(())
+}
+
+/// `--only` で指定できるジャッジの名前です。
+pub const JUDGE_NAMES: [&str; 4] = ["atcoder", "yukicoder", "library-checker", "aoj"];
+
+/// 問題の URL からジャッジの名前 ([`JUDGE_NAMES`] のどれか) を返します。知らないジャッジなら `None` です。
+pub fn judge_name(problem_url: &str) -> Option<&'static str> {
+    let host = problem_url
+        .split_once("://")
+        .map_or(problem_url, |(_, rest)| rest)
+        .split('/')
+        .next()?;
+    match host {
+        "atcoder.jp" => Some("atcoder"),
+        "yukicoder.me" => Some("yukicoder"),
+        "judge.yosupo.jp" => Some("library-checker"),
+        "judge.u-aizu.ac.jp" | "onlinejudge.u-aizu.ac.jp" => Some("aoj"),
+        _ => None,
+    }
+}
+
+/// クレートの `verify/` に置いた解答が、そのクレートの `Cargo.toml` で example として宣言されているかを確かめます。
+///
+/// cargo が自動で見つけるのは `examples/` だけなので、`verify/` の解答は次のように宣言する必要があります。
+///
+/// ```toml
+/// [[example]]
+/// name = "ntl_1_a"
+/// path = "verify/ntl_1_a.rs"
+/// ```
+pub fn ensure_declared_as_example(solver_path: &Path) -> Result<()> {
+    let file_name = solver_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .context("invalid solver path")?;
+    let stem = solver_path.file_stem().and_then(|s| s.to_str()).unwrap();
+    let crate_dir = solver_path
+        .parent()
+        .and_then(|p| p.parent())
+        .context("invalid solver path")?;
+    let manifest = fs::read_to_string(crate_dir.join("Cargo.toml"))?;
+    let path = format!("verify/{}", file_name);
+    // name と path は同じ [[example]] に書かれている必要がある
+    let declared = example_tables(&manifest).iter().any(|example| {
+        example.name.as_deref() == Some(stem) && example.path.as_deref() == Some(path.as_str())
+    });
+    ensure!(
+        declared,
+        "{:?} is not declared in {:?}: add [[example]] with name = \"{}\" and path = \"verify/{}\"",
+        solver_path,
+        crate_dir.join("Cargo.toml"),
+        stem,
+        file_name
+    );
+    Ok(())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ExampleTable {
+    name: Option<String>,
+    path: Option<String>,
+}
+
+// Cargo.toml の [[example]] を読む
+fn example_tables(manifest: &str) -> Vec<ExampleTable> {
+    let mut examples = Vec::new();
+    let mut in_example = false;
+    for line in manifest.lines() {
+        let line = strip_toml_comment(line).trim();
+        if line.starts_with('[') {
+            in_example = line == "[[example]]";
+            if in_example {
+                examples.push(ExampleTable::default());
+            }
+            continue;
+        }
+        let (true, Some(example), Some((key, value))) =
+            (in_example, examples.last_mut(), line.split_once('='))
+        else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => example.name = Some(value),
+            "path" => example.path = Some(value),
+            _ => {}
+        }
+    }
+    examples
+}
+
+// 文字列の外にある # から行末までを取り除く
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// `source_path` にある example をリリースビルドします。
+pub fn build_example(source_path: &Path) -> Result<()> {
+    let name = source_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .context("invalid example path")?;
+    let mut cargo_command = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo_command
+        .arg("build")
+        .arg("--release")
+        .arg("--example")
+        .arg(name);
+    info!("execute {:?}", cargo_command);
+    let status = cargo_command.status()?;
+    ensure!(status.success(), "failed: cargo build --example {}", name);
+    Ok(())
+}
+
+/// ワークスペースのルートディレクトリを返します。
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..")
 }
 
 fn cargo_target_examples_dir() -> PathBuf {
-    Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("..")
-        .join("..")
+    workspace_root()
         .join("target")
         .join("release")
         .join("examples")
@@ -141,7 +443,33 @@ fn example_binary_path(source_path: &Path) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use crate::TestProperty;
+    use std::path::PathBuf;
+
+    use std::env;
+    use std::fs;
+
+    use crate::{judge_name, merge_testcases, ProblemSolver, TestProperty};
+
+    #[test]
+    fn judge_name_test() {
+        assert_eq!(
+            judge_name("https://atcoder.jp/contests/abc100/tasks/abc100_a"),
+            Some("atcoder")
+        );
+        assert_eq!(
+            judge_name("https://yukicoder.me/problems/no/1"),
+            Some("yukicoder")
+        );
+        assert_eq!(
+            judge_name("https://judge.yosupo.jp/problem/aplusb"),
+            Some("library-checker")
+        );
+        assert_eq!(
+            judge_name("https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_3_A"),
+            Some("aoj")
+        );
+        assert_eq!(judge_name("https://example.com/atcoder.jp"), None);
+    }
 
     #[test]
     fn parse_property_test() {
@@ -151,6 +479,12 @@ mod tests {
 // problem4 : https://example4.com
 
 // judge_program_rs: ./my_judge.rs
+// checker_cpp: https://example.com/checker.cpp
+// timeout: 2.5
+// error: 1e-6
+// generator_rs: ./gen.rs
+// naive_rs: ./naive.rs
+// tags: graph, shortest-path
 fn main() {
 // return;
 }"#;
@@ -160,6 +494,130 @@ fn main() {
         assert_eq!(property.get("problem3"), Some("https://example3.com"));
         assert_eq!(property.get("problem4"), Some("https://example4.com"));
         assert_eq!(property.get("judge_program_rs"), Some("./my_judge.rs"));
+        assert_eq!(
+            property.get("checker_cpp"),
+            Some("https://example.com/checker.cpp")
+        );
+        assert_eq!(property.get("timeout"), Some("2.5"));
+        assert_eq!(property.get("error"), Some("1e-6"));
+        assert_eq!(property.get("generator_rs"), Some("./gen.rs"));
+        assert_eq!(property.get("naive_rs"), Some("./naive.rs"));
+        assert_eq!(property.get("tags"), Some("graph, shortest-path"));
         assert_eq!(property.get("return"), None);
     }
+
+    #[test]
+    fn merge_testcases_test() {
+        let dir = env::temp_dir()
+            .join("oj_test_merge_testcases_test")
+            .join(std::process::id().to_string());
+        let (downloaded, local, merged) = (dir.join("d"), dir.join("l"), dir.join("m"));
+        fs::create_dir_all(&downloaded).unwrap();
+        fs::create_dir_all(&local).unwrap();
+        fs::write(downloaded.join("1.in"), "1").unwrap();
+        fs::write(downloaded.join("1.out"), "2").unwrap();
+        fs::write(downloaded.join(".oj_test_complete"), "").unwrap();
+        fs::write(local.join("1.in"), "3").unwrap();
+        fs::write(local.join("1.out"), "6").unwrap();
+        fs::write(local.join("memo.txt"), "").unwrap();
+        // 前回の結果は消す
+        fs::create_dir_all(&merged).unwrap();
+        fs::write(merged.join("old.in"), "").unwrap();
+
+        merge_testcases(&downloaded, &local, &merged).unwrap();
+        let mut files = fs::read_dir(&merged)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, vec!["1.in", "1.out", "local-1.in", "local-1.out"]);
+        assert_eq!(fs::read_to_string(merged.join("local-1.out")).unwrap(), "6");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn local_testcase_dir_test() {
+        let root = crate::workspace_root();
+        let solver = ProblemSolver::new(
+            &root.join("tool/oj_test_examples/simple_example/examples/aplusb.rs"),
+        );
+        assert_eq!(
+            solver.local_testcase_dir(),
+            Some(root.join("tool/oj_test_examples/simple_example/tests/aplusb"))
+        );
+        let solver = ProblemSolver::new(&root.join("algo/union_find/examples/unionfind.rs"));
+        assert_eq!(solver.local_testcase_dir(), None);
+    }
+
+    #[test]
+    fn problem_urls_test() {
+        let solver = |source_code: &str| ProblemSolver {
+            solver_path: PathBuf::from("a.rs"),
+            test_property: TestProperty::new(source_code),
+        };
+        let s = solver(
+            r#"// problem: https://judge.yosupo.jp/problem/unionfind
+// problem: https://atcoder.jp/contests/practice2/tasks/practice2_a
+// problem3: https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_1_A
+// problem_revision: 2
+// problem: https://judge.yosupo.jp/problem/unionfind
+"#,
+        );
+        assert_eq!(
+            s.problem_urls(),
+            vec![
+                "https://judge.yosupo.jp/problem/unionfind",
+                "https://atcoder.jp/contests/practice2/tasks/practice2_a",
+                "https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_1_A"
+            ]
+        );
+        assert_eq!(
+            s.problem_url(),
+            Some("https://judge.yosupo.jp/problem/unionfind")
+        );
+        assert_eq!(solver("fn main() {}").problem_url(), None);
+    }
+
+    #[test]
+    fn verify_solver_declared_test() {
+        let workspace_root = crate::workspace_root();
+        let declared = workspace_root
+            .join("algo")
+            .join("prime_factorization")
+            .join("verify")
+            .join("ntl_1_a.rs");
+        assert!(crate::ensure_declared_as_example(&declared).is_ok());
+        let undeclared = declared.with_file_name("undeclared.rs");
+        assert!(crate::ensure_declared_as_example(&undeclared).is_err());
+    }
+
+    #[test]
+    fn example_tables_test() {
+        let manifest = r##"[package]
+name = "ntl_1_a" # package
+
+[[example]]
+name = "ntl_1_a"
+# path = "verify/ntl_1_a.rs"
+path = "verify/other.rs"
+
+[[example]]
+name = "other"
+path = "verify/ntl_1_a.rs" # "#"
+
+[dev-dependencies]
+path = "x"
+"##;
+        let example = |name: &str, path: &str| crate::ExampleTable {
+            name: Some(name.to_string()),
+            path: Some(path.to_string()),
+        };
+        assert_eq!(
+            crate::example_tables(manifest),
+            vec![
+                example("ntl_1_a", "verify/other.rs"),
+                example("other", "verify/ntl_1_a.rs")
+            ]
+        );
+    }
 }
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use glob::glob;
+use log::info;
+
+use crate::download::cache_root;
+
+const REPOSITORY_URL: &str = "https://github.com/yosupo06/library-checker-problems";
+
+/// Library Checker の問題の URL から問題の名前を返します。
+pub(crate) fn problem_name(problem_url: &str) -> Option<&str> {
+    let name = problem_url
+        .strip_prefix("https://judge.yosupo.jp/problem/")?
+        .trim_end_matches('/');
+    (!name.is_empty() && !name.contains('/')).then_some(name)
+}
+
+pub(crate) fn repository_dir() -> PathBuf {
+    cache_root().join("library-checker-problems")
+}
+
+/// library-checker-problems を clone (すでにあれば pull) して、その生成器で作った問題 `name` のテストケースを `dir` にコピーします。
+///
+/// `oj download` が失敗したときの代わりです。生成には python3 と C++ コンパイラ、library-checker-problems の `requirements.txt` のパッケージが要ります。
+pub(crate) fn generate_testcases(name: &str, dir: &Path) -> Result<()> {
+    let repository = update_repository()?;
+    let mut command = Command::new("python3");
+    command
+        .current_dir(&repository)
+        .arg("generate.py")
+        .arg("-p")
+        .arg(name);
+    info!("execute {:?}", command);
+    let status = command.status().context("failed to execute python3")?;
+    ensure!(status.success(), "failed: generate.py -p {}", name);
+
+    let problem_dir = find_problem_dir(&repository, name)?;
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    for (sub_dir, ext) in [("in", "in"), ("out", "out")] {
+        for entry in fs::read_dir(problem_dir.join(sub_dir))? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |e| e == ext) {
+                fs::copy(&path, dir.join(path.file_name().unwrap()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// 何度も pull しないように、1 回の実行で 1 回だけ更新する
+fn update_repository() -> Result<PathBuf> {
+    static REPOSITORY: OnceLock<std::result::Result<PathBuf, String>> = OnceLock::new();
+    REPOSITORY
+        .get_or_init(|| {
+            let dir = repository_dir();
+            let mut command = Command::new("git");
+            if dir.join(".git").exists() {
+                command
+                    .arg("-C")
+                    .arg(&dir)
+                    .arg("pull")
+                    .arg("--ff-only")
+                    .arg("--quiet");
+            } else {
+                command
+                    .arg("clone")
+                    .arg("--depth")
+                    .arg("1")
+                    .arg("--quiet")
+                    .arg(REPOSITORY_URL)
+                    .arg(&dir);
+            }
+            info!("execute {:?}", command);
+            match command.status() {
+                Ok(status) if status.success() => Ok(dir),
+                Ok(_) => Err(format!("failed: {:?}", command)),
+                Err(err) => Err(format!("failed to execute git: {}", err)),
+            }
+        })
+        .clone()
+        .map_err(|err| anyhow!(err))
+}
+
+// 問題は <カテゴリ>/<名前>/info.toml にある
+fn find_problem_dir(repository: &Path, name: &str) -> Result<PathBuf> {
+    let pattern = repository.join("*").join(name).join("info.toml");
+    let info = glob(&pattern.to_string_lossy())?
+        .next()
+        .with_context(|| format!("problem not found in library-checker-problems: {}", name))??;
+    Ok(info.parent().unwrap().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::{find_problem_dir, problem_name};
+
+    #[test]
+    fn problem_name_test() {
+        assert_eq!(
+            problem_name("https://judge.yosupo.jp/problem/unionfind"),
+            Some("unionfind")
+        );
+        assert_eq!(
+            problem_name("https://judge.yosupo.jp/problem/scc/"),
+            Some("scc")
+        );
+        assert_eq!(problem_name("https://judge.yosupo.jp/problem/"), None);
+        assert_eq!(
+            problem_name("https://atcoder.jp/contests/abc100/tasks/abc100_a"),
+            None
+        );
+    }
+
+    #[test]
+    fn find_problem_dir_test() {
+        let repository = env::temp_dir()
+            .join("oj_test_find_problem_dir_test")
+            .join(std::process::id().to_string());
+        fs::create_dir_all(repository.join("graph").join("scc")).unwrap();
+        fs::write(repository.join("graph").join("scc").join("info.toml"), "").unwrap();
+        assert_eq!(
+            find_problem_dir(&repository, "scc").unwrap(),
+            repository.join("graph").join("scc")
+        );
+        assert!(find_problem_dir(&repository, "unionfind").is_err());
+        fs::remove_dir_all(&repository).unwrap();
+    }
+}
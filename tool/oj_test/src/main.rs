@@ -1,32 +1,425 @@
-use anyhow::Result;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{bail, ensure, Context, Result};
 use glob::glob;
-use log::info;
+use log::{error, info, warn};
+
+use oj_test::{
+    affected_package_dirs, affected_solvers, build_example, bundle, cache_root, coverage_markdown,
+    download_online_judge_testcase, ensure_declared_as_example, gc_testcase_cache, judge_name,
+    new_example, oj_version, snippets, stress_test, summary_json, summary_markdown,
+    testcase_cache_dir, verification_coverage, workspace_root, Baseline, FileWatcher,
+    ProblemSolver, SolverSummary, JUDGE_NAMES,
+};
 
-use oj_test::{download_online_judge_testcase, ProblemSolver};
+const USAGE: &str = "usage:
+    oj_test [--report-json FILE] [--report-markdown FILE] [--changed-since GIT_REF] [--refresh URL|all]
+            [--only atcoder|yukicoder|library-checker|aoj] [--tag TAG]
+            [--baseline FILE [--update-baseline] [--regression-threshold RATIO] [--fail-on-regression]]
+                                             verify all solvers with online judge testcases
+    oj_test --watch [--changed-since GIT_REF] [--only JUDGE] [--tag TAG]
+                                             re-verify solvers affected by file changes
+    oj_test stress [--cases N] [SOLVER...]   compare solvers with naive solutions on random inputs
+    oj_test bundle SOLVER                    print SOLVER with the workspace crates it uses inlined
+    oj_test snippet                          print the library crates as VSCode snippets (JSON)
+    oj_test gc                               remove cached testcases no solver uses
+    oj_test coverage [--deny-uncovered]     list the library crates and the solvers verifying them
+    oj_test new-example [--name NAME] URL CRATE...
+                                             create a solver for URL in the examples of the first CRATE";
 
 fn main() -> Result<()> {
     env_logger::init();
 
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(|a| a.as_str()) {
+        Some("stress") => stress(&args[1..]),
+        Some("bundle") => {
+            ensure!(args.len() == 2, "{}", USAGE);
+            print!("{}", bundle(Path::new(&args[1]), &workspace_root())?);
+            Ok(())
+        }
+        Some("gc") => gc(),
+        Some("coverage") => coverage(&args[1..]),
+        Some("new-example") => scaffold(&args[1..]),
+        Some("snippet") => {
+            println!("{}", snippets(&workspace_root())?);
+            Ok(())
+        }
+        Some("-h" | "--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => verify(&args),
+    }
+}
+
+fn collect_solvers() -> Result<Vec<ProblemSolver>> {
     let mut solvers = Vec::new();
     for entry in glob("**/examples/*.rs")? {
         let path = entry?;
-        // oj download に失敗するのでスキップ
-        if path.ends_with("scc.rs") || path.ends_with("cycle_detection.rs") {
-            continue;
-        }
+        solvers.push(ProblemSolver::new(path.as_path()));
+    }
+    // ライブラリのクレートが持っている検証用の解答
+    for entry in glob("algo/*/verify/*.rs")? {
+        let path = entry?;
+        ensure_declared_as_example(path.as_path())?;
         solvers.push(ProblemSolver::new(path.as_path()));
     }
     solvers.sort_by(|s1, s2| s1.solver_path().cmp(s2.solver_path()));
+    Ok(solvers)
+}
+
+fn verify(args: &[String]) -> Result<()> {
+    let mut report_json = None;
+    let mut report_markdown = None;
+    let mut changed_since = None;
+    let mut refresh = Vec::new();
+    let mut only = Vec::new();
+    let mut tags = Vec::new();
+    let mut baseline_path = None;
+    let mut update_baseline = false;
+    let mut regression_threshold = 1.5;
+    let mut fail_on_regression = false;
+    let mut watch = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--report-json" => {
+                report_json = Some(args.next().context("--report-json requires a path")?);
+            }
+            "--report-markdown" => {
+                report_markdown = Some(args.next().context("--report-markdown requires a path")?);
+            }
+            "--refresh" => {
+                refresh.push(args.next().context("--refresh requires a problem URL")?);
+            }
+            "--only" => {
+                let judge = args.next().context("--only requires a judge")?;
+                ensure!(
+                    JUDGE_NAMES.contains(&judge.as_str()),
+                    "unknown judge: {} (expected one of {})",
+                    judge,
+                    JUDGE_NAMES.join(", ")
+                );
+                only.push(judge.as_str());
+            }
+            "--tag" => {
+                tags.push(args.next().context("--tag requires a tag")?.as_str());
+            }
+            "--baseline" => {
+                baseline_path = Some(args.next().context("--baseline requires a path")?);
+            }
+            "--update-baseline" => update_baseline = true,
+            "--regression-threshold" => {
+                let r = args
+                    .next()
+                    .context("--regression-threshold requires a value")?;
+                regression_threshold = r
+                    .parse()
+                    .with_context(|| format!("invalid --regression-threshold: {}", r))?;
+            }
+            "--fail-on-regression" => fail_on_regression = true,
+            "--watch" => watch = true,
+            "--changed-since" => {
+                changed_since = Some(args.next().context("--changed-since requires a git ref")?);
+            }
+            _ => bail!("unknown argument: {}\n{}", arg, USAGE),
+        }
+    }
+
+    // 監視中は 1 回の検証ごとにまとめる結果が無いので、結果を書き出すオプションは使えない
+    if watch {
+        let ignored = [
+            ("--report-json", report_json.is_some()),
+            ("--report-markdown", report_markdown.is_some()),
+            ("--baseline", baseline_path.is_some()),
+            ("--refresh", !refresh.is_empty()),
+        ];
+        if let Some((option, _)) = ignored.iter().find(|(_, given)| *given) {
+            bail!("{} cannot be used with --watch", option);
+        }
+    }
+
+    // 基準値のファイルが無いことに検証した後で気づかないように、先に読む
+    let baseline = match baseline_path {
+        Some(path) if Path::new(path).exists() => Some(
+            Baseline::parse(&fs::read_to_string(path)?)
+                .with_context(|| format!("invalid baseline: {}", path))?,
+        ),
+        Some(path) => {
+            // 空の基準値と比べても遅くなったテストケースは見つからないので、作るとき以外はエラーにする
+            ensure!(
+                update_baseline,
+                "baseline not found: {} (create it with --update-baseline)",
+                path
+            );
+            warn!("baseline not found: {}, create a new one", path);
+            Some(Baseline::default())
+        }
+        None => None,
+    };
+
+    let mut solvers = collect_solvers()?;
+    // --only や --tag を複数指定したときは、どれかに当てはまる解答を検証する
+    if !tags.is_empty() {
+        solvers.retain(|s| s.tags().iter().any(|t| tags.contains(t)));
+    }
+    if let Some(git_ref) = changed_since {
+        // 変更の影響を受けるパッケージの解答だけを検証する
+        if let Some(dirs) = affected_package_dirs(git_ref)? {
+            let dirs = dirs
+                .iter()
+                .map(|d| d.canonicalize())
+                .collect::<Result<Vec<_>, _>>()?;
+            solvers.retain(|s| {
+                s.solver_path()
+                    .canonicalize()
+                    .map_or(false, |p| dirs.iter().any(|d| p.starts_with(d)))
+            });
+            info!(
+                "{} solver(s) affected by changes since {}",
+                solvers.len(),
+                git_ref
+            );
+        }
+    }
+
+    let oj_version = if solvers.iter().any(|s| s.problem_url().is_some()) {
+        oj_version()?
+    } else {
+        String::new()
+    };
+    if watch {
+        return watch_solvers(&solvers, &only, &oj_version);
+    }
+    let mut summaries = Vec::new();
+    for s in &solvers {
+        summaries.extend(verify_solver(s, &only, &oj_version, &refresh));
+    }
+
+    if let Some(path) = report_json {
+        fs::write(path, summary_json(&summaries))?;
+    }
+    if let Some(path) = report_markdown {
+        fs::write(path, summary_markdown(&summaries))?;
+    }
+
+    // 実行時間を基準値と比べる
+    let mut regressions = Vec::new();
+    if let (Some(path), Some(mut baseline)) = (baseline_path, baseline) {
+        regressions = baseline.regressions(&summaries, regression_threshold);
+        for r in &regressions {
+            warn!(
+                "{} {}: {:.3}s -> {:.3}s",
+                r.solver, r.testcase, r.baseline, r.elapsed
+            );
+        }
+        if update_baseline {
+            baseline.update(&summaries);
+            fs::write(path, baseline.to_json())?;
+        }
+    }
+
+    // TLE と WA などを分けて表示する
+    let mut failures = summaries
+        .iter()
+        .filter(|s| !s.is_accepted())
+        .collect::<Vec<_>>();
+    failures.sort_by_key(|s| s.status());
+    for s in &failures {
+        error!("{}: {:?} ({})", s.status(), s.solver_path, s.problem_url);
+    }
+    ensure!(failures.is_empty(), "{} solver(s) failed", failures.len());
+    ensure!(
+        !fail_on_regression || regressions.is_empty(),
+        "{} testcase(s) are more than {} times slower than the baseline",
+        regressions.len(),
+        regression_threshold
+    );
+
+    Ok(())
+}
+
+// ひとつ失敗しても残りの解答は検証できるように、エラーも SolverSummary にして返す
+fn verify_solver(
+    s: &ProblemSolver,
+    only: &[&str],
+    oj_version: &str,
+    refresh: &[&String],
+) -> Vec<SolverSummary> {
+    let problem_urls = s
+        .problem_urls()
+        .into_iter()
+        .filter(|url| only.is_empty() || judge_name(url).map_or(false, |j| only.contains(&j)))
+        .collect::<Vec<_>>();
+    if problem_urls.is_empty() {
+        info!("skip {}", s);
+    }
+    let mut summaries = Vec::new();
+    // 複数の問題があれば、それぞれのテストケースで検証する
+    for problem_url in problem_urls {
+        let testcase_dir = testcase_cache_dir(problem_url, oj_version, s.problem_revision());
+        let refresh = refresh
+            .iter()
+            .any(|url| *url == problem_url || *url == "all");
+        let result = download_online_judge_testcase(problem_url, &testcase_dir, refresh)
+            .and_then(|testcase_dir| {
+                // テストケースと一緒にチェッカーも取り直す
+                if refresh {
+                    s.refresh_checker()?;
+                }
+                s.with_local_testcases(&testcase_dir)
+            })
+            .and_then(|testcase_dir| s.run(testcase_dir.as_path()))
+            .map_err(|err| format!("{:#}", err));
+        match &result {
+            Ok(report) => info!(
+                "{} ({}): {} ({} testcases, max {:.3}s)",
+                s,
+                problem_url,
+                report.verdict(),
+                report.testcases.len(),
+                report.max_elapsed()
+            ),
+            Err(err) => error!("{} ({}): {}", s, problem_url, err),
+        }
+        summaries.push(SolverSummary {
+            solver_path: s.solver_path().to_path_buf(),
+            problem_url: problem_url.to_string(),
+            result,
+        });
+    }
+    summaries
+}
+
+// ファイルが変更されるたびに、影響を受ける解答をビルドし直して検証する
+fn watch_solvers(solvers: &[ProblemSolver], only: &[&str], oj_version: &str) -> Result<()> {
+    let mut watcher = FileWatcher::new(&workspace_root())?;
+    info!("watching {} solver(s), press Ctrl-C to stop", solvers.len());
+    loop {
+        let changed = watcher.wait_for_changes(Duration::from_secs(1))?;
+        // 編集中に失敗しても監視は続ける
+        let affected = match affected_solvers(solvers, &changed) {
+            Ok(affected) => affected,
+            Err(err) => {
+                error!("{:#}", err);
+                continue;
+            }
+        };
+        info!(
+            "{} file(s) changed, {} solver(s) affected",
+            changed.len(),
+            affected.len()
+        );
+        for s in affected {
+            if let Err(err) = build_example(s.solver_path()) {
+                error!("{}: {:#}", s, err);
+                continue;
+            }
+            verify_solver(s, only, oj_version, &[]);
+        }
+    }
+}
+
+fn stress(args: &[String]) -> Result<()> {
+    let mut cases = 100;
+    let mut solvers = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--cases" {
+            let n = args.next().context("--cases requires a value")?;
+            cases = n
+                .parse()
+                .with_context(|| format!("invalid --cases: {}", n))?;
+        } else {
+            solvers.push(ProblemSolver::new(Path::new(arg)));
+        }
+    }
+    if solvers.is_empty() {
+        solvers = collect_solvers()?
+            .into_iter()
+            .filter(|s| s.has_stress_test())
+            .collect();
+    }
 
+    let mut failed = 0;
     for s in solvers {
-        if let Some(problem_url) = s.problem_url() {
-            let dir_suffix = s.solver_path().with_extension("");
-            let testcase_dir = download_online_judge_testcase(problem_url, dir_suffix.as_path())?;
-            s.run(testcase_dir.as_path())?;
+        if let Some(failure) = stress_test(&s, cases)? {
+            error!(
+                "{}: wrong answer on seed {} (input: {:?})\nexpected:\n{}\nactual:\n{}",
+                s, failure.seed, failure.input_path, failure.expected, failure.actual
+            );
+            failed += 1;
+        }
+    }
+    ensure!(failed == 0, "{} solver(s) failed", failed);
+
+    Ok(())
+}
+
+fn gc() -> Result<()> {
+    let oj_version = oj_version()?;
+    let keep = collect_solvers()?
+        .iter()
+        .flat_map(|s| {
+            s.problem_urls()
+                .into_iter()
+                .map(|url| testcase_cache_dir(url, &oj_version, s.problem_revision()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<BTreeSet<_>>();
+    let removed = gc_testcase_cache(&keep)?;
+    for path in &removed {
+        info!("remove {:?}", path);
+    }
+    info!("removed {} cache(s) in {:?}", removed.len(), cache_root());
+
+    Ok(())
+}
+
+fn scaffold(args: &[String]) -> Result<()> {
+    let mut name = None;
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--name" {
+            name = Some(args.next().context("--name requires a value")?.as_str());
         } else {
-            info!("skip {}", s);
+            rest.push(arg.clone());
+        }
+    }
+    ensure!(rest.len() >= 2, "{}", USAGE);
+    let path = new_example(&workspace_root(), &rest[0], &rest[1..], name)?;
+    info!("created {:?}", path);
+
+    Ok(())
+}
+
+fn coverage(args: &[String]) -> Result<()> {
+    let mut deny_uncovered = false;
+    for arg in args {
+        match arg.as_str() {
+            "--deny-uncovered" => deny_uncovered = true,
+            _ => bail!("unknown argument: {}\n{}", arg, USAGE),
         }
     }
+    let coverage = verification_coverage(&workspace_root(), &collect_solvers()?)?;
+    print!("{}", coverage_markdown(&coverage));
+    let uncovered = coverage
+        .iter()
+        .filter(|(_, solvers)| solvers.is_empty())
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    ensure!(
+        !deny_uncovered || uncovered.is_empty(),
+        "{} crate(s) are not verified: {}",
+        uncovered.len(),
+        uncovered.join(", ")
+    );
 
     Ok(())
 }
@@ -0,0 +1,308 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// テストケースの判定です。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer,
+    RuntimeError,
+    TimeLimitExceeded,
+    MemoryLimitExceeded,
+}
+
+impl Verdict {
+    fn from_oj_status(status: &str) -> Result<Self> {
+        let verdict = match status {
+            "AC" => Verdict::Accepted,
+            "WA" => Verdict::WrongAnswer,
+            "RE" => Verdict::RuntimeError,
+            "TLE" => Verdict::TimeLimitExceeded,
+            "MLE" => Verdict::MemoryLimitExceeded,
+            _ => bail!("unknown status: {}", status),
+        };
+        Ok(verdict)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Accepted => "AC",
+            Verdict::WrongAnswer => "WA",
+            Verdict::RuntimeError => "RE",
+            Verdict::TimeLimitExceeded => "TLE",
+            Verdict::MemoryLimitExceeded => "MLE",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+// oj と同じく AC や WA などの文字列にする
+impl Serialize for Verdict {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// ひとつのテストケースの結果です。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestcaseResult {
+    pub name: String,
+    pub verdict: Verdict,
+    /// 実行時間 (秒)
+    pub elapsed: f64,
+}
+
+/// `oj test` の結果です。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestReport {
+    pub testcases: Vec<TestcaseResult>,
+}
+
+impl TestReport {
+    /// `oj test --json` の出力を読みます。
+    pub fn parse_oj_json(s: &str) -> Result<Self> {
+        // JSON の前に oj のログが混ざることがあるので、最後の行だけ読む
+        let line = s
+            .lines()
+            .rev()
+            .find(|l| l.trim_start().starts_with('['))
+            .context("oj test output has no JSON")?;
+        let cases: Vec<OjTestcaseResult> = serde_json::from_str(line)?;
+        let mut testcases = Vec::new();
+        for case in cases {
+            testcases.push(TestcaseResult {
+                name: case.testcase.name,
+                verdict: Verdict::from_oj_status(&case.status)?,
+                elapsed: case.elapsed.unwrap_or(0.0),
+            });
+        }
+        testcases.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self { testcases })
+    }
+
+    /// 全体の判定を返します。AC でないテストケースがあれば、そのうち最も重い判定 (TLE は WA より重い) です。
+    pub fn verdict(&self) -> Verdict {
+        self.testcases
+            .iter()
+            .map(|t| t.verdict)
+            .max()
+            .unwrap_or(Verdict::Accepted)
+    }
+
+    /// 最大の実行時間 (秒) を返します。
+    pub fn max_elapsed(&self) -> f64 {
+        self.testcases.iter().map(|t| t.elapsed).fold(0.0, f64::max)
+    }
+}
+
+// oj test --json の出力のうち使う部分
+#[derive(Deserialize)]
+struct OjTestcaseResult {
+    testcase: OjTestcase,
+    status: String,
+    elapsed: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct OjTestcase {
+    name: String,
+}
+
+/// ひとつの解答の検証結果です。
+#[derive(Debug, Clone)]
+pub struct SolverSummary {
+    pub solver_path: PathBuf,
+    pub problem_url: String,
+    /// テストケースのダウンロードや実行に失敗したときはエラーメッセージ
+    pub result: std::result::Result<TestReport, String>,
+}
+
+impl SolverSummary {
+    /// 判定を返します。検証できなかったときは `ERROR` です。
+    pub fn status(&self) -> String {
+        match &self.result {
+            Ok(report) => report.verdict().to_string(),
+            Err(_) => "ERROR".to_string(),
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        matches!(&self.result, Ok(report) if report.verdict() == Verdict::Accepted)
+    }
+
+    fn to_json(&self) -> SolverSummaryJson<'_> {
+        let (testcases, max_elapsed, error) = match &self.result {
+            Ok(report) => (
+                Some(report.testcases.len()),
+                Some(report.max_elapsed()),
+                None,
+            ),
+            Err(err) => (None, None, Some(err.as_str())),
+        };
+        SolverSummaryJson {
+            solver: self.solver_path.display().to_string(),
+            problem: &self.problem_url,
+            verdict: self.status(),
+            testcases,
+            max_elapsed,
+            error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SummaryJson<'a> {
+    total: usize,
+    accepted: usize,
+    solvers: Vec<SolverSummaryJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct SolverSummaryJson<'a> {
+    solver: String,
+    problem: &'a str,
+    verdict: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    testcases: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_elapsed: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// 検証結果の一覧を JSON にします。
+pub fn summary_json(summaries: &[SolverSummary]) -> String {
+    let json = SummaryJson {
+        total: summaries.len(),
+        accepted: summaries.iter().filter(|s| s.is_accepted()).count(),
+        solvers: summaries.iter().map(SolverSummary::to_json).collect(),
+    };
+    serde_json::to_string(&json).unwrap()
+}
+
+/// 検証結果の一覧を Markdown の表にします。
+pub fn summary_markdown(summaries: &[SolverSummary]) -> String {
+    let accepted = summaries.iter().filter(|s| s.is_accepted()).count();
+    let mut result = format!(
+        "## Verification\n\n{} / {} solvers accepted\n\n| solver | problem | verdict | testcases | max time |\n| --- | --- | --- | ---: | ---: |\n",
+        accepted,
+        summaries.len()
+    );
+    for s in summaries {
+        let (testcases, max_elapsed) = match &s.result {
+            Ok(report) => (
+                report.testcases.len().to_string(),
+                format!("{:.3}s", report.max_elapsed()),
+            ),
+            Err(_) => ("-".to_string(), "-".to_string()),
+        };
+        result.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} |\n",
+            s.solver_path.display(),
+            s.problem_url,
+            s.status(),
+            testcases,
+            max_elapsed
+        ));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{
+        summary_json, summary_markdown, SolverSummary, TestReport, TestcaseResult, Verdict,
+    };
+    use serde_json::Value;
+
+    #[test]
+    fn parse_oj_json_test() {
+        let output = r#"[INFO] some log
+[{"status": "AC", "testcase": {"name": "b", "input": "b.in", "output": "b.out"}, "output": "2\n", "exitcode": 0, "elapsed": 0.5, "memory": 3.0},
+ {"status": "TLE", "testcase": {"name": "a", "input": "a.in"}, "output": null, "exitcode": null, "elapsed": 2.0, "memory": null}]
+"#
+        .replace("},\n {", "}, {");
+        let report = TestReport::parse_oj_json(&output).unwrap();
+        assert_eq!(report.testcases.len(), 2);
+        assert_eq!(report.testcases[0].name, "a");
+        assert_eq!(report.testcases[0].verdict, Verdict::TimeLimitExceeded);
+        assert_eq!(report.testcases[1].verdict, Verdict::Accepted);
+        assert_eq!(report.verdict(), Verdict::TimeLimitExceeded);
+        assert_eq!(report.max_elapsed(), 2.0);
+    }
+
+    #[test]
+    fn verdict_order_test() {
+        assert!(Verdict::Accepted < Verdict::WrongAnswer);
+        assert!(Verdict::WrongAnswer < Verdict::TimeLimitExceeded);
+        let report = TestReport { testcases: vec![] };
+        assert_eq!(report.verdict(), Verdict::Accepted);
+    }
+
+    fn summaries() -> Vec<SolverSummary> {
+        let testcase = |name: &str, verdict, elapsed| TestcaseResult {
+            name: name.to_string(),
+            verdict,
+            elapsed,
+        };
+        vec![
+            SolverSummary {
+                solver_path: PathBuf::from("algo/a/examples/a.rs"),
+                problem_url: "https://example.com/a".to_string(),
+                result: Ok(TestReport {
+                    testcases: vec![
+                        testcase("1", Verdict::Accepted, 0.5),
+                        testcase("2", Verdict::Accepted, 0.25),
+                    ],
+                }),
+            },
+            SolverSummary {
+                solver_path: PathBuf::from("algo/b/examples/b.rs"),
+                problem_url: "https://example.com/b".to_string(),
+                result: Ok(TestReport {
+                    testcases: vec![testcase("1", Verdict::WrongAnswer, 0.1)],
+                }),
+            },
+            SolverSummary {
+                solver_path: PathBuf::from("algo/c/examples/c.rs"),
+                problem_url: "https://example.com/c".to_string(),
+                result: Err("failed: oj download".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn summary_json_test() {
+        let json: Value = serde_json::from_str(&summary_json(&summaries())).unwrap();
+        assert_eq!(json["total"], 3);
+        assert_eq!(json["accepted"], 1);
+        let solvers = json["solvers"].as_array().unwrap();
+        let verdicts = solvers
+            .iter()
+            .map(|s| s["verdict"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(verdicts, vec!["AC", "WA", "ERROR"]);
+        assert_eq!(solvers[0]["testcases"], 2);
+        assert_eq!(solvers[0]["max_elapsed"], 0.5);
+        assert_eq!(solvers[0].get("error"), None);
+        assert_eq!(solvers[2]["error"], "failed: oj download");
+    }
+
+    #[test]
+    fn summary_markdown_test() {
+        let markdown = summary_markdown(&summaries());
+        assert!(markdown.contains("1 / 3 solvers accepted"));
+        assert!(markdown
+            .contains("| `algo/a/examples/a.rs` | https://example.com/a | AC | 2 | 0.500s |"));
+        assert!(
+            markdown.contains("| `algo/c/examples/c.rs` | https://example.com/c | ERROR | - | - |")
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context, Result};
+use glob::glob;
+
+/// `crates[0]` の `examples/` に、`problem_url` を検証する解答のひな形を作って、そのパスを返します。
+///
+/// ひな形は `// problem:` の行、proconio の `input!`、`crates` の `use` からなります。
+/// `crates[0]` 以外のクレートと proconio は、`crates[0]` の `Cargo.toml` の dev-dependencies に足します。
+/// ファイル名は `name` か、指定しなければ問題の URL から決めます。同じ名前の example があるときはエラーです。
+pub fn new_example(
+    workspace_root: &Path,
+    problem_url: &str,
+    crates: &[String],
+    name: Option<&str>,
+) -> Result<PathBuf> {
+    ensure!(!crates.is_empty(), "no crates specified");
+    for c in crates {
+        ensure!(
+            workspace_root
+                .join("algo")
+                .join(c)
+                .join("Cargo.toml")
+                .exists(),
+            "unknown crate: {}",
+            c
+        );
+    }
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => example_name(problem_url).with_context(|| {
+            format!(
+                "cannot decide example name from {}; use --name",
+                problem_url
+            )
+        })?,
+    };
+    // 同じ名前の example があると cargo build --example で区別できない
+    let pattern = workspace_root.join("**").join(format!("{}.rs", name));
+    for entry in glob(&pattern.to_string_lossy())? {
+        let path = entry?;
+        if path
+            .parent()
+            .map_or(false, |p| p.ends_with("examples") || p.ends_with("verify"))
+        {
+            bail!("example {} already exists: {:?}", name, path);
+        }
+    }
+
+    let crate_dir = workspace_root.join("algo").join(&crates[0]);
+    let manifest_path = crate_dir.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)?;
+    fs::write(
+        &manifest_path,
+        add_dev_dependencies(&manifest, &crates[1..]),
+    )?;
+
+    let source_path = crate_dir.join("examples").join(format!("{}.rs", name));
+    fs::create_dir_all(source_path.parent().unwrap())?;
+    fs::write(&source_path, example_source(problem_url, crates))?;
+    Ok(source_path)
+}
+
+// https://judge.yosupo.jp/problem/point_add_range_sum -> point_add_range_sum
+// https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_3_A -> dsl_3_a
+// https://atcoder.jp/contests/abc100/tasks/abc100_a -> abc100_a
+// https://yukicoder.me/problems/no/1 -> yukicoder_1
+fn example_name(problem_url: &str) -> Option<String> {
+    let name = if let Some((_, id)) = problem_url.split_once("?id=") {
+        let id = id.split('&').next().unwrap();
+        // AOJ の数字だけの問題は aoj_0560 のようにする
+        if id.chars().all(|c| c.is_ascii_digit()) {
+            format!("aoj_{}", id)
+        } else {
+            id.to_string()
+        }
+    } else {
+        let last = problem_url.trim_end_matches('/').rsplit('/').next()?;
+        if problem_url.contains("yukicoder.me") {
+            format!("yukicoder_{}", last)
+        } else {
+            last.to_string()
+        }
+    };
+    let name = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+fn example_source(problem_url: &str, crates: &[String]) -> String {
+    let mut uses = crates
+        .iter()
+        .map(|c| format!("use {}::*;", c))
+        .chain(std::iter::once("use proconio::input;".to_string()))
+        .collect::<Vec<_>>();
+    uses.sort();
+    format!(
+        "// problem: {}\n{}\n\nfn main() {{\n    input! {{\n        n: usize,\n    }}\n\n    todo!(\"{{}}\", n);\n}}\n",
+        problem_url,
+        uses.join("\n")
+    )
+}
+
+// 足りない dev-dependencies を [dev-dependencies] の最後に足す
+fn add_dev_dependencies(manifest: &str, crates: &[String]) -> String {
+    let declared = |name: &str| {
+        manifest.lines().any(|l| {
+            l.split_once('=')
+                .map_or(false, |(key, _)| key.trim() == name)
+        })
+    };
+    let mut lines = Vec::new();
+    if !declared("proconio") {
+        lines.push("proconio = { version = \"0.4.5\" }".to_string());
+    }
+    for c in crates {
+        if !declared(c) {
+            lines.push(format!("{} = {{ path = \"../{}\" }}", c, c));
+        }
+    }
+    if lines.is_empty() {
+        return manifest.to_string();
+    }
+
+    let mut result = manifest.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+    match result.iter().position(|l| l.trim() == "[dev-dependencies]") {
+        Some(start) => {
+            let mut end = result[start + 1..]
+                .iter()
+                .position(|l| l.trim_start().starts_with('['))
+                .map_or(result.len(), |i| start + 1 + i);
+            // 次のセクションとの間の空行の前に足す
+            while end > start + 1 && result[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            result.splice(end..end, lines);
+        }
+        None => {
+            result.push(String::new());
+            result.push("[dev-dependencies]".to_string());
+            result.extend(lines);
+        }
+    }
+    result.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{add_dev_dependencies, example_name};
+
+    #[test]
+    fn example_name_test() {
+        assert_eq!(
+            example_name("https://judge.yosupo.jp/problem/point_add_range_sum").as_deref(),
+            Some("point_add_range_sum")
+        );
+        assert_eq!(
+            example_name("https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=DSL_3_A")
+                .as_deref(),
+            Some("dsl_3_a")
+        );
+        assert_eq!(
+            example_name("https://judge.u-aizu.ac.jp/onlinejudge/description.jsp?id=0560")
+                .as_deref(),
+            Some("aoj_0560")
+        );
+        assert_eq!(
+            example_name("https://atcoder.jp/contests/abc100/tasks/abc100_a").as_deref(),
+            Some("abc100_a")
+        );
+        assert_eq!(
+            example_name("https://yukicoder.me/problems/no/1/").as_deref(),
+            Some("yukicoder_1")
+        );
+    }
+
+    #[test]
+    fn add_dev_dependencies_test() {
+        let manifest = r#"[package]
+name = "inversion_count"
+
+[dependencies]
+fenwick_tree = { path = "../fenwick_tree" }
+
+[dev-dependencies]
+rand = "0.7"
+
+[[example]]
+name = "a"
+"#;
+        assert_eq!(
+            add_dev_dependencies(manifest, &["fenwick_tree".to_string(), "zarts".to_string()]),
+            r#"[package]
+name = "inversion_count"
+
+[dependencies]
+fenwick_tree = { path = "../fenwick_tree" }
+
+[dev-dependencies]
+rand = "0.7"
+proconio = { version = "0.4.5" }
+zarts = { path = "../zarts" }
+
+[[example]]
+name = "a"
+"#
+        );
+
+        let manifest = "[package]\nname = \"a\"\n\n[dependencies]\n";
+        assert_eq!(
+            add_dev_dependencies(manifest, &[]),
+            "[package]\nname = \"a\"\n\n[dependencies]\n\n[dev-dependencies]\nproconio = { version = \"0.4.5\" }\n"
+        );
+
+        let manifest = "[dev-dependencies]\nproconio = \"0.4.5\"\n";
+        assert_eq!(add_dev_dependencies(manifest, &[]), manifest);
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::bundle::{bundle_crate, library_crates};
+
+#[derive(Serialize)]
+struct Snippet {
+    prefix: String,
+    body: Vec<String>,
+    description: String,
+}
+
+/// ワークスペースのライブラリクレートを VSCode のスニペットの形式 (JSON) にします。
+///
+/// プレフィックスはクレート名で、本文は `bundle` と同じ `mod クレート名 { ... }` です。依存先のクレートは含まないので、説明に書いておきます。
+pub fn snippets(workspace_root: &Path) -> Result<String> {
+    let mut snippets = BTreeMap::new();
+    for (name, library) in library_crates(workspace_root)? {
+        let body = bundle_crate(&name, &library)?;
+        let mut description = format!("{} (rust-competitive-programming)", name);
+        if !library.dependencies.is_empty() {
+            description.push_str(&format!(", requires {}", library.dependencies.join(", ")));
+        }
+        let snippet = Snippet {
+            prefix: name.clone(),
+            body: body.lines().map(escape_snippet).collect(),
+            description,
+        };
+        snippets.insert(name, snippet);
+    }
+    Ok(serde_json::to_string(&snippets)?)
+}
+
+// スニペットの本文では $ がプレースホルダの意味になる
+fn escape_snippet(line: &str) -> String {
+    line.replace('\\', "\\\\").replace('$', "\\$")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_snippet_test() {
+        assert_eq!(escape_snippet("$crate::out!($w)"), "\\$crate::out!(\\$w)");
+        assert_eq!(escape_snippet("b'\\n'"), "b'\\\\n'");
+    }
+
+    #[test]
+    fn snippets_test() {
+        let workspace_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("..");
+        let json: serde_json::Value =
+            serde_json::from_str(&snippets(&workspace_root).unwrap()).unwrap();
+        let snippet = &json["inversion_count"];
+        assert_eq!(snippet["prefix"], "inversion_count");
+        let body = snippet["body"].as_array().unwrap();
+        assert!(body.iter().any(|l| l == "pub mod inversion_count {"));
+        assert!(snippet["description"]
+            .as_str()
+            .unwrap()
+            .contains("fenwick_tree"));
+    }
+}
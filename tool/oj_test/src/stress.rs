@@ -0,0 +1,106 @@
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use anyhow::{ensure, Context, Result};
+use log::info;
+
+use crate::download::fnv1a;
+use crate::{build_example, example_binary_path, ProblemSolver};
+
+/// ランダムテストで見つかった、出力が食い違う入力です。
+#[derive(Debug)]
+pub struct StressFailure {
+    pub seed: u64,
+    /// 入力を保存したファイル
+    pub input_path: PathBuf,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// `// generator_rs:` で指定したジェネレータで `cases` 個の入力を作り、解答と `// naive_rs:` で指定した愚直解の出力を比べます。
+///
+/// ジェネレータはシード値をコマンドライン引数で受け取り、入力を標準出力に書くプログラムです。
+/// 出力が食い違う入力が見つかったら、それを一時ディレクトリに保存して返します。
+pub fn stress_test(solver: &ProblemSolver, cases: u64) -> Result<Option<StressFailure>> {
+    let generator = solver
+        .generator_path()
+        .context("generator_rs is not specified")?;
+    let naive = solver.naive_path().context("naive_rs is not specified")?;
+    for source in [solver.solver_path(), generator.as_path(), naive.as_path()] {
+        build_example(source)?;
+    }
+    let generator = example_binary_path(generator.as_path());
+    let naive = example_binary_path(naive.as_path());
+    let solver_binary = example_binary_path(solver.solver_path());
+
+    for seed in 0..cases {
+        let input = execute(&generator, &[seed.to_string()], "")?;
+        let expected = execute(&naive, &[], &input)?;
+        let actual = execute(&solver_binary, &[], &input)?;
+        if !same_output(&expected, &actual) {
+            // 解答のパスが絶対パスでも temp_dir の下に収まるように、名前とハッシュにする
+            let path = solver.solver_path();
+            let dir = env::temp_dir().join("oj_test_stress").join(format!(
+                "{}-{:016x}",
+                path.file_stem().unwrap().to_string_lossy(),
+                fnv1a(path.to_string_lossy().as_bytes())
+            ));
+            fs::create_dir_all(&dir)?;
+            let input_path = dir.join(format!("seed_{}.in", seed));
+            fs::write(&input_path, &input)?;
+            return Ok(Some(StressFailure {
+                seed,
+                input_path,
+                expected,
+                actual,
+            }));
+        }
+    }
+    info!("{}: passed {} random cases", solver, cases);
+    Ok(None)
+}
+
+fn execute(binary: &Path, args: &[String], input: &str) -> Result<String> {
+    let mut child = Command::new(binary)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to execute {:?}", binary))?;
+    // 出力を読む前に入力を全部書くと、パイプが詰まって止まることがある
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_string();
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+    // 入力を読み切らずに終了するプログラムもあるので、書き込みの失敗は無視する
+    let _ = writer.join();
+    ensure!(
+        output.status.success(),
+        "{:?} exited with {}",
+        binary,
+        output.status
+    );
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+// 空白の違いは無視する
+fn same_output(expected: &str, actual: &str) -> bool {
+    expected.split_whitespace().eq(actual.split_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::same_output;
+
+    #[test]
+    fn same_output_test() {
+        assert!(same_output("1 2\n3\n", "1 2 3"));
+        assert!(same_output("", "\n"));
+        assert!(!same_output("1 2", "1 3"));
+        assert!(!same_output("1 2", "1 2 3"));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use glob::glob;
+
+use crate::changed::affected_package_dirs_by_files;
+use crate::ProblemSolver;
+
+// 監視するファイル (ワークスペースのルートからの相対パス)
+const PATTERNS: [&str; 4] = [
+    "algo/*/Cargo.toml",
+    "algo/*/**/*.rs",
+    "tool/oj_test_examples/*/Cargo.toml",
+    "tool/oj_test_examples/*/**/*.rs",
+];
+
+/// ライブラリのクレートと解答のファイルの変更を、更新時刻を定期的に見て検出します。
+pub struct FileWatcher {
+    workspace_root: PathBuf,
+    snapshot: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl FileWatcher {
+    pub fn new(workspace_root: &Path) -> Result<Self> {
+        // cargo metadata のパスと比べられるように、絶対パスにしておく
+        let workspace_root = workspace_root
+            .canonicalize()
+            .with_context(|| format!("failed to canonicalize {:?}", workspace_root))?;
+        let snapshot = take_snapshot(&workspace_root)?;
+        Ok(Self {
+            workspace_root,
+            snapshot,
+        })
+    }
+
+    /// ファイルが変更 (追加と削除も含む) されるまで `interval` ごとに確かめて、変更されたファイルを返します。
+    pub fn wait_for_changes(&mut self, interval: Duration) -> Result<Vec<PathBuf>> {
+        loop {
+            thread::sleep(interval);
+            let snapshot = take_snapshot(&self.workspace_root)?;
+            let changed = changed_files(&self.snapshot, &snapshot);
+            self.snapshot = snapshot;
+            if !changed.is_empty() {
+                return Ok(changed);
+            }
+        }
+    }
+}
+
+fn take_snapshot(workspace_root: &Path) -> Result<BTreeMap<PathBuf, SystemTime>> {
+    let mut snapshot = BTreeMap::new();
+    for pattern in PATTERNS {
+        for entry in glob(&workspace_root.join(pattern).to_string_lossy())? {
+            let path = entry?;
+            // 消えたばかりのファイルは次の確認で削除として扱う
+            if let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) {
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+    Ok(snapshot)
+}
+
+fn changed_files(
+    old: &BTreeMap<PathBuf, SystemTime>,
+    new: &BTreeMap<PathBuf, SystemTime>,
+) -> Vec<PathBuf> {
+    let mut changed = new
+        .iter()
+        .filter(|(path, modified)| old.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .chain(old.keys().filter(|path| !new.contains_key(*path)).cloned())
+        .collect::<Vec<_>>();
+    changed.sort();
+    changed
+}
+
+/// `changed_files` (絶対パス) の影響を受ける解答を返します。
+///
+/// 解答のファイルが変更されたときはその解答だけ、ライブラリのクレートが変更されたときはそれに依存するパッケージの解答全てです。
+pub fn affected_solvers<'a>(
+    solvers: &'a [ProblemSolver],
+    changed_files: &[PathBuf],
+) -> Result<Vec<&'a ProblemSolver>> {
+    let solver_paths = solvers
+        .iter()
+        .map(|s| s.solver_path().canonicalize().ok())
+        .collect::<Vec<_>>();
+    let is_solver = |file: &PathBuf| solver_paths.iter().any(|p| p.as_ref() == Some(file));
+    let library_files = changed_files
+        .iter()
+        .filter(|f| !is_solver(f))
+        .cloned()
+        .collect::<Vec<_>>();
+    let package_dirs = if library_files.is_empty() {
+        Some(Vec::new())
+    } else {
+        affected_package_dirs_by_files(&library_files)?
+    };
+    Ok(solvers
+        .iter()
+        .zip(&solver_paths)
+        .filter(|(_, path)| {
+            let Some(path) = path else {
+                return false;
+            };
+            changed_files.contains(path)
+                || package_dirs
+                    .as_ref()
+                    .map_or(true, |dirs| dirs.iter().any(|d| path.starts_with(d)))
+        })
+        .map(|(s, _)| s)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{affected_solvers, changed_files};
+    use crate::{workspace_root, ProblemSolver};
+
+    #[test]
+    fn changed_files_test() {
+        let t = SystemTime::UNIX_EPOCH;
+        let old = [("a", t), ("b", t), ("c", t)]
+            .into_iter()
+            .map(|(p, t)| (PathBuf::from(p), t))
+            .collect::<BTreeMap<_, _>>();
+        let new = [("a", t), ("c", t + Duration::from_secs(1)), ("d", t)]
+            .into_iter()
+            .map(|(p, t)| (PathBuf::from(p), t))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            changed_files(&old, &new),
+            vec![PathBuf::from("b"), PathBuf::from("c"), PathBuf::from("d")]
+        );
+        assert!(changed_files(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn affected_solvers_test() {
+        let root = workspace_root().canonicalize().unwrap();
+        let solvers = [
+            ProblemSolver::new(&root.join("algo/fenwick_tree/examples/point_add_range_sum.rs")),
+            ProblemSolver::new(&root.join("algo/union_find/examples/unionfind.rs")),
+        ];
+        let paths = |files: &[&str]| {
+            let files = files.iter().map(|f| root.join(f)).collect::<Vec<_>>();
+            affected_solvers(&solvers, &files)
+                .unwrap()
+                .iter()
+                .map(|s| s.solver_path().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            paths(&["algo/union_find/examples/unionfind.rs"]),
+            vec![solvers[1].solver_path()]
+        );
+        assert_eq!(
+            paths(&["algo/union_find/src/lib.rs"]),
+            vec![solvers[1].solver_path()]
+        );
+        // join は両方の解答が使っている
+        assert_eq!(
+            paths(&["algo/join/src/lib.rs"]),
+            vec![solvers[0].solver_path(), solvers[1].solver_path()]
+        );
+        assert_eq!(paths(&["algo/zarts/src/lib.rs"]), Vec::<PathBuf>::new());
+    }
+}
@@ -1,11 +1,11 @@
 use std::path::Path;
 
-use oj_test::ProblemSolver;
+use oj_test::{stress_test, ProblemSolver, Verdict};
 
 #[test]
 #[ignore]
 fn local_testcase() {
-    env_logger::init();
+    let _ = env_logger::try_init();
 
     let solver = ProblemSolver::new(
         Path::new(std::env!("CARGO_MANIFEST_DIR"))
@@ -17,5 +17,22 @@ fn local_testcase() {
         .join("tests")
         .join("hand-made");
 
-    solver.run(testcase_dir.as_path()).unwrap();
+    let report = solver.run(testcase_dir.as_path()).unwrap();
+    assert_eq!(report.verdict(), Verdict::Accepted);
+}
+
+#[test]
+#[ignore]
+fn stress() {
+    let _ = env_logger::try_init();
+
+    let solver = ProblemSolver::new(
+        Path::new(std::env!("CARGO_MANIFEST_DIR"))
+            .join("examples")
+            .join("double.rs")
+            .as_path(),
+    );
+
+    let failure = stress_test(&solver, 20).unwrap();
+    assert!(failure.is_none(), "{:?}", failure);
 }